@@ -1,23 +1,36 @@
 use anyhow::Result;
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::prelude::*;
 use ratatui::widgets::ListState;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
 use tui_textarea::{Input, TextArea};
+use unicode_width::UnicodeWidthStr;
 
+use crate::config::{Config, LoadingStyle, MergeMethod};
+use crate::git;
 use crate::event::{Event, EventHandler};
-use crate::github::types::{Commit, Job, PullRequest, RecentBranch, Review, WorkflowRun};
-use crate::github::Client;
+use crate::github::dispatch::DispatchInput;
+use crate::github::types::{
+    aggregate_ci_status, latest_reviews, Artifact, Check, Commit, Job, Notification, PendingDeployment, PullRequest, RecentBranch, Review, RunFilter, RunStatusFilter, Workflow, WorkflowRun, RUN_EVENTS,
+};
+use crate::github::{Client, MAX_PR_PAGES, RUN_PAGE_SIZE};
+use crate::keymap::{Action, KeyMap, Scope};
 use crate::ui;
-use crate::ui::MatrixRain;
+use crate::ui::{MatrixRain, Theme};
+use crate::util;
 
-#[derive(Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Tab {
     #[default]
     PRs,
     Actions,
     Logs,
+    Notifications,
 }
 
 #[derive(Default, Clone, Copy, PartialEq, Eq)]
@@ -33,16 +46,48 @@ pub enum View {
 pub enum Focus {
     #[default]
     List,
+    Description,
     Detail,
     PrChecks,
 }
 
-#[derive(Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum PrFilter {
     #[default]
     All,
     Mine,
     ReviewRequested,
+    Closed,
+}
+
+/// How long a PR changed by a refresh stays marked in the list
+const UPDATED_MARKER: Duration = Duration::from_secs(15);
+
+/// Order of the PR list, applied after filtering
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub enum PrSort {
+    #[default]
+    Created,  // Newest first, as the API returns them
+    Updated,  // Most recently updated first
+    Number,  // Highest number first
+    Title,  // Alphabetical
+}
+
+/// A row of the CI checks panel
+pub enum CheckRow {
+    /// Header of a workflow with several runs, over this range of `pr_checks`
+    Group(std::ops::Range<usize>),
+    /// A single check; `grouped` ones sit under a header
+    Check { index: usize, grouped: bool },
+}
+
+/// How the full diff view lays out changes
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLayout {
+    #[default]
+    Unified,
+    Split,  // Old and new side by side
 }
 
 #[derive(Default, Clone, Copy, PartialEq, Eq)]
@@ -55,16 +100,37 @@ pub enum DiffMode {
 // Messages for async operations
 pub enum AsyncMsg {
     UserLoaded(String),
-    PrsLoaded(Vec<PullRequest>),
+    UserTeamsLoaded(Vec<String>),
+    PrsLoaded(Vec<PullRequest>, Option<String>),  // With a warning when only some pages loaded
+    ClosedPrsLoaded(Vec<PullRequest>, Option<String>),
     RecentBranchLoaded(Option<RecentBranch>),
+    MergeableLoaded(u64, Option<bool>),
     RunsLoaded(Vec<WorkflowRun>),
-    DiffLoaded(String),
-    PrChecksLoaded(Vec<WorkflowRun>),
-    ReviewsLoaded(Vec<Review>),
-    JobsLoaded(Vec<Job>),
+    MoreRunsLoaded(u32, Vec<WorkflowRun>),
+    RunsPolled(Vec<WorkflowRun>),
+    DiffLoaded(u64, String),
+    PrChecksLoaded(String, Vec<Check>),
+    CiStatusLoaded(String, Option<String>),
+    ReviewsLoaded(u64, Vec<Review>),
+    AutoMergeLoaded(u64, Option<String>),
+    RequiredApprovalsLoaded(String, Option<u32>),
+    CheckedOut(u64, String),
+    JobsLoaded(u64, Vec<Job>),  // Tagged with the run ID, so results for another run are dropped
+    JobsFollowed(u64, Vec<Job>),
+    BatchDone(String, Vec<u64>),  // Summary and the PRs that failed
+    ArtifactsLoaded(u64, Vec<Artifact>),
+    PendingDeploymentsLoaded(u64, Vec<PendingDeployment>),
     LogsLoaded(String),
-    CommitsLoaded(Vec<Commit>),
-    CommitDiffLoaded(String),
+    LogsFollowed(u64, u64, String),  // Run and job the log belongs to
+    LogsRefreshed(String),
+    CommitsLoaded(u64, Vec<Commit>),
+    CommitCiLoaded(String, Option<String>),
+    CommitDiffLoaded(String, String),
+    NotificationsLoaded(Vec<Notification>),
+    WorkflowsLoaded(Vec<Workflow>, String),
+    DispatchInputsLoaded(Option<Vec<DispatchInput>>),
+    WorkflowDispatched(String),
+    NotificationRead(String),
     Error(String),
     Message(String),
 }
@@ -78,20 +144,41 @@ pub struct App {
     pub owner: String,
     pub repo_name: String,
     pub current_user: Option<String>,
+    user_teams: HashSet<String>,  // Lowercase `org/slug` of the current user's teams
 
     // PR state
     pub all_prs: Vec<PullRequest>,  // All PRs from API
+    pub closed_prs: Vec<PullRequest>,  // Closed/merged PRs, fetched on demand
     pub prs: Vec<PullRequest>,       // Filtered PRs for display
     pub recent_branch: Option<RecentBranch>,  // Recently pushed branch without a PR
     pub pr_list_state: ListState,
     pub selected_pr: Option<PullRequest>,
+    pub selected_prs: HashSet<u64>,  // Marked for batch approve/merge
+    pr_generation: u64,  // Bumped on every selection; tags PR fetches so stale results are dropped
     pub pr_diff: Option<String>,
+    pub pr_diff_stat: Option<DiffStat>,  // Computed once when the diff loads
+    pub pr_diff_view: ui::RenderedDiff,  // pr_diff styled for display
+    pub commit_diff_view: ui::RenderedDiff,  // commit_diff styled for display
+    pub diff_search: Option<String>,  // Lines containing it are highlighted in diffs
+    diff_full: bool,  // Style large diffs of this PR too
+    pub viewed_files: HashMap<u64, HashSet<String>>,  // Per PR, for this session only
+    pub diff_layout: DiffLayout,
+    pub diff_ignore_ws: bool,  // Hide hunks that only change whitespace
+    pub diff_width: u16,  // Width of the full diff pane at the last draw
     pub pr_filter: PrFilter,
+    pub pr_sort: PrSort,
+    pub pr_query: String,  // Typed filter over title, author and branch; empty for none
+    pub updated_prs: HashMap<u64, Instant>,  // PRs changed by the last refresh, until their marker expires
     pub diff_scroll: u16,
+    pub diff_height: u16,  // Full diff lines that fit on screen at the last draw
+    pub body_scroll: u16,
+    pub mouse_areas: MouseAreas,
+    pub body_area: (u16, u16),  // Width and height of the description text at the last draw
 
     // PR checks (workflow runs for selected PR)
-    pub pr_checks: Vec<WorkflowRun>,
-    pub pr_checks_state: ListState,
+    pub pr_checks: Vec<Check>,
+    pub pr_checks_state: ListState,  // Selects a row of `pr_check_rows()`
+    pub collapsed_workflows: HashSet<String>,  // Workflow names whose runs are hidden under their header
 
     // PR reviews (approval status)
     pub pr_reviews: Vec<Review>,
@@ -100,32 +187,90 @@ pub struct App {
     pub diff_mode: DiffMode,
     pub pr_commits: Vec<Commit>,
     pub pr_commits_state: ListState,
+    pub pr_auto_merge: Option<String>,  // Merge method of the selected PR's pending auto-merge
+    pub required_approvals: HashMap<String, Option<u32>>,  // By base branch, from branch protection
+    pub local_head: Option<git::LocalHead>,  // Branch checked out in the working copy
+    merged_branch: Option<String>,  // Head branch of the PR just merged, until it's deleted or kept
+    pub commit_ci: HashMap<String, Option<String>>,  // Aggregated CI status by commit SHA
+    commits_loaded: bool,  // Commits are fetched once per PR, when the commit view opens
+    reviews_loaded: bool,  // Reviews are fetched once per PR
+    reviews_known: bool,  // The reviews of this PR have arrived; until then its review state is unknown
     pub commit_diff: Option<String>,
 
     // Actions state
     pub runs: Vec<WorkflowRun>,
     pub run_list_state: ListState,
+    pub runs_page: u32,             // Last page of runs fetched (1-based)
+    pub runs_loading_more: bool,    // A next-page fetch is in flight
+    pub runs_exhausted: bool,       // Last page fetched was short - nothing more to load
+    runs_reloading: bool,           // reload_runs is waiting for the first page; paging is off
+    pub run_filter: RunFilter,
     pub selected_run: Option<WorkflowRun>,
     pub jobs: Vec<Job>,
     pub job_list_state: ListState,
+    pub artifacts: Vec<Artifact>,
+    pub artifact_list_state: ListState,
+    pub artifacts_focused: bool,  // j/k move through artifacts instead of jobs
+    pub expanded_jobs: HashSet<u64>,  // Jobs whose steps are listed under them
+    pub pending_deployments: Vec<PendingDeployment>,  // Environments the selected run waits on
+    failure_jump: bool,  // Open the failed job's logs at the first error once jobs, then logs, load
 
     // Logs state
     pub logs: String,
+    log_spans: Vec<std::ops::Range<usize>>,  // Byte range of each line in `logs`
+    log_levels: Vec<Option<ui::LineLevel>>,  // Error/warning classification of each line
     pub log_scroll: u16,
     pub log_h_scroll: u16,
+    pub log_height: u16,  // Log lines that fit on screen at the last draw
+    pub log_width: u16,  // Columns of the log pane at the last draw
     pub log_search: Option<String>,
     pub log_matches: Vec<usize>,
     pub log_match_index: usize,
+    pub log_plain: bool,  // Strip ANSI colors instead of rendering them
+    pub log_problems_only: bool,  // Show only error/warning lines and their context
+    pub log_line_numbers: bool,  // Prefix log lines with their line number
+    pub log_wrap: bool,  // Wrap long log lines instead of truncating them
+    pub log_groups: Vec<LogGroup>,
+    pub collapsed_groups: HashSet<usize>,  // Start lines of folded groups
+    log_visible: Vec<usize>,  // visible_log_lines(), rebuilt when the log, folds or filter change
+    log_pending_z: bool,  // First key of a `za` fold toggle was pressed
+    pub log_follow: bool,  // Re-fetching logs of a running job
+    selected_url: Option<String>,  // Last URL picked with next_url
+    last_log_poll: Option<Instant>,
+
+    // Notifications state
+    pub notifications: Vec<Notification>,
+    pub notification_list_state: ListState,
+    pub notifications_all_repos: bool,  // Inbox across all repos instead of the current one
+    notifications_loaded: bool,
+
+    // Auto-refresh of in-progress runs (opt-in)
+    pub auto_refresh: bool,
+    last_poll: Option<Instant>,
+    count: Option<usize>,  // Count prefix being typed, e.g. the 10 of `10j`
+    dirty: bool,  // State changed since the last draw
 
     // UI state
     pub loading: bool,
     pub loading_what: Option<String>,
+    loads: Vec<CancellationToken>,  // One per fetch behind the loading overlay, cancelled by Esc
     pub error: Option<String>,
-    pub status_message: Option<StatusMessage>,
+    pub status_messages: VecDeque<StatusMessage>,  // Oldest first, at most one prompt
     pub should_quit: bool,
     pub show_help: bool,
+    pub error_popup: bool,  // The error was too long for the footer at the last draw
+    pub error_flash_until: Option<Instant>,
+    pub error_scroll: u16,
     pub input_mode: Option<InputMode>,
     pub input_buffer: String,
+    pub pending_action: Option<PendingAction>,
+    pub label_picker: Option<ListState>,  // Open while choosing a label to remove
+    pub review_picker: Option<ListState>,  // Open while acting on the selected PR's reviews
+    dismissing_review: Option<u64>,  // Review whose dismissal message is being typed
+    pub workflows: Vec<Workflow>,
+    pub workflow_picker: Option<ListState>,  // Open while choosing a workflow to dispatch
+    default_branch: String,
+    dispatch: Option<DispatchForm>,
 
     // Description editor
     pub editing_description: bool,
@@ -133,10 +278,20 @@ pub struct App {
 
     // Matrix rain animation
     pub matrix_rain: MatrixRain,
+    pub spinner_frame: usize,  // Advances every tick while loading with the spinner style
 
-    // Initial PR to select (from CLI argument)
+    // PR to select once the list loads (from CLI argument or a `:` jump)
     pub initial_pr: Option<u64>,
 
+    // GitHub API base URL override (for GitHub Enterprise)
+    pub api_base: Option<String>,
+    pub config: Config,
+    pub keymap: KeyMap,
+    pub theme: Theme,
+
+    // System clipboard, created on first copy
+    clipboard: Option<arboard::Clipboard>,
+
     // GitHub client
     pub client: Option<Client>,
 
@@ -145,6 +300,24 @@ pub struct App {
     async_tx: Option<mpsc::UnboundedSender<AsyncMsg>>,
 }
 
+/// Screen areas of the panes at the last draw, to map mouse events onto them
+#[derive(Default, Clone, Copy)]
+pub struct MouseAreas {
+    pub pr_list: Rect,
+    pub runs: Rect,
+    pub jobs: Rect,
+    pub diff: Rect,
+    pub logs: Rect,
+}
+
+/// A workflow_dispatch being filled in: the ref, then one prompt per input
+struct DispatchForm {
+    workflow: Workflow,
+    git_ref: String,
+    inputs: Vec<DispatchInput>,
+    values: HashMap<String, String>,
+}
+
 /// Status bar message with explicit lifetime semantics
 #[derive(Clone)]
 pub enum StatusMessage {
@@ -176,6 +349,10 @@ impl StatusMessage {
         }
     }
 
+    pub fn is_prompt(&self) -> bool {
+        matches!(self, StatusMessage::Prompt(_))
+    }
+
     /// Check if this message has expired
     pub fn is_expired(&self) -> bool {
         match self {
@@ -185,23 +362,143 @@ impl StatusMessage {
     }
 }
 
+/// A foldable `##[group]` ... `##[endgroup]` section of a workflow log
+#[derive(Clone)]
+pub struct LogGroup {
+    pub start: usize,  // Line index of the ##[group] header
+    pub end: usize,    // Line index of the ##[endgroup] marker (inclusive)
+    pub name: String,
+}
+
+impl LogGroup {
+    /// Find all groups in a log. A group missing its ##[endgroup] runs until
+    /// the next group starts or the log ends.
+    pub fn parse(logs: &str) -> Vec<LogGroup> {
+        let mut groups: Vec<LogGroup> = Vec::new();
+        let mut open: Option<(usize, String)> = None;
+
+        for (i, line) in logs.lines().enumerate() {
+            if let Some(pos) = line.find("##[group]") {
+                if let Some((start, name)) = open.take() {
+                    groups.push(LogGroup { start, end: i.saturating_sub(1).max(start), name });
+                }
+                open = Some((i, line[pos + "##[group]".len()..].trim().to_string()));
+            } else if line.contains("##[endgroup]") {
+                if let Some((start, name)) = open.take() {
+                    groups.push(LogGroup { start, end: i, name });
+                }
+            }
+        }
+
+        if let Some((start, name)) = open {
+            let last = logs.lines().count().saturating_sub(1);
+            groups.push(LogGroup { start, end: last.max(start), name });
+        }
+
+        groups
+    }
+}
+
+/// Size of a diff: files touched and added/removed line counts
+#[derive(Clone, Copy, Default)]
+pub struct DiffStat {
+    pub files: usize,
+    pub additions: usize,
+    pub deletions: usize,
+}
+
+impl DiffStat {
+    /// Count the same lines `render_diff_lines` shows as files, additions and removals
+    pub fn parse(diff: &str) -> DiffStat {
+        let mut stat = DiffStat::default();
+        for line in diff.lines() {
+            if line.starts_with("diff --git ") {
+                stat.files += 1;
+            } else if line.starts_with("+++") || line.starts_with("---") {
+                // File path lines, not content
+            } else if line.starts_with('+') {
+                stat.additions += 1;
+            } else if line.starts_with('-') {
+                stat.deletions += 1;
+            }
+        }
+        stat
+    }
+
+    pub fn summary(&self) -> String {
+        let files = if self.files == 1 { "file" } else { "files" };
+        format!("{} {} · +{} −{}", self.files, files, self.additions, self.deletions)
+    }
+}
+
+/// Destructive action awaiting a y/n confirmation
+#[derive(Clone, PartialEq, Eq)]
+pub enum PendingAction {
+    // With what speaks against merging that GitHub may still allow
+    MergePr(u64, MergeMethod, Vec<&'static str>),
+    ClosePr(u64),
+    UpdateBranch(u64),
+    EnableAutoMerge(u64, MergeMethod),
+    // The branch is in `merged_branch`
+    DeleteBranch(u64),
+    SearchClosed(u64),
+    // The PRs come from `selected_prs`; the count is for the prompt
+    ApproveSelected(usize),
+    MergeSelected(usize, MergeMethod),
+}
+
+impl PendingAction {
+    /// Question shown in the confirmation overlay
+    pub fn prompt(&self) -> String {
+        match self {
+            PendingAction::MergePr(number, method, warnings) if warnings.is_empty() => {
+                format!("Merge PR #{} ({})? (y/n)", number, method.as_str())
+            }
+            PendingAction::MergePr(number, method, warnings) => {
+                format!("Merge PR #{} ({}) despite {}? (y/n)", number, method.as_str(), warnings.join(", "))
+            }
+            PendingAction::ClosePr(number) => format!("Close PR #{}? (y/n)", number),
+            PendingAction::UpdateBranch(number) => format!("Merge the base branch into PR #{}? (y/n)", number),
+            PendingAction::DeleteBranch(number) => format!("PR #{} merged. Delete its branch? (y/n)", number),
+            PendingAction::EnableAutoMerge(number, method) => {
+                format!("Auto-merge PR #{} ({}) once it can be merged? (y/n)", number, method.as_str())
+            }
+            PendingAction::SearchClosed(number) => format!("PR #{} is not open. Search closed PRs? (y/n)", number),
+            PendingAction::ApproveSelected(count) => format!("Approve {} selected PRs? (y/n)", count),
+            PendingAction::MergeSelected(count, method) => {
+                format!("Merge {} selected PRs ({})? (y/n)", count, method.as_str())
+            }
+        }
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum InputMode {
     Search,
+    GotoLine,
     Comment,
+    ApproveComment,
+    DismissReview,
     EditTitle,
     AddLabel,
     AddReviewer,
+    AddAssignee,
+    Command,
+    DispatchRef,
+    DispatchInput,
+    FilterPrs,
+    SearchDiff,
+    ApproveDeployment,
+    RejectDeployment,
 }
 
 impl App {
     pub fn new(repo: String) -> Self {
-        let parts: Vec<&str> = repo.split('/').collect();
-        let (owner, repo_name) = if parts.len() == 2 {
-            (parts[0].to_string(), parts[1].to_string())
-        } else {
-            ("shopsys".to_string(), "shopsys".to_string())
-        };
+        // main() has already checked the owner/repo format
+        let (owner, repo_name) = repo
+            .split_once('/')
+            .map(|(owner, name)| (owner.to_string(), name.to_string()))
+            .unwrap_or_default();
 
         let (tx, rx) = mpsc::unbounded_channel();
 
@@ -216,13 +513,14 @@ impl App {
             job_list_state: ListState::default(),
             async_rx: Some(rx),
             async_tx: Some(tx),
+            local_head: git::local_head(),
             ..Default::default()
         }
     }
 
     pub async fn run(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
         // Initialize GitHub client
-        self.client = Some(Client::new().await?);
+        self.client = Some(Client::new(self.api_base.clone()).await?);
 
         // Initial data fetch (async)
         self.loading = true;
@@ -230,57 +528,132 @@ impl App {
         terminal.draw(|f| ui::render(f, self))?;
 
         self.spawn_fetch_current_user();
+        self.spawn_fetch_user_teams();
         self.spawn_fetch_prs();
         self.spawn_fetch_runs();
-
-        // Event loop
-        let mut events = EventHandler::new(Duration::from_millis(100));
+        // Restored from the last session; closed PRs are otherwise fetched when it's picked
+        if self.pr_filter == PrFilter::Closed {
+            self.spawn_fetch_closed_prs();
+        }
+        self.load_notifications_once();
+
+        // Event loop. Ticks run at `tick_ms` while idle, as input and results wake
+        // it on their own; the loading animation needs them at least every
+        // ANIMATION_TICK to look smooth.
+        const ANIMATION_TICK: Duration = Duration::from_millis(100);
+        const IDLE_TICK: Duration = Duration::from_millis(250);
+        let idle_tick = self.config.tick_ms.map_or(IDLE_TICK, Duration::from_millis);
+        let mut events = EventHandler::new(idle_tick);
+        // Relative times ("5m ago") age even when nothing else changes
+        const IDLE_REDRAW: Duration = Duration::from_secs(30);
+        let mut last_draw = Instant::now();
+        // Errors can arrive while looking elsewhere; alert once per new one
+        const ERROR_FLASH: Duration = Duration::from_millis(500);
+        let mut alerted_error: Option<String> = None;
 
         while !self.should_quit {
             // Process async messages
-            self.process_async_messages();
+            self.process_async_messages(None);
+
+            if self.error != alerted_error {
+                if self.error.is_some() {
+                    if self.config.error_alert.rings() {
+                        use std::io::Write;
+                        let mut stdout = std::io::stdout();
+                        let _ = stdout.write_all(b"\x07").and_then(|_| stdout.flush());
+                    }
+                    if self.config.error_alert.flashes() {
+                        self.error_flash_until = Some(Instant::now() + ERROR_FLASH);
+                        self.dirty = true;
+                    }
+                }
+                alerted_error = self.error.clone();
+            }
+            if self.error_flash_until.is_some_and(|until| until <= Instant::now()) {
+                self.error_flash_until = None;
+                self.dirty = true;
+            }
 
             // Auto-dismiss expired status messages BEFORE drawing
-            if let Some(ref msg) = self.status_message {
-                if msg.is_expired() {
-                    self.status_message = None;
-                }
+            let shown = self.status_messages.len();
+            self.status_messages.retain(|msg| !msg.is_expired());
+            if self.status_messages.len() != shown {
+                self.dirty = true;
+            }
+            let marked = self.updated_prs.len();
+            let now = Instant::now();
+            self.updated_prs.retain(|_, expires_at| *expires_at > now);
+            if self.updated_prs.len() != marked {
+                self.dirty = true;
             }
 
-            terminal.draw(|f| ui::render(f, self))?;
+            // Only repaint on changes; the loading animation needs every frame
+            let animating = self.loading && matches!(self.config.loading_style, LoadingStyle::Matrix | LoadingStyle::Spinner);
+            let fast = animating || self.error_flash_until.is_some();
+            events.set_tick_rate(if fast { idle_tick.min(ANIMATION_TICK) } else { idle_tick });
+            // Durations of running workflows and jobs count up every second
+            let redraw_after = if self.shows_running_durations() { Duration::from_secs(1) } else { IDLE_REDRAW };
+            if self.dirty || animating || last_draw.elapsed() >= redraw_after {
+                terminal.draw(|f| ui::render(f, self))?;
+                self.dirty = false;
+                last_draw = Instant::now();
+            }
 
-            if let Some(event) = events.next().await {
-                match event {
+            // Results are handled, and drawn, as soon as they arrive rather than on the next tick
+            let async_rx = &mut self.async_rx;
+            let next_msg = async move {
+                match async_rx {
+                    Some(rx) => rx.recv().await,
+                    None => std::future::pending().await,
+                }
+            };
+            tokio::select! {
+                Some(msg) = next_msg => self.process_async_messages(Some(msg)),
+                Some(event) = events.next() => match event {
                     Event::Tick => {
-                        // Process any pending async messages
-                        self.process_async_messages();
-                        // Advance matrix rain animation when loading
+                        // Advance the loading animation
                         if self.loading {
-                            self.matrix_rain.tick();
+                            match self.config.loading_style {
+                                LoadingStyle::Matrix => self.matrix_rain.tick(),
+                                LoadingStyle::Spinner => self.spinner_frame = self.spinner_frame.wrapping_add(1),
+                                LoadingStyle::Popup | LoadingStyle::None => {}
+                            }
                         }
+                        self.poll_in_progress_runs();
+                        self.poll_followed_logs();
+                    }
+                    Event::Key(key) => {
+                        self.handle_key(key).await;
+                        self.dirty = true;
+                    }
+                    Event::Mouse(mouse) => {
+                        self.handle_mouse(mouse);
+                        self.dirty = true;
                     }
-                    Event::Key(key) => self.handle_key(key).await,
                     Event::Resize(w, h) => {
                         self.matrix_rain.resize(w, h);
+                        self.dirty = true;
                     }
-                }
+                },
+                else => break,
             }
         }
 
         Ok(())
     }
 
-    fn process_async_messages(&mut self) {
+    /// Handle `first` and every other message already waiting
+    fn process_async_messages(&mut self, first: Option<AsyncMsg>) {
         // Collect messages first to avoid borrow issues
-        let messages: Vec<AsyncMsg> = if let Some(ref mut rx) = self.async_rx {
-            let mut msgs = Vec::new();
+        let mut messages: Vec<AsyncMsg> = first.into_iter().collect();
+        if let Some(ref mut rx) = self.async_rx {
             while let Ok(msg) = rx.try_recv() {
-                msgs.push(msg);
+                messages.push(msg);
             }
-            msgs
-        } else {
-            Vec::new()
-        };
+        }
+        if !messages.is_empty() {
+            self.dirty = true;
+        }
 
         let mut needs_filter = false;
         let mut needs_select_pr: Option<u64> = None;
@@ -291,11 +664,30 @@ impl App {
                     self.current_user = Some(user);
                     needs_filter = true;
                 }
-                AsyncMsg::PrsLoaded(prs) => {
+                AsyncMsg::UserTeamsLoaded(teams) => {
+                    self.user_teams = teams.into_iter().collect();
+                    needs_filter = true;
+                }
+                AsyncMsg::PrsLoaded(prs, warning) => {
+                    // Mark PRs that are new or were updated since the last load
+                    if !self.all_prs.is_empty() {
+                        let before: HashMap<u64, &str> = self.all_prs.iter()
+                            .map(|pr| (pr.number, pr.updated_at.as_str()))
+                            .collect();
+                        let expires_at = Instant::now() + UPDATED_MARKER;
+                        for pr in &prs {
+                            if before.get(&pr.number).is_none_or(|&at| at < pr.updated_at.as_str()) {
+                                self.updated_prs.insert(pr.number, expires_at);
+                            }
+                        }
+                    }
                     self.all_prs = prs;
                     needs_filter = true;
                     self.loading = false;
                     self.loading_what = None;
+                    if let Some(warning) = warning {
+                        self.set_message(warning);
+                    }
 
                     // Handle initial PR selection from CLI argument
                     if let Some(pr_number) = self.initial_pr.take() {
@@ -304,61 +696,282 @@ impl App {
 
                     // Fetch recent branch after PRs are loaded (so we know which branches have PRs)
                     self.spawn_fetch_recent_branch();
+                    self.spawn_fetch_ci_statuses();
+                }
+                AsyncMsg::ClosedPrsLoaded(prs, warning) => {
+                    self.closed_prs = prs;
+                    needs_filter = true;
+                    self.loading = false;
+                    self.loading_what = None;
+                    if let Some(warning) = warning {
+                        self.set_message(warning);
+                    }
+
+                    // A `:` jump to a PR that wasn't open
+                    if self.pr_filter == PrFilter::Closed {
+                        if let Some(pr_number) = self.initial_pr.take() {
+                            needs_select_pr = Some(pr_number);
+                        }
+                    }
                 }
                 AsyncMsg::RecentBranchLoaded(branch) => {
                     self.recent_branch = branch;
                 }
+                AsyncMsg::MergeableLoaded(number, mergeable) => {
+                    let prs = self.all_prs.iter_mut()
+                        .chain(self.closed_prs.iter_mut())
+                        .chain(self.prs.iter_mut())
+                        .chain(self.selected_pr.iter_mut());
+                    for pr in prs.filter(|pr| pr.number == number) {
+                        pr.mergeable = mergeable;
+                    }
+                }
                 AsyncMsg::RunsLoaded(runs) => {
+                    self.runs_reloading = false;
+                    self.runs_page = 1;
+                    self.runs_exhausted = runs.len() < self.run_page_size() as usize;
                     self.runs = runs;
                     if !self.runs.is_empty() && self.run_list_state.selected().is_none() {
                         self.run_list_state.select(Some(0));
                     }
                 }
-                AsyncMsg::DiffLoaded(diff) => {
+                AsyncMsg::MoreRunsLoaded(page, runs) => {
+                    self.runs_loading_more = false;
+                    self.runs_page = page;
+                    self.runs_exhausted = runs.len() < self.run_page_size() as usize;
+                    // Runs shift between pages as new ones start, so skip ids we already have
+                    for run in runs {
+                        if !self.runs.iter().any(|r| r.id == run.id) {
+                            self.runs.push(run);
+                        }
+                    }
+                }
+                AsyncMsg::RunsPolled(runs) => {
+                    self.merge_polled_runs(runs);
+                }
+                // Results for a PR that is no longer selected
+                AsyncMsg::DiffLoaded(generation, _)
+                | AsyncMsg::ReviewsLoaded(generation, _)
+                | AsyncMsg::AutoMergeLoaded(generation, _)
+                | AsyncMsg::CommitsLoaded(generation, _)
+                    if generation != self.pr_generation => {}
+                AsyncMsg::DiffLoaded(_, diff) => {
+                    self.pr_diff_stat = Some(DiffStat::parse(&diff));
                     self.pr_diff = Some(diff);
+                    self.update_rendered_diffs();
                     self.loading = false;
                     self.loading_what = None;
                 }
-                AsyncMsg::PrChecksLoaded(checks) => {
-                    self.pr_checks = checks;
-                    if !self.pr_checks.is_empty() && self.pr_checks_state.selected().is_none() {
-                        self.pr_checks_state.select(Some(0));
+                AsyncMsg::PrChecksLoaded(sha, checks) => {
+                    self.set_ci_status(&sha, aggregate_ci_status(&checks));
+                    // Ignore stale results for a PR that is no longer selected
+                    if self.selected_pr.as_ref().is_some_and(|pr| pr.head.sha == sha) {
+                        self.pr_checks = group_checks(checks);
+                        let rows = self.pr_check_rows().len();
+                        if rows == 0 {
+                            self.pr_checks_state.select(None);
+                        } else {
+                            let selected = self.pr_checks_state.selected().map_or(0, |i| i.min(rows - 1));
+                            self.pr_checks_state.select(Some(selected));
+                        }
                     }
                 }
-                AsyncMsg::ReviewsLoaded(reviews) => {
+                AsyncMsg::CiStatusLoaded(sha, status) => {
+                    self.set_ci_status(&sha, status);
+                }
+                AsyncMsg::CommitCiLoaded(sha, status) => {
+                    self.commit_ci.insert(sha, status);
+                }
+                AsyncMsg::ReviewsLoaded(_, reviews) => {
                     self.pr_reviews = reviews;
+                    self.reviews_known = true;
+                }
+                AsyncMsg::AutoMergeLoaded(_, method) => {
+                    self.pr_auto_merge = method;
                 }
-                AsyncMsg::JobsLoaded(jobs) => {
+                AsyncMsg::RequiredApprovalsLoaded(branch, count) => {
+                    self.required_approvals.insert(branch, count);
+                }
+                AsyncMsg::CheckedOut(pr_number, branch) => {
+                    self.local_head = git::local_head();
+                    self.set_message(format!("Checked out PR #{} as {}", pr_number, branch));
+                }
+                AsyncMsg::JobsLoaded(run_id, _)
+                | AsyncMsg::JobsFollowed(run_id, _)
+                | AsyncMsg::LogsFollowed(run_id, _, _)
+                    if self.selected_run.as_ref().map(|r| r.id) != Some(run_id) => {}
+                AsyncMsg::JobsLoaded(_, jobs) => {
                     self.jobs = jobs;
                     if !self.jobs.is_empty() && self.job_list_state.selected().is_none() {
                         self.job_list_state.select(Some(0));
                     }
                     self.loading = false;
                     self.loading_what = None;
+                    if self.failure_jump {
+                        match self.jobs.iter().position(|j| matches!(j.conclusion.as_deref(), Some("failure" | "timed_out"))) {
+                            Some(i) => {
+                                self.job_list_state.select(Some(i));
+                                self.fetch_logs();
+                            }
+                            None => {
+                                self.failure_jump = false;
+                                self.tab = Tab::Actions;
+                                self.set_message("No failed job in this run");
+                            }
+                        }
+                    }
+                }
+                AsyncMsg::ArtifactsLoaded(run_id, artifacts) => {
+                    // Ignore results for a run that is no longer shown
+                    if self.selected_run.as_ref().is_some_and(|r| r.id == run_id) {
+                        self.artifacts = artifacts;
+                        let selected = self.artifact_list_state.selected().unwrap_or(0);
+                        self.artifact_list_state
+                            .select((!self.artifacts.is_empty()).then(|| selected.min(self.artifacts.len() - 1)));
+                        if self.artifacts.is_empty() {
+                            self.artifacts_focused = false;
+                        }
+                    }
+                }
+                AsyncMsg::PendingDeploymentsLoaded(run_id, deployments) => {
+                    if self.selected_run.as_ref().is_some_and(|r| r.id == run_id) {
+                        self.pending_deployments = deployments;
+                    }
+                }
+                AsyncMsg::BatchDone(summary, failed) => {
+                    // Failed PRs stay selected so the batch can be retried
+                    if failed.is_empty() {
+                        self.set_message(summary);
+                    } else {
+                        self.error = Some(summary);
+                    }
+                    self.selected_prs = failed.into_iter().collect();
+                    self.spawn_fetch_prs();
                 }
                 AsyncMsg::LogsLoaded(logs) => {
-                    self.logs = logs;
+                    // Groups start collapsed so long logs open as a list of steps
+                    self.set_logs(logs);
+                    self.collapsed_groups = self.log_groups.iter().map(|g| g.start).collect();
+                    self.refresh_visible_log_lines();
                     self.log_scroll = 0;
                     self.log_h_scroll = 0;
                     self.loading = false;
                     self.loading_what = None;
+                    if std::mem::take(&mut self.failure_jump) {
+                        let first_error = self.log_lines().position(|l| l.contains("##[error]"));
+                        if let Some(line) = first_error {
+                            self.scroll_to_log_line(line);
+                        }
+                    }
+                }
+                AsyncMsg::JobsFollowed(_, jobs) => {
+                    self.jobs = jobs;
+                }
+                AsyncMsg::LogsFollowed(_, job_id, logs) => {
+                    if self.selected_job().map(|j| j.id) == Some(job_id) {
+                        self.update_logs_in_place(logs);
+                    }
+                }
+                AsyncMsg::LogsRefreshed(logs) => {
+                    self.update_logs_in_place(logs);
+                    self.loading = false;
+                    self.loading_what = None;
                 }
-                AsyncMsg::CommitsLoaded(commits) => {
+                AsyncMsg::CommitsLoaded(_, commits) => {
                     self.pr_commits = commits;
+                    self.spawn_fetch_commit_ci();
+                    self.loading = false;
+                    self.loading_what = None;
                     if !self.pr_commits.is_empty() && self.pr_commits_state.selected().is_none() {
                         self.pr_commits_state.select(Some(0));
                     }
+                    // The commit view was opened while these were loading
+                    if self.diff_mode == DiffMode::ByCommit {
+                        if self.pr_commits.is_empty() {
+                            self.diff_mode = DiffMode::Full;
+                            self.set_message("No commits found for this PR");
+                        } else {
+                            self.load_selected_commit_diff();
+                        }
+                    }
                 }
-                AsyncMsg::CommitDiffLoaded(diff) => {
+                AsyncMsg::CommitDiffLoaded(sha, diff) => {
+                    // Ignore a slow diff for a commit the user has moved past
+                    let selected_sha = self.pr_commits_state.selected()
+                        .and_then(|i| self.pr_commits.get(i))
+                        .map(|c| c.sha.as_str());
+                    if selected_sha != Some(sha.as_str()) {
+                        continue;
+                    }
                     self.commit_diff = Some(diff);
+                    self.update_rendered_diffs();
                     self.diff_scroll = 0;
                     self.loading = false;
                     self.loading_what = None;
                 }
+                AsyncMsg::NotificationsLoaded(notifications) => {
+                    self.notifications = notifications;
+                    let len = self.notifications.len();
+                    match self.notification_list_state.selected() {
+                        _ if len == 0 => self.notification_list_state.select(None),
+                        Some(i) if i < len => {}
+                        _ => self.notification_list_state.select(Some(0)),
+                    }
+                    self.loading = false;
+                    self.loading_what = None;
+                }
+                AsyncMsg::WorkflowsLoaded(workflows, default_branch) => {
+                    self.loading = false;
+                    self.loading_what = None;
+                    if workflows.is_empty() {
+                        self.set_message("No active workflows");
+                    } else {
+                        self.workflows = workflows;
+                        self.default_branch = default_branch;
+                        self.workflow_picker = Some(ListState::default().with_selected(Some(0)));
+                    }
+                }
+                AsyncMsg::DispatchInputsLoaded(inputs) => {
+                    self.loading = false;
+                    self.loading_what = None;
+                    match (inputs, self.dispatch.as_mut()) {
+                        (Some(inputs), Some(form)) => {
+                            form.inputs = inputs;
+                            self.prompt_dispatch_input();
+                        }
+                        (None, Some(form)) => {
+                            self.error = Some(format!("'{}' has no workflow_dispatch trigger", form.workflow.name));
+                            self.dispatch = None;
+                        }
+                        // Cancelled meanwhile
+                        (_, None) => {}
+                    }
+                }
+                AsyncMsg::WorkflowDispatched(message) => {
+                    self.loading = false;
+                    self.loading_what = None;
+                    self.set_message(message);
+                    // The new run may take a moment to be listed; a later refresh shows it
+                    self.spawn_fetch_runs();
+                }
+                AsyncMsg::NotificationRead(id) => {
+                    // The inbox only lists unread threads
+                    self.notifications.retain(|n| n.id != id);
+                    let len = self.notifications.len();
+                    if len == 0 {
+                        self.notification_list_state.select(None);
+                    } else if self.notification_list_state.selected().is_some_and(|i| i >= len) {
+                        self.notification_list_state.select(Some(len - 1));
+                    }
+                    self.set_message("Marked as read");
+                }
                 AsyncMsg::Error(e) => {
                     self.error = Some(e);
                     self.loading = false;
                     self.loading_what = None;
+                    self.runs_loading_more = false;
+                    self.stop_reloading_runs();
+                    self.failure_jump = false;
                 }
                 AsyncMsg::Message(m) => {
                     self.set_message(m);
@@ -368,6 +981,7 @@ impl App {
 
         if needs_filter {
             self.apply_pr_filter();
+            self.sync_selected_pr();
         }
 
         // Select initial PR if specified
@@ -377,6 +991,37 @@ impl App {
     }
 
     // Spawn async tasks for fetching data
+    /// Spawn a fetch. One started while loading gets a token of its own, so
+    /// `cancel_loading` drops it at its next await and a hung request neither
+    /// lingers nor reports late; background fetches run to the end.
+    fn spawn_load(&mut self, task: impl std::future::Future<Output = ()> + Send + 'static) {
+        if !self.loading {
+            tokio::spawn(task);
+            return;
+        }
+        // Finished loads cancel their own token, so they can be dropped here
+        self.loads.retain(|load| !load.is_cancelled());
+        let cancel = CancellationToken::new();
+        self.loads.push(cancel.clone());
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = cancel.cancelled() => {}
+                _ = task => cancel.cancel(),
+            }
+        });
+    }
+
+    /// Give up on the fetches behind the loading overlay and hand control back
+    fn cancel_loading(&mut self) {
+        for load in self.loads.drain(..) {
+            load.cancel();
+        }
+        self.stop_reloading_runs();
+        self.loading = false;
+        self.loading_what = None;
+        self.set_message("Cancelled");
+    }
+
     fn spawn_fetch_current_user(&self) {
         if let (Some(client), Some(tx)) = (self.client.clone(), self.async_tx.clone()) {
             tokio::spawn(async move {
@@ -392,19 +1037,45 @@ impl App {
         }
     }
 
-    fn spawn_fetch_prs(&self) {
+    fn spawn_fetch_user_teams(&self) {
+        if let (Some(client), Some(tx)) = (self.client.clone(), self.async_tx.clone()) {
+            tokio::spawn(async move {
+                // Without teams (e.g. no read:org scope) only individual review requests count
+                if let Ok(teams) = client.get_user_teams().await {
+                    let _ = tx.send(AsyncMsg::UserTeamsLoaded(teams));
+                }
+            });
+        }
+    }
+
+    fn spawn_fetch_prs(&mut self) {
         if let (Some(client), Some(tx)) = (self.client.clone(), self.async_tx.clone()) {
             let owner = self.owner.clone();
             let repo = self.repo_name.clone();
-            tokio::spawn(async move {
-                match client.list_prs(&owner, &repo).await {
-                    Ok(prs) => { let _ = tx.send(AsyncMsg::PrsLoaded(prs)); }
+            let max_pages = self.config.max_pr_pages.unwrap_or(MAX_PR_PAGES);
+            self.spawn_load(async move {
+                match client.list_prs(&owner, &repo, octocrab::params::State::Open, max_pages).await {
+                    Ok((prs, warning)) => { let _ = tx.send(AsyncMsg::PrsLoaded(prs, warning)); }
                     Err(e) => { let _ = tx.send(AsyncMsg::Error(format!("Failed to fetch PRs: {}", e))); }
                 }
             });
         }
     }
 
+    fn spawn_fetch_closed_prs(&mut self) {
+        if let (Some(client), Some(tx)) = (self.client.clone(), self.async_tx.clone()) {
+            let owner = self.owner.clone();
+            let repo = self.repo_name.clone();
+            let max_pages = self.config.max_pr_pages.unwrap_or(MAX_PR_PAGES);
+            self.spawn_load(async move {
+                match client.list_prs(&owner, &repo, octocrab::params::State::Closed, max_pages).await {
+                    Ok((prs, warning)) => { let _ = tx.send(AsyncMsg::ClosedPrsLoaded(prs, warning)); }
+                    Err(e) => { let _ = tx.send(AsyncMsg::Error(format!("Failed to fetch closed PRs: {}", e))); }
+                }
+            });
+        }
+    }
+
     fn spawn_fetch_recent_branch(&self) {
         if let (Some(client), Some(tx)) = (self.client.clone(), self.async_tx.clone()) {
             let owner = self.owner.clone();
@@ -424,12 +1095,19 @@ impl App {
         }
     }
 
-    fn spawn_fetch_runs(&self) {
+    /// Runs per page: `per_page` from the config, or RUN_PAGE_SIZE
+    fn run_page_size(&self) -> u8 {
+        self.config.per_page.unwrap_or(RUN_PAGE_SIZE)
+    }
+
+    fn spawn_fetch_runs(&mut self) {
         if let (Some(client), Some(tx)) = (self.client.clone(), self.async_tx.clone()) {
             let owner = self.owner.clone();
             let repo = self.repo_name.clone();
-            tokio::spawn(async move {
-                match client.list_runs(&owner, &repo).await {
+            let per_page = self.run_page_size();
+            let filter = self.run_filter.clone();
+            self.spawn_load(async move {
+                match client.list_runs(&owner, &repo, per_page, &filter).await {
                     Ok(runs) => { let _ = tx.send(AsyncMsg::RunsLoaded(runs)); }
                     Err(e) => { let _ = tx.send(AsyncMsg::Error(format!("Failed to fetch runs: {}", e))); }
                 }
@@ -437,147 +1115,429 @@ impl App {
         }
     }
 
-    fn spawn_fetch_diff(&self, pr_number: u64) {
+    /// Fetch dispatchable workflows and the default branch to suggest as the ref
+    fn spawn_fetch_workflows(&mut self) {
         if let (Some(client), Some(tx)) = (self.client.clone(), self.async_tx.clone()) {
             let owner = self.owner.clone();
             let repo = self.repo_name.clone();
-            tokio::spawn(async move {
-                match client.get_pr_diff(&owner, &repo, pr_number).await {
-                    Ok(diff) => { let _ = tx.send(AsyncMsg::DiffLoaded(diff)); }
-                    Err(e) => { let _ = tx.send(AsyncMsg::Error(format!("Failed to fetch diff: {}", e))); }
+            self.spawn_load(async move {
+                let result = tokio::try_join!(
+                    client.list_workflows(&owner, &repo),
+                    client.get_default_branch(&owner, &repo),
+                );
+                match result {
+                    Ok((workflows, branch)) => { let _ = tx.send(AsyncMsg::WorkflowsLoaded(workflows, branch)); }
+                    Err(e) => { let _ = tx.send(AsyncMsg::Error(format!("Failed to fetch workflows: {}", e))); }
                 }
             });
         }
     }
 
-    fn spawn_fetch_pr_checks(&self, head_sha: &str) {
+    /// Fetch unread notifications for this repo, or all repos
+    fn spawn_fetch_notifications(&mut self) {
         if let (Some(client), Some(tx)) = (self.client.clone(), self.async_tx.clone()) {
             let owner = self.owner.clone();
             let repo = self.repo_name.clone();
-            let sha = head_sha.to_string();
-            tokio::spawn(async move {
-                match client.list_runs_for_commit(&owner, &repo, &sha).await {
-                    Ok(runs) => { let _ = tx.send(AsyncMsg::PrChecksLoaded(runs)); }
-                    Err(e) => { let _ = tx.send(AsyncMsg::Error(format!("Failed to fetch PR checks: {}", e))); }
+            let all_repos = self.notifications_all_repos;
+            self.spawn_load(async move {
+                let scope = (!all_repos).then_some((owner.as_str(), repo.as_str()));
+                match client.list_notifications(scope).await {
+                    Ok(notifications) => { let _ = tx.send(AsyncMsg::NotificationsLoaded(notifications)); }
+                    Err(e) => { let _ = tx.send(AsyncMsg::Error(format!("Failed to fetch notifications: {}", e))); }
                 }
             });
         }
     }
 
-    fn spawn_fetch_reviews(&self, pr_number: u64) {
+    fn spawn_poll_runs(&self) {
         if let (Some(client), Some(tx)) = (self.client.clone(), self.async_tx.clone()) {
             let owner = self.owner.clone();
             let repo = self.repo_name.clone();
+            let per_page = self.run_page_size();
+            let filter = self.run_filter.clone();
             tokio::spawn(async move {
-                match client.list_pr_reviews(&owner, &repo, pr_number).await {
-                    Ok(reviews) => { let _ = tx.send(AsyncMsg::ReviewsLoaded(reviews)); }
-                    Err(e) => { let _ = tx.send(AsyncMsg::Error(format!("Failed to fetch reviews: {}", e))); }
+                // Polling is best-effort - the next tick will try again
+                if let Ok(runs) = client.list_runs(&owner, &repo, per_page, &filter).await {
+                    let _ = tx.send(AsyncMsg::RunsPolled(runs));
                 }
             });
         }
     }
 
-    fn spawn_fetch_jobs(&self, run_id: u64) {
+    fn spawn_fetch_more_runs(&mut self) {
+        if self.runs_loading_more || self.runs_exhausted {
+            return;
+        }
         if let (Some(client), Some(tx)) = (self.client.clone(), self.async_tx.clone()) {
             let owner = self.owner.clone();
             let repo = self.repo_name.clone();
+            let page = self.runs_page + 1;
+            let per_page = self.run_page_size();
+            let filter = self.run_filter.clone();
+            self.runs_loading_more = true;
             tokio::spawn(async move {
-                match client.list_jobs(&owner, &repo, run_id).await {
-                    Ok(jobs) => { let _ = tx.send(AsyncMsg::JobsLoaded(jobs)); }
-                    Err(e) => { let _ = tx.send(AsyncMsg::Error(format!("Failed to fetch jobs: {}", e))); }
+                match client.list_runs_paged(&owner, &repo, page, per_page, &filter).await {
+                    Ok(runs) => { let _ = tx.send(AsyncMsg::MoreRunsLoaded(page, runs)); }
+                    Err(e) => { let _ = tx.send(AsyncMsg::Error(format!("Failed to fetch more runs: {}", e))); }
                 }
             });
         }
     }
 
-    fn spawn_fetch_logs(&self, run_id: u64, job_id: Option<u64>) {
+    /// Fetch the full diff of a PR; a `refresh` bypasses the client's cache
+    fn spawn_fetch_diff(&mut self, pr: &PullRequest, refresh: bool) {
         if let (Some(client), Some(tx)) = (self.client.clone(), self.async_tx.clone()) {
             let owner = self.owner.clone();
             let repo = self.repo_name.clone();
-            tokio::spawn(async move {
-                match client.get_run_logs(&owner, &repo, run_id, job_id).await {
-                    Ok(logs) => { let _ = tx.send(AsyncMsg::LogsLoaded(logs)); }
-                    Err(e) => { let _ = tx.send(AsyncMsg::Error(format!("Failed to fetch logs: {}", e))); }
+            let generation = self.pr_generation;
+            let (pr_number, head_sha) = (pr.number, pr.head.sha.clone());
+            self.spawn_load(async move {
+                match client.get_pr_diff(&owner, &repo, pr_number, &head_sha, refresh).await {
+                    Ok(diff) => { let _ = tx.send(AsyncMsg::DiffLoaded(generation, diff)); }
+                    Err(e) => { let _ = tx.send(AsyncMsg::Error(format!("Failed to fetch diff: {}", e))); }
                 }
             });
         }
     }
 
-    fn spawn_fetch_commits(&self, pr_number: u64) {
+    /// Fetch the PR itself for its mergeable state. GitHub computes that lazily,
+    /// so retry a few times while it is still null.
+    fn spawn_fetch_pr(&self, pr_number: u64) {
+        const ATTEMPTS: usize = 3;
+
         if let (Some(client), Some(tx)) = (self.client.clone(), self.async_tx.clone()) {
             let owner = self.owner.clone();
             let repo = self.repo_name.clone();
             tokio::spawn(async move {
-                match client.list_pr_commits(&owner, &repo, pr_number).await {
-                    Ok(commits) => { let _ = tx.send(AsyncMsg::CommitsLoaded(commits)); }
-                    Err(e) => { let _ = tx.send(AsyncMsg::Error(format!("Failed to fetch commits: {}", e))); }
+                for attempt in 1..=ATTEMPTS {
+                    match client.get_pr(&owner, &repo, pr_number).await {
+                        Ok(pr) if pr.mergeable.is_none() && attempt < ATTEMPTS => {
+                            tokio::time::sleep(Duration::from_secs(2)).await;
+                        }
+                        Ok(pr) => {
+                            let _ = tx.send(AsyncMsg::MergeableLoaded(pr_number, pr.mergeable));
+                            break;
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AsyncMsg::Error(format!("Failed to fetch PR: {}", e)));
+                            break;
+                        }
+                    }
                 }
             });
         }
     }
 
-    fn spawn_fetch_commit_diff(&self, sha: &str) {
+    fn spawn_fetch_pr_checks(&mut self, head_sha: &str) {
         if let (Some(client), Some(tx)) = (self.client.clone(), self.async_tx.clone()) {
             let owner = self.owner.clone();
             let repo = self.repo_name.clone();
-            let sha = sha.to_string();
-            tokio::spawn(async move {
-                match client.get_commit_diff(&owner, &repo, &sha).await {
-                    Ok(diff) => { let _ = tx.send(AsyncMsg::CommitDiffLoaded(diff)); }
-                    Err(e) => { let _ = tx.send(AsyncMsg::Error(format!("Failed to fetch commit diff: {}", e))); }
+            let sha = head_sha.to_string();
+            self.spawn_load(async move {
+                match client.list_commit_checks(&owner, &repo, &sha).await {
+                    Ok(checks) => { let _ = tx.send(AsyncMsg::PrChecksLoaded(sha, checks)); }
+                    Err(e) => { let _ = tx.send(AsyncMsg::Error(format!("Failed to fetch PR checks: {}", e))); }
                 }
             });
         }
     }
 
-    async fn handle_key(&mut self, key: KeyEvent) {
-        // Handle Ctrl+C globally
-        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
-            self.should_quit = true;
-            return;
-        }
+    /// Fetch CI status for listed PRs in the background, one commit at a time.
+    /// Check runs alone cover Actions and most apps in a single request each;
+    /// commit statuses are only fetched for the selected PR.
+    fn spawn_fetch_ci_statuses(&self) {
+        const MAX_CI_FETCHES: usize = 30;
 
-        // Handle description editor mode
-        if self.editing_description {
-            if let Some(ref mut textarea) = self.description_editor {
-                match (key.modifiers, key.code) {
-                    (KeyModifiers::CONTROL, KeyCode::Char('s')) => {
-                        // Save the description
-                        self.save_description().await;
-                    }
-                    (_, KeyCode::Esc) => {
-                        // Cancel editing
-                        self.editing_description = false;
-                        self.description_editor = None;
-                        self.set_message("Description edit cancelled");
-                    }
-                    _ => {
-                        // Pass input to textarea
-                        let input = Input::from(key);
-                        textarea.input(input);
+        if let (Some(client), Some(tx)) = (self.client.clone(), self.async_tx.clone()) {
+            let owner = self.owner.clone();
+            let repo = self.repo_name.clone();
+            let shas: Vec<String> = self.all_prs.iter()
+                .filter(|pr| pr.ci_status.is_none())
+                .map(|pr| pr.head.sha.clone())
+                .take(MAX_CI_FETCHES)
+                .collect();
+
+            tokio::spawn(async move {
+                for sha in shas {
+                    // Silently skip failures - the icon just stays unknown
+                    if let Ok(checks) = client.list_check_runs(&owner, &repo, &sha).await {
+                        if tx.send(AsyncMsg::CiStatusLoaded(sha, aggregate_ci_status(&checks))).is_err() {
+                            break;
+                        }
                     }
                 }
-            }
-            return;
+            });
         }
+    }
 
-        // Handle input mode
-        if let Some(mode) = self.input_mode {
-            match key.code {
-                KeyCode::Esc => {
-                    self.input_mode = None;
-                    self.input_buffer.clear();
-                    self.status_message = None;
+    /// Fetch CI status for each of the PR's commits, one at a time, newest first.
+    /// Settled results are kept, as a commit's checks don't change once done.
+    fn spawn_fetch_commit_ci(&self) {
+        const MAX_COMMIT_CI_FETCHES: usize = 50;
+
+        if let (Some(client), Some(tx)) = (self.client.clone(), self.async_tx.clone()) {
+            let owner = self.owner.clone();
+            let repo = self.repo_name.clone();
+            let shas: Vec<String> = self.pr_commits.iter()
+                .rev()
+                .filter(|c| !matches!(self.commit_ci.get(&c.sha), Some(Some(status)) if status != "pending"))
+                .map(|c| c.sha.clone())
+                .take(MAX_COMMIT_CI_FETCHES)
+                .collect();
+
+            tokio::spawn(async move {
+                for sha in shas {
+                    // Silently skip failures - the icon just stays unknown
+                    if let Ok(checks) = client.list_check_runs(&owner, &repo, &sha).await {
+                        if tx.send(AsyncMsg::CommitCiLoaded(sha, aggregate_ci_status(&checks))).is_err() {
+                            break;
+                        }
+                    }
                 }
-                KeyCode::Enter => {
-                    match mode {
-                        InputMode::Search => {
+            });
+        }
+    }
+
+    fn spawn_fetch_auto_merge(&mut self, pr_number: u64) {
+        if let (Some(client), Some(tx)) = (self.client.clone(), self.async_tx.clone()) {
+            let owner = self.owner.clone();
+            let repo = self.repo_name.clone();
+            let generation = self.pr_generation;
+            self.spawn_load(async move {
+                // Silently skip failures (e.g. a token without GraphQL access);
+                // the detail view then just doesn't mention auto-merge
+                if let Ok(method) = client.auto_merge_method(&owner, &repo, pr_number).await {
+                    let _ = tx.send(AsyncMsg::AutoMergeLoaded(generation, method));
+                }
+            });
+        }
+    }
+
+    /// Look up the approvals required to merge into `branch`, once per branch
+    fn spawn_fetch_required_approvals(&self, branch: &str) {
+        if self.required_approvals.contains_key(branch) {
+            return;
+        }
+        if let (Some(client), Some(tx)) = (self.client.clone(), self.async_tx.clone()) {
+            let owner = self.owner.clone();
+            let repo = self.repo_name.clone();
+            let branch = branch.to_string();
+            tokio::spawn(async move {
+                // Without admin access protection can't be read; the count just isn't
+                // shown, and isn't asked for again
+                let count = client.get_branch_protection(&owner, &repo, &branch).await.unwrap_or(None);
+                let _ = tx.send(AsyncMsg::RequiredApprovalsLoaded(branch, count));
+            });
+        }
+    }
+
+    fn spawn_fetch_reviews(&mut self, pr_number: u64) {
+        if let (Some(client), Some(tx)) = (self.client.clone(), self.async_tx.clone()) {
+            let owner = self.owner.clone();
+            let repo = self.repo_name.clone();
+            let generation = self.pr_generation;
+            self.spawn_load(async move {
+                match client.list_pr_reviews(&owner, &repo, pr_number).await {
+                    Ok(reviews) => { let _ = tx.send(AsyncMsg::ReviewsLoaded(generation, reviews)); }
+                    Err(e) => { let _ = tx.send(AsyncMsg::Error(format!("Failed to fetch reviews: {}", e))); }
+                }
+            });
+        }
+    }
+
+    fn spawn_fetch_jobs(&mut self, run_id: u64) {
+        if let (Some(client), Some(tx)) = (self.client.clone(), self.async_tx.clone()) {
+            let owner = self.owner.clone();
+            let repo = self.repo_name.clone();
+            self.spawn_load(async move {
+                match client.list_jobs(&owner, &repo, run_id).await {
+                    Ok(jobs) => { let _ = tx.send(AsyncMsg::JobsLoaded(run_id, jobs)); }
+                    Err(e) => { let _ = tx.send(AsyncMsg::Error(format!("Failed to fetch jobs: {}", e))); }
+                }
+            });
+        }
+    }
+
+    fn spawn_fetch_artifacts(&mut self, run_id: u64) {
+        if let (Some(client), Some(tx)) = (self.client.clone(), self.async_tx.clone()) {
+            let owner = self.owner.clone();
+            let repo = self.repo_name.clone();
+            self.spawn_load(async move {
+                match client.list_artifacts(&owner, &repo, run_id).await {
+                    Ok(artifacts) => { let _ = tx.send(AsyncMsg::ArtifactsLoaded(run_id, artifacts)); }
+                    Err(e) => { let _ = tx.send(AsyncMsg::Error(format!("Failed to fetch artifacts: {}", e))); }
+                }
+            });
+        }
+    }
+
+    fn spawn_fetch_pending_deployments(&self, run_id: u64) {
+        if let (Some(client), Some(tx)) = (self.client.clone(), self.async_tx.clone()) {
+            let owner = self.owner.clone();
+            let repo = self.repo_name.clone();
+            tokio::spawn(async move {
+                match client.list_pending_deployments(&owner, &repo, run_id).await {
+                    Ok(deployments) => { let _ = tx.send(AsyncMsg::PendingDeploymentsLoaded(run_id, deployments)); }
+                    Err(e) => { let _ = tx.send(AsyncMsg::Error(format!("Failed to fetch pending deployments: {}", e))); }
+                }
+            });
+        }
+    }
+
+    /// Fetch logs for the Logs tab. A `refresh` keeps the scroll position and folds.
+    fn spawn_fetch_logs(&mut self, run_id: u64, job_id: Option<u64>, refresh: bool) {
+        if let (Some(client), Some(tx)) = (self.client.clone(), self.async_tx.clone()) {
+            let owner = self.owner.clone();
+            let repo = self.repo_name.clone();
+            let cacheable = self.selected_job().is_some_and(|j| j.completed_at.is_some());
+            self.spawn_load(async move {
+                match client.get_run_logs(&owner, &repo, run_id, job_id, cacheable, refresh).await {
+                    Ok(logs) if refresh => { let _ = tx.send(AsyncMsg::LogsRefreshed(logs)); }
+                    Ok(logs) => { let _ = tx.send(AsyncMsg::LogsLoaded(logs)); }
+                    Err(e) => { let _ = tx.send(AsyncMsg::Error(format!("Failed to fetch logs: {}", e))); }
+                }
+            });
+        }
+    }
+
+    /// Re-fetch the followed job's status and logs without disturbing the view
+    fn spawn_follow_logs(&self, run_id: u64, job_id: u64) {
+        if let (Some(client), Some(tx)) = (self.client.clone(), self.async_tx.clone()) {
+            let owner = self.owner.clone();
+            let repo = self.repo_name.clone();
+            tokio::spawn(async move {
+                // Best-effort - the next poll will try again
+                if let Ok(jobs) = client.list_jobs(&owner, &repo, run_id).await {
+                    let _ = tx.send(AsyncMsg::JobsFollowed(run_id, jobs));
+                }
+                if let Ok(logs) = client.get_run_logs(&owner, &repo, run_id, Some(job_id), false, false).await {
+                    let _ = tx.send(AsyncMsg::LogsFollowed(run_id, job_id, logs));
+                }
+            });
+        }
+    }
+
+    fn spawn_fetch_commits(&mut self, pr_number: u64) {
+        if let (Some(client), Some(tx)) = (self.client.clone(), self.async_tx.clone()) {
+            let owner = self.owner.clone();
+            let repo = self.repo_name.clone();
+            let generation = self.pr_generation;
+            self.spawn_load(async move {
+                match client.list_pr_commits(&owner, &repo, pr_number).await {
+                    Ok(commits) => { let _ = tx.send(AsyncMsg::CommitsLoaded(generation, commits)); }
+                    Err(e) => { let _ = tx.send(AsyncMsg::Error(format!("Failed to fetch commits: {}", e))); }
+                }
+            });
+        }
+    }
+
+    /// Fetch a commit's diff; a `refresh` bypasses the client's cache
+    fn spawn_fetch_commit_diff(&mut self, sha: &str, refresh: bool) {
+        if let (Some(client), Some(tx)) = (self.client.clone(), self.async_tx.clone()) {
+            let owner = self.owner.clone();
+            let repo = self.repo_name.clone();
+            let sha = sha.to_string();
+            self.spawn_load(async move {
+                match client.get_commit_diff(&owner, &repo, &sha, refresh).await {
+                    Ok(diff) => { let _ = tx.send(AsyncMsg::CommitDiffLoaded(sha, diff)); }
+                    Err(e) => { let _ = tx.send(AsyncMsg::Error(format!("Failed to fetch commit diff: {}", e))); }
+                }
+            });
+        }
+    }
+
+    async fn handle_key(&mut self, key: KeyEvent) {
+        // Handle Ctrl+C globally
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            self.should_quit = true;
+            return;
+        }
+
+        // Handle description editor mode
+        if self.editing_description {
+            if let Some(ref mut textarea) = self.description_editor {
+                match (key.modifiers, key.code) {
+                    (KeyModifiers::CONTROL, KeyCode::Char('s')) => {
+                        // Save the description
+                        self.save_description().await;
+                    }
+                    (_, KeyCode::Esc) => {
+                        // Cancel editing
+                        self.editing_description = false;
+                        self.description_editor = None;
+                        self.set_message("Description edit cancelled");
+                    }
+                    _ => {
+                        // Pass input to textarea
+                        let input = Input::from(key);
+                        textarea.input(input);
+                    }
+                }
+            }
+            return;
+        }
+
+        // Handle confirmation prompt - only 'y' proceeds, any other key cancels
+        if let Some(action) = self.pending_action.take() {
+            if matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+                self.run_pending_action(action).await;
+            } else {
+                self.set_message("Cancelled");
+            }
+            return;
+        }
+
+        // Handle label removal picker
+        if self.label_picker.is_some() {
+            self.handle_label_picker_keys(key).await;
+            return;
+        }
+
+        if self.workflow_picker.is_some() {
+            self.handle_workflow_picker_keys(key);
+            return;
+        }
+
+        if self.review_picker.is_some() {
+            self.handle_review_picker_keys(key).await;
+            return;
+        }
+
+        // Handle input mode
+        if let Some(mode) = self.input_mode {
+            match key.code {
+                KeyCode::Esc => {
+                    self.input_mode = None;
+                    self.input_buffer.clear();
+                    self.clear_prompt();
+                    self.dispatch = None;
+                    if mode == InputMode::FilterPrs {
+                        self.pr_query.clear();
+                        self.apply_pr_filter();
+                    }
+                }
+                KeyCode::Enter => {
+                    self.input_mode = None;
+                    self.clear_prompt();
+                    match mode {
+                        InputMode::Search => {
                             self.log_search = Some(self.input_buffer.clone());
                             self.find_log_matches();
                         }
+                        InputMode::GotoLine => {
+                            self.goto_log_line();
+                        }
                         InputMode::Comment => {
                             self.submit_comment().await;
                         }
+                        InputMode::DismissReview => {
+                            self.submit_dismiss_review().await;
+                        }
+                        InputMode::ApproveComment => {
+                            // An empty comment approves without one
+                            let body = self.input_buffer.trim().to_string();
+                            self.approve_pr((!body.is_empty()).then_some(body)).await;
+                        }
                         InputMode::EditTitle => {
                             self.submit_edit_title().await;
                         }
@@ -587,192 +1547,466 @@ impl App {
                         InputMode::AddReviewer => {
                             self.submit_add_reviewer().await;
                         }
+                        InputMode::AddAssignee => {
+                            self.submit_assignee().await;
+                        }
+                        InputMode::Command => {
+                            self.submit_command();
+                        }
+                        InputMode::DispatchRef => {
+                            self.submit_dispatch_ref();
+                        }
+                        InputMode::DispatchInput => {
+                            self.submit_dispatch_input();
+                        }
+                        // Already applied while typing
+                        InputMode::FilterPrs => {}
+                        InputMode::ApproveDeployment => {
+                            self.submit_deployment_review(true).await;
+                        }
+                        InputMode::RejectDeployment => {
+                            self.submit_deployment_review(false).await;
+                        }
+                        InputMode::SearchDiff => {
+                            let query = self.input_buffer.trim();
+                            self.diff_search = (!query.is_empty()).then(|| query.to_string());
+                            self.goto_diff_match(true, true);
+                        }
+                    }
+                    // Unless the submit opened a follow-up prompt
+                    if self.input_mode.is_none() {
+                        self.input_buffer.clear();
                     }
-                    self.input_mode = None;
-                    self.input_buffer.clear();
-                    self.status_message = None;
                 }
                 KeyCode::Backspace => {
                     self.input_buffer.pop();
                 }
-                KeyCode::Char(c) => {
-                    // Limit input buffer to prevent unbounded memory usage
-                    if self.input_buffer.len() < 1024 {
-                        self.input_buffer.push(c);
-                    }
+                // Limit input buffer (in chars, not bytes) to prevent unbounded memory usage
+                KeyCode::Char(c) if self.input_buffer.chars().count() < 1024 => {
+                    self.input_buffer.push(c);
                 }
                 _ => {}
             }
+            // The PR list narrows as the query is typed
+            if mode == InputMode::FilterPrs && self.input_mode.is_some() && self.pr_query != self.input_buffer {
+                self.pr_query = self.input_buffer.clone();
+                self.apply_pr_filter();
+            }
             return;
         }
 
         // Handle help overlay
         if self.show_help {
-            if key.code == KeyCode::Esc || key.code == KeyCode::Char('?') {
+            if key.code == KeyCode::Esc || self.keymap.action(&key, Scope::Global) == Some(Action::Help) {
                 self.show_help = false;
             }
             return;
         }
 
+        // Back dismisses an error before it goes back a view. The popup for a
+        // long error takes every key, scrolling it with j/k.
+        if self.error.is_some() {
+            let action = self.keymap.action(&key, Scope::Common);
+            if action == Some(Action::Back) {
+                self.error = None;
+                self.error_scroll = 0;
+                return;
+            }
+            if self.error_popup {
+                match action {
+                    Some(Action::NextItem) => self.error_scroll = self.error_scroll.saturating_add(1),
+                    Some(Action::PrevItem) => self.error_scroll = self.error_scroll.saturating_sub(1),
+                    _ => {}
+                }
+                return;
+            }
+        }
+
+        // Back behind the loading overlay abandons the fetch rather than waiting
+        // it out; with the spinner or no indicator the view stays usable as is
+        if self.loading
+            && self.config.loading_style.is_overlay()
+            && self.keymap.action(&key, Scope::Common) == Some(Action::Back)
+        {
+            self.cancel_loading();
+            return;
+        }
+
+        // Count prefix for j/k motions, e.g. `10j`
+        if self.push_count_digit(&key) {
+            return;
+        }
+        let count = self.count.take().unwrap_or(1);
+        let motion = matches!(self.keymap.action(&key, Scope::Common), Some(Action::NextItem | Action::PrevItem));
+
         // Global keys
-        match key.code {
-            KeyCode::Char('q') => {
-                self.should_quit = true;
+        if let Some(action) = self.keymap.action(&key, Scope::Global) {
+            if self.handle_global_action(action) {
                 return;
             }
-            KeyCode::Char('?') => {
+        }
+
+        // Tab-specific keys; a motion runs `count` times
+        for _ in 0..if motion { count } else { 1 } {
+            match self.tab {
+                Tab::PRs => self.handle_pr_keys(key).await,
+                Tab::Actions => self.handle_actions_keys(key).await,
+                Tab::Logs => self.handle_logs_keys(key),
+                Tab::Notifications => self.handle_notification_keys(key),
+            }
+        }
+    }
+
+    /// Click to select in the PR, run and job lists; the wheel scrolls the diff
+    /// and log panes and moves through lists
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        const WHEEL_LINES: u16 = 3;
+
+        // Overlays and prompts take the keyboard only
+        if self.input_mode.is_some()
+            || self.pending_action.is_some()
+            || self.show_help
+            || self.editing_description
+            || self.label_picker.is_some()
+            || self.workflow_picker.is_some()
+            || self.review_picker.is_some()
+            || (self.error_popup && self.error.is_some())
+        {
+            return;
+        }
+
+        let at = Position::new(mouse.column, mouse.row);
+        let areas = self.mouse_areas;
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if let Some(row) = list_row(areas.pr_list, at, self.pr_list_state.offset()) {
+                    if row < self.prs.len() {
+                        self.focus = Focus::List;
+                        // A click on the highlighted PR opens it, like Enter
+                        if self.pr_list_state.selected() == Some(row) {
+                            self.select_pr();
+                            self.view = View::Detail;
+                        } else {
+                            self.pr_list_state.select(Some(row));
+                            self.mark_pr_seen();
+                        }
+                    }
+                } else if let Some(row) = list_row(areas.runs, at, self.run_list_state.offset()) {
+                    if row < self.runs.len() {
+                        self.run_list_state.select(Some(row));
+                        self.select_run();
+                        self.view = View::Jobs;
+                    }
+                } else if let Some(job) = list_row(areas.jobs, at, 0).and_then(|row| self.job_at_row(row)) {
+                    self.artifacts_focused = false;
+                    // A click on the highlighted job opens its logs
+                    if self.job_list_state.selected() == Some(job) {
+                        self.fetch_logs();
+                        self.tab = Tab::Logs;
+                    } else {
+                        self.job_list_state.select(Some(job));
+                    }
+                }
+            }
+            MouseEventKind::ScrollDown | MouseEventKind::ScrollUp => {
+                let down = mouse.kind == MouseEventKind::ScrollDown;
+                let scroll = |value: u16| if down { value.saturating_add(WHEEL_LINES) } else { value.saturating_sub(WHEEL_LINES) };
+                if areas.diff.contains(at) {
+                    self.diff_scroll = scroll(self.diff_scroll);
+                } else if areas.logs.contains(at) {
+                    self.log_scroll = scroll(self.log_scroll);
+                } else if areas.pr_list.contains(at) {
+                    if down { self.next_pr() } else { self.previous_pr() }
+                } else if areas.runs.contains(at) {
+                    if down { self.next_run() } else { self.previous_run() }
+                } else if areas.jobs.contains(at) {
+                    if down { self.next_job() } else { self.previous_job() }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Run a Global-scope action; false if it isn't one
+    fn handle_global_action(&mut self, action: Action) -> bool {
+        match action {
+            Action::Quit => {
+                self.should_quit = true;
+                true
+            }
+            Action::Help => {
                 self.show_help = true;
-                return;
+                true
             }
-            KeyCode::Char('1') => {
+            Action::TabPrs => {
                 self.tab = Tab::PRs;
                 self.view = View::List;
                 self.focus = Focus::List;
-                return;
+                true
             }
-            KeyCode::Char('2') => {
+            Action::TabActions => {
                 self.tab = Tab::Actions;
                 self.view = View::List;
-                return;
+                true
             }
-            KeyCode::Char('3') => {
+            Action::TabLogs => {
                 self.tab = Tab::Logs;
-                return;
+                true
+            }
+            Action::TabNotifications => {
+                self.tab = Tab::Notifications;
+                self.load_notifications_once();
+                true
             }
-            KeyCode::Char('r') => {
+            Action::Refresh => {
                 self.refresh();
-                return;
+                true
+            }
+            Action::Command => {
+                self.input_mode = Some(InputMode::Command);
+                self.show_prompt("Command: 1234 or pr 1234 (jump to PR), repo owner/name");
+                true
+            }
+            Action::ToggleAutoRefresh => {
+                self.auto_refresh = !self.auto_refresh;
+                self.last_poll = None;
+                self.set_message(if self.auto_refresh {
+                    "Auto-refresh on: polling running workflows"
+                } else {
+                    "Auto-refresh off"
+                });
+                true
             }
-            KeyCode::Tab => {
-                // Cycle through tabs: PRs -> Actions -> Logs -> PRs
+            Action::NextTab => {
+                // Cycle through tabs: PRs -> Actions -> Logs -> Notifications -> PRs
                 self.tab = match self.tab {
                     Tab::PRs => Tab::Actions,
                     Tab::Actions => Tab::Logs,
-                    Tab::Logs => Tab::PRs,
+                    Tab::Logs => Tab::Notifications,
+                    Tab::Notifications => Tab::PRs,
                 };
                 self.view = View::List;
-                return;
+                self.load_notifications_once();
+                true
             }
-            KeyCode::BackTab => {
-                // Reverse cycle: PRs -> Logs -> Actions -> PRs
+            Action::PrevTab => {
+                // Reverse cycle: PRs -> Notifications -> Logs -> Actions -> PRs
                 self.tab = match self.tab {
-                    Tab::PRs => Tab::Logs,
+                    Tab::PRs => Tab::Notifications,
                     Tab::Actions => Tab::PRs,
                     Tab::Logs => Tab::Actions,
+                    Tab::Notifications => Tab::Logs,
                 };
                 self.view = View::List;
-                return;
-            }
-            KeyCode::Char('n') if self.tab == Tab::PRs && self.view == View::List => {
-                self.create_pr();
-                return;
+                self.load_notifications_once();
+                true
             }
-            _ => {}
+            _ => false,
         }
+    }
 
-        // Tab-specific keys
-        match self.tab {
-            Tab::PRs => self.handle_pr_keys(key).await,
-            Tab::Actions => self.handle_actions_keys(key).await,
-            Tab::Logs => self.handle_logs_keys(key),
+    /// Collect a digit of a count prefix. A count can't start with 0 or with a
+    /// digit bound to a global action (1-4 switch tabs), which acts right away.
+    fn push_count_digit(&mut self, key: &KeyEvent) -> bool {
+        const MAX_COUNT: usize = 9999;
+
+        let KeyCode::Char(c) = key.code else {
+            return false;
+        };
+        let Some(digit) = c.to_digit(10).filter(|_| key.modifiers.difference(KeyModifiers::SHIFT).is_empty()) else {
+            return false;
+        };
+        if self.count.is_none() && (digit == 0 || self.keymap.action(key, Scope::Global).is_some()) {
+            return false;
         }
+
+        let count = self.count.unwrap_or(0) * 10 + digit as usize;
+        self.count = Some(count.min(MAX_COUNT));
+        true
     }
 
     async fn handle_pr_keys(&mut self, key: KeyEvent) {
         match self.view {
-            View::List | View::Detail => match key.code {
-                KeyCode::Char('j') | KeyCode::Down => {
+            View::List | View::Detail => match self.keymap.action(&key, Scope::Prs) {
+                Some(Action::NextItem) => {
                     match self.focus {
                         Focus::List => self.next_pr(),
+                        Focus::Description => self.body_scroll = self.body_scroll.saturating_add(1),
                         Focus::Detail => self.diff_scroll = self.diff_scroll.saturating_add(1),
                         Focus::PrChecks => self.next_pr_check(),
                     }
                 }
-                KeyCode::Char('k') | KeyCode::Up => {
+                Some(Action::PrevItem) => {
                     match self.focus {
                         Focus::List => self.previous_pr(),
+                        Focus::Description => self.body_scroll = self.body_scroll.saturating_sub(1),
                         Focus::Detail => self.diff_scroll = self.diff_scroll.saturating_sub(1),
                         Focus::PrChecks => self.previous_pr_check(),
                     }
                 }
-                KeyCode::Char('h') | KeyCode::Left => {
+                Some(Action::FocusLeft) => {
                     self.focus = Focus::List;
                 }
-                KeyCode::Char('l') | KeyCode::Right => {
+                Some(Action::FocusRight) => {
                     if self.focus == Focus::List {
+                        self.focus = Focus::Description;
+                    } else if self.focus == Focus::Description {
                         self.focus = Focus::Detail;
                     } else if self.focus == Focus::Detail {
                         self.focus = Focus::PrChecks;
                     }
                 }
-                KeyCode::Char('o') => {
-                    // Cycle focus: List -> Detail -> PrChecks -> List
+                Some(Action::CycleFocus) => {
+                    // Cycle focus: List -> Description -> Detail -> PrChecks -> List
                     self.focus = match self.focus {
-                        Focus::List => Focus::Detail,
+                        Focus::List => Focus::Description,
+                        Focus::Description => Focus::Detail,
                         Focus::Detail => Focus::PrChecks,
                         Focus::PrChecks => Focus::List,
                     };
                 }
-                KeyCode::Enter => {
+                Some(Action::Select) => {
                     if self.focus == Focus::List {
                         self.select_pr();
                         self.view = View::Detail;
                         self.focus = Focus::Detail;
-                    } else if self.focus == Focus::PrChecks {
+                    } else if self.focus == Focus::PrChecks && !self.toggle_check_group() {
                         // View logs for selected check
                         self.view_pr_check_jobs();
                     }
                 }
-                KeyCode::Esc => {
-                    if self.view == View::Detail {
-                        self.view = View::List;
-                        self.focus = Focus::List;
-                    }
+                Some(Action::Back) if self.view == View::Detail => {
+                    self.view = View::List;
+                    self.focus = Focus::List;
                 }
-                KeyCode::Char('d') => {
-                    if self.selected_pr.is_some() {
-                        self.view = View::Diff;
-                        self.diff_scroll = 0;
-                    }
+                Some(Action::Back) if !self.selected_prs.is_empty() => {
+                    self.selected_prs.clear();
+                    self.set_message("Selection cleared");
                 }
-                KeyCode::Char('v') => {
-                    self.approve_pr().await;
+                Some(Action::Back) if !self.pr_query.is_empty() => {
+                    self.pr_query.clear();
+                    self.apply_pr_filter();
                 }
-                KeyCode::Char('x') => {
-                    self.input_mode = Some(InputMode::Comment);
-                    self.status_message = Some(StatusMessage::prompt("Enter comment for request changes:"));
+                Some(Action::Search) if self.view == View::List => {
+                    self.input_mode = Some(InputMode::FilterPrs);
+                    self.input_buffer = self.pr_query.clone();
+                    self.show_prompt("Filter PRs:");
                 }
-                KeyCode::Char('c') => {
-                    self.input_mode = Some(InputMode::Comment);
-                    self.status_message = Some(StatusMessage::prompt("Enter comment:"));
+                Some(Action::Search) if self.focus == Focus::Detail => {
+                    self.input_mode = Some(InputMode::SearchDiff);
+                    self.show_prompt("Search diff:");
                 }
-                KeyCode::Char('m') => {
-                    self.merge_pr().await;
+                Some(Action::ToggleSelect) if self.focus == Focus::List => {
+                    self.toggle_pr_selection();
                 }
-                KeyCode::Char('C') => {
-                    self.checkout_pr();
+                Some(Action::NewPr) if self.view == View::List => {
+                    self.create_pr();
                 }
-                KeyCode::Char('f') => {
-                    self.cycle_filter();
+                Some(Action::ViewDiff) if self.selected_pr.is_some() => {
+                    self.view = View::Diff;
+                    self.diff_scroll = 0;
                 }
-                KeyCode::Char('R') => {
-                    // Rerun selected PR check
-                    self.rerun_pr_check().await;
+                Some(Action::Approve) if !self.selected_prs.is_empty() => {
+                    self.pending_action = Some(PendingAction::ApproveSelected(self.selected_prs.len()));
                 }
-                KeyCode::Char('L') => {
-                    // View logs for selected PR check
-                    self.view_pr_check_jobs();
+                Some(Action::Approve) => {
+                    self.approve_pr(None).await;
                 }
-                KeyCode::Char('e') => {
-                    // Edit PR title
-                    if self.selected_pr.is_some() {
-                        self.input_mode = Some(InputMode::EditTitle);
-                        self.input_buffer = self.selected_pr.as_ref().map(|p| p.title.clone()).unwrap_or_default();
-                        self.status_message = Some(StatusMessage::prompt("Edit PR title:"));
-                    }
+                Some(Action::ApproveWithComment) if self.selected_pr.is_some() => {
+                    self.input_mode = Some(InputMode::ApproveComment);
+                    self.show_prompt("Approve with comment:");
                 }
-                KeyCode::Char('E') => {
-                    // Edit PR description with built-in editor
+                Some(Action::RequestChanges) => {
+                    self.input_mode = Some(InputMode::Comment);
+                    self.show_prompt("Enter comment for request changes:");
+                }
+                Some(Action::Comment) => {
+                    self.input_mode = Some(InputMode::Comment);
+                    self.show_prompt("Enter comment:");
+                }
+                Some(Action::Merge) if !self.selected_prs.is_empty() => {
+                    let count = self.selected_prs.len();
+                    self.pending_action = Some(PendingAction::MergeSelected(count, self.config.merge_method));
+                }
+                Some(Action::Merge) => {
+                    if let Some(pr) = &self.selected_pr {
+                        let blockers = merge_blockers(pr);
+                        if blockers.is_empty() {
+                            let warnings = self.merge_warnings(pr);
+                            self.pending_action = Some(PendingAction::MergePr(pr.number, self.config.merge_method, warnings));
+                        } else {
+                            self.error = Some(format!("Cannot merge #{}: {}", pr.number, blockers.join(", ")));
+                        }
+                    }
+                }
+                Some(Action::Checkout) => {
+                    self.checkout_pr();
+                }
+                Some(Action::CloseReopen) => {
+                    // Close an open PR (confirmed) or reopen a closed one
+                    if let Some(pr) = &self.selected_pr {
+                        let pr_number = pr.number;
+                        if pr.merged {
+                            self.set_message("Merged PRs cannot be reopened");
+                        } else if pr.state == "closed" {
+                            self.set_pr_open(pr_number, true).await;
+                        } else {
+                            self.pending_action = Some(PendingAction::ClosePr(pr_number));
+                        }
+                    }
+                }
+                Some(Action::UpdateBranch) if self.view == View::Detail => {
+                    if let Some(pr) = &self.selected_pr {
+                        if pr.state == "open" {
+                            self.pending_action = Some(PendingAction::UpdateBranch(pr.number));
+                        } else {
+                            self.set_message("Only open PRs can be updated");
+                        }
+                    }
+                }
+                Some(Action::ToggleAutoMerge) => {
+                    if let Some(pr) = &self.selected_pr {
+                        let pr_number = pr.number;
+                        if pr.state != "open" {
+                            self.set_message("Only open PRs can be auto-merged");
+                        } else if self.pr_auto_merge.is_some() {
+                            self.set_auto_merge(pr_number, None).await;
+                        } else {
+                            self.pending_action = Some(PendingAction::EnableAutoMerge(pr_number, self.config.merge_method));
+                        }
+                    }
+                }
+                Some(Action::CycleFilter) => {
+                    self.cycle_filter();
+                }
+                Some(Action::CyclePrSort) => {
+                    self.pr_sort = match self.pr_sort {
+                        PrSort::Created => PrSort::Updated,
+                        PrSort::Updated => PrSort::Number,
+                        PrSort::Number => PrSort::Title,
+                        PrSort::Title => PrSort::Created,
+                    };
+                    self.apply_pr_filter();
+                }
+                Some(Action::Rerun) => {
+                    // Rerun selected PR check
+                    self.rerun_pr_check().await;
+                }
+                Some(Action::ViewLogs) => {
+                    // View logs for selected PR check
+                    self.view_pr_check_jobs();
+                }
+                Some(Action::FailureLogs) => {
+                    self.jump_to_failure();
+                }
+                Some(Action::EditTitle) if self.selected_pr.is_some() => {
+                    // Edit PR title
+                    self.input_mode = Some(InputMode::EditTitle);
+                    self.input_buffer = self.selected_pr.as_ref().map(|p| p.title.clone()).unwrap_or_default();
+                    self.show_prompt("Edit PR title:");
+                }
+                Some(Action::EditDescription) => {
+                    // Edit PR description with built-in editor
                     if let Some(pr) = &self.selected_pr {
                         let content = pr.body.clone().unwrap_or_default();
                         let mut textarea = TextArea::new(content.lines().map(|s| s.to_string()).collect());
@@ -786,74 +2020,150 @@ impl App {
                         self.editing_description = true;
                     }
                 }
-                KeyCode::Char('a') => {
+                Some(Action::AddReviewer) if self.selected_pr.is_some() => {
                     // Add reviewer
-                    if self.selected_pr.is_some() {
-                        self.input_mode = Some(InputMode::AddReviewer);
-                        self.status_message = Some(StatusMessage::prompt("Add reviewer (username):"));
-                    }
+                    self.input_mode = Some(InputMode::AddReviewer);
+                    self.show_prompt("Add reviewer (username):");
                 }
-                KeyCode::Char('b') => {
+                Some(Action::Assign) if self.selected_pr.is_some() => {
+                    // Assign (or unassign with a leading '-')
+                    self.input_mode = Some(InputMode::AddAssignee);
+                    self.show_prompt("Assign user (username, -username to unassign):");
+                }
+                Some(Action::AddLabel) if self.selected_pr.is_some() => {
                     // Add label
-                    if self.selected_pr.is_some() {
-                        self.input_mode = Some(InputMode::AddLabel);
-                        self.status_message = Some(StatusMessage::prompt("Add label:"));
+                    self.input_mode = Some(InputMode::AddLabel);
+                    self.show_prompt("Add label:");
+                }
+                Some(Action::Reviews) if self.selected_pr.is_some() => {
+                    if self.pr_reviews.is_empty() {
+                        self.set_message("PR has no reviews");
+                    } else {
+                        let mut state = ListState::default();
+                        state.select(Some(0));
+                        self.review_picker = Some(state);
+                    }
+                }
+                Some(Action::RemoveLabel) => {
+                    // Pick a label to remove
+                    if let Some(pr) = &self.selected_pr {
+                        if pr.labels.is_empty() {
+                            self.set_message("PR has no labels");
+                        } else {
+                            let mut state = ListState::default();
+                            state.select(Some(0));
+                            self.label_picker = Some(state);
+                        }
                     }
                 }
-                KeyCode::Char('w') => {
+                Some(Action::OpenInBrowser) => {
                     // Open PR in browser
                     self.open_pr_in_browser();
                 }
-                KeyCode::Char('y') => {
+                Some(Action::NextUrl) => {
+                    self.next_url();
+                }
+                Some(Action::OpenUrl) => {
+                    self.open_selected_url();
+                }
+                Some(Action::CopyBranch) => {
                     // Copy branch name to clipboard
                     self.copy_branch_to_clipboard();
                 }
-                KeyCode::Char('Y') => {
+                Some(Action::CopyCheckout) => {
                     // Copy checkout command to clipboard
                     self.copy_checkout_command_to_clipboard();
                 }
-                KeyCode::Char('u') => {
+                Some(Action::CopyUrl) => {
                     // Copy PR URL to clipboard
                     self.copy_pr_url_to_clipboard();
                 }
-                KeyCode::Char('p') => {
+                Some(Action::ToggleCommits) => {
                     // Toggle diff mode (Full <-> ByCommit)
                     self.toggle_diff_mode();
                 }
-                KeyCode::Char('P') => {
+                Some(Action::ToggleWhitespace) => {
+                    self.toggle_ignore_whitespace();
+                }
+                Some(Action::LoadFullDiff) => {
+                    self.load_full_diff();
+                }
+                Some(Action::CreatePrFromBranch) => {
                     // Create PR from recent branch (if available)
                     self.create_pr_from_recent_branch();
                 }
-                KeyCode::Char('[') => {
+                Some(Action::PrevCommit) if self.diff_mode == DiffMode::ByCommit => {
                     // Previous commit (in commit mode)
-                    if self.diff_mode == DiffMode::ByCommit {
-                        self.previous_commit();
-                        self.load_selected_commit_diff();
-                    }
+                    self.previous_commit();
+                    self.load_selected_commit_diff();
                 }
-                KeyCode::Char(']') => {
+                Some(Action::NextCommit) if self.diff_mode == DiffMode::ByCommit => {
                     // Next commit (in commit mode)
-                    if self.diff_mode == DiffMode::ByCommit {
-                        self.next_commit();
-                        self.load_selected_commit_diff();
-                    }
+                    self.next_commit();
+                    self.load_selected_commit_diff();
+                }
+                Some(Action::CopyCommitSha) if self.diff_mode == DiffMode::ByCommit => {
+                    self.copy_commit_sha_to_clipboard();
+                }
+                Some(Action::CopyCommitMessage) if self.diff_mode == DiffMode::ByCommit => {
+                    self.copy_commit_message_to_clipboard();
                 }
                 _ => {}
             },
-            View::Diff => match key.code {
-                KeyCode::Char('j') | KeyCode::Down => {
+            View::Diff => match self.keymap.action(&key, Scope::Diff) {
+                Some(Action::NextItem) => {
                     self.diff_scroll = self.diff_scroll.saturating_add(1);
                 }
-                KeyCode::Char('k') | KeyCode::Up => {
+                Some(Action::PrevItem) => {
                     self.diff_scroll = self.diff_scroll.saturating_sub(1);
                 }
-                KeyCode::PageDown => {
+                Some(Action::PageDown) => {
                     self.diff_scroll = self.diff_scroll.saturating_add(20);
                 }
-                KeyCode::PageUp => {
+                Some(Action::PageUp) => {
                     self.diff_scroll = self.diff_scroll.saturating_sub(20);
                 }
-                KeyCode::Esc | KeyCode::Char('q') => {
+                Some(Action::HalfPageDown) => {
+                    self.diff_scroll = self.diff_scroll.saturating_add((self.diff_height / 2).max(1));
+                }
+                Some(Action::HalfPageUp) => {
+                    self.diff_scroll = self.diff_scroll.saturating_sub((self.diff_height / 2).max(1));
+                }
+                Some(Action::NextFile) => {
+                    let scroll = self.diff_scroll as usize;
+                    if let Some(&offset) = self.file_offsets().iter().find(|&&o| o > scroll) {
+                        self.diff_scroll = offset as u16;
+                    }
+                }
+                Some(Action::PrevFile) => {
+                    let scroll = self.diff_scroll as usize;
+                    if let Some(&offset) = self.file_offsets().iter().rev().find(|&&o| o < scroll) {
+                        self.diff_scroll = offset as u16;
+                    }
+                }
+                Some(Action::ToggleDiffLayout) => {
+                    self.toggle_diff_layout();
+                }
+                Some(Action::ToggleViewed) => {
+                    self.toggle_file_viewed();
+                }
+                Some(Action::ToggleWhitespace) => {
+                    self.toggle_ignore_whitespace();
+                }
+                Some(Action::LoadFullDiff) => {
+                    self.load_full_diff();
+                }
+                Some(Action::Search) => {
+                    self.input_mode = Some(InputMode::SearchDiff);
+                    self.show_prompt("Search diff:");
+                }
+                Some(Action::NextMatch) => {
+                    self.goto_diff_match(true, false);
+                }
+                Some(Action::PrevMatch) => {
+                    self.goto_diff_match(false, false);
+                }
+                Some(Action::Back) => {
                     self.view = View::Detail;
                 }
                 _ => {}
@@ -862,41 +2172,332 @@ impl App {
         }
     }
 
+    /// Whether the full diff is drawn side by side: chosen, and the pane is wide enough
+    pub fn diff_split_shown(&self) -> bool {
+        self.diff_layout == DiffLayout::Split && self.diff_width >= ui::SPLIT_DIFF_MIN_WIDTH
+    }
+
+    /// File header offsets in the layout currently on screen
+    fn file_offsets(&self) -> &[usize] {
+        if self.diff_split_shown() {
+            &self.pr_diff_view.split_file_offsets
+        } else {
+            &self.pr_diff_view.file_offsets
+        }
+    }
+
+    /// The diff on screen and whether it's side by side: the PR's in the full diff
+    /// view, otherwise the preview's, which may be a single commit's
+    fn shown_diff_view(&self) -> (&ui::RenderedDiff, bool) {
+        if self.view == View::Diff {
+            (&self.pr_diff_view, self.diff_split_shown() && self.pr_diff_view.held_back.is_none())
+        } else if self.diff_mode == DiffMode::ByCommit {
+            (&self.commit_diff_view, false)
+        } else {
+            (&self.pr_diff_view, false)
+        }
+    }
+
+    /// Scroll to the next diff line matching the search, wrapping around. A new
+    /// search may stay on the top line if it matches.
+    fn goto_diff_match(&mut self, forward: bool, include_top: bool) {
+        let Some(query) = self.diff_search.clone() else {
+            return;
+        };
+        let (view, split) = self.shown_diff_view();
+        let matches = view.find(&query, split);
+        let (Some(&first), Some(&last)) = (matches.first(), matches.last()) else {
+            self.set_message(format!("No matches for \"{}\"", query));
+            return;
+        };
+
+        let top = self.diff_scroll as usize;
+        let target = if forward {
+            matches.iter().copied().find(|&m| m > top || (include_top && m == top)).unwrap_or(first)
+        } else {
+            matches.iter().copied().rev().find(|&m| m < top).unwrap_or(last)
+        };
+        let index = matches.iter().position(|&m| m == target).unwrap_or(0);
+        self.diff_scroll = target as u16;
+        self.set_message(format!("Match {} of {}", index + 1, matches.len()));
+    }
+
+    /// Restyle the diffs after they, the whitespace setting or the viewed files changed
+    fn update_rendered_diffs(&mut self) {
+        let viewed = self.pr_viewed_files();
+        let render = |diff: Option<&str>| {
+            diff.map(|diff| ui::RenderedDiff::new(diff, self.diff_ignore_ws, viewed, self.diff_full, &self.theme))
+                .unwrap_or_default()
+        };
+        let pr_diff_view = render(self.pr_diff.as_deref());
+        let commit_diff_view = render(self.commit_diff.as_deref());
+        self.pr_diff_view = pr_diff_view;
+        self.commit_diff_view = commit_diff_view;
+    }
+
+    /// Style a diff that was held back for its size
+    fn load_full_diff(&mut self) {
+        if self.pr_diff_view.held_back.is_none() && self.commit_diff_view.held_back.is_none() {
+            return;
+        }
+        self.diff_full = true;
+        self.update_rendered_diffs();
+    }
+
+    /// Files of the selected PR marked as viewed
+    pub fn pr_viewed_files(&self) -> Option<&HashSet<String>> {
+        self.viewed_files.get(&self.selected_pr.as_ref()?.number)
+    }
+
+    /// Viewed files and all files in the diff as displayed
+    pub fn viewed_count(&self) -> (usize, usize) {
+        let files = &self.pr_diff_view.files;
+        let viewed = self.pr_viewed_files().map_or(0, |viewed| {
+            files.iter().filter(|f| viewed.contains(*f)).count()
+        });
+        (viewed, files.len())
+    }
+
+    /// Mark the file at the top of the diff as viewed and move on to the next
+    /// one, or unmark it
+    fn toggle_file_viewed(&mut self) {
+        let Some(number) = self.selected_pr.as_ref().map(|pr| pr.number) else {
+            return;
+        };
+        let scroll = self.diff_scroll as usize;
+        let offsets = self.file_offsets();
+        let index = offsets.iter().rposition(|&o| o <= scroll).unwrap_or(0);
+        let next = offsets.get(index + 1).copied();
+        let Some(file) = self.pr_diff_view.files.get(index).cloned() else {
+            return;
+        };
+
+        let viewed = self.viewed_files.entry(number).or_default();
+        if viewed.remove(&file) {
+            self.set_message(format!("Unmarked {}", file));
+        } else {
+            viewed.insert(file);
+            if let Some(next) = next {
+                self.diff_scroll = next as u16;
+            }
+        }
+        self.update_rendered_diffs();
+    }
+
+    /// Show or hide whitespace-only hunks. Lines shift, so go back to the top.
+    fn toggle_ignore_whitespace(&mut self) {
+        self.diff_ignore_ws = !self.diff_ignore_ws;
+        self.diff_scroll = 0;
+        self.update_rendered_diffs();
+        self.set_message(if self.diff_ignore_ws {
+            "Hiding whitespace-only changes"
+        } else {
+            "Showing whitespace changes"
+        });
+    }
+
+    /// Switch between unified and split, staying at the top of the current file
+    fn toggle_diff_layout(&mut self) {
+        let scroll = self.diff_scroll as usize;
+        let file = self.file_offsets().iter().rposition(|&o| o <= scroll);
+
+        self.diff_layout = match self.diff_layout {
+            DiffLayout::Unified => DiffLayout::Split,
+            DiffLayout::Split => DiffLayout::Unified,
+        };
+        self.diff_scroll = file
+            .and_then(|i| self.file_offsets().get(i))
+            .map_or(0, |&o| o as u16);
+    }
+
+    async fn handle_label_picker_keys(&mut self, key: KeyEvent) {
+        let len = self.selected_pr.as_ref().map(|pr| pr.labels.len()).unwrap_or(0);
+        let Some(state) = self.label_picker.as_mut() else {
+            return;
+        };
+
+        match self.keymap.action(&key, Scope::Common) {
+            Some(Action::NextItem) if len > 0 => {
+                let i = state.selected().map(|i| (i + 1) % len).unwrap_or(0);
+                state.select(Some(i));
+            }
+            Some(Action::PrevItem) if len > 0 => {
+                let i = state.selected().map(|i| (i + len - 1) % len).unwrap_or(0);
+                state.select(Some(i));
+            }
+            Some(Action::Select) => {
+                let label = state.selected()
+                    .and_then(|i| self.selected_pr.as_ref()?.labels.get(i))
+                    .map(|l| l.name.clone());
+                self.label_picker = None;
+                if let Some(label) = label {
+                    self.remove_label(&label).await;
+                }
+            }
+            Some(Action::Back) => {
+                self.label_picker = None;
+            }
+            _ if key.code == KeyCode::Char('q') => {
+                self.label_picker = None;
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_workflow_picker_keys(&mut self, key: KeyEvent) {
+        let len = self.workflows.len();
+        let Some(state) = self.workflow_picker.as_mut() else {
+            return;
+        };
+
+        match self.keymap.action(&key, Scope::Common) {
+            Some(Action::NextItem) if len > 0 => {
+                let i = state.selected().map(|i| (i + 1) % len).unwrap_or(0);
+                state.select(Some(i));
+            }
+            Some(Action::PrevItem) if len > 0 => {
+                let i = state.selected().map(|i| (i + len - 1) % len).unwrap_or(0);
+                state.select(Some(i));
+            }
+            Some(Action::Select) => {
+                let workflow = state.selected().and_then(|i| self.workflows.get(i)).cloned();
+                self.workflow_picker = None;
+                if let Some(workflow) = workflow {
+                    self.show_prompt(format!("Run '{}' on branch or tag:", workflow.name));
+                    self.input_mode = Some(InputMode::DispatchRef);
+                    self.input_buffer = self.default_branch.clone();
+                    self.dispatch = Some(DispatchForm {
+                        workflow,
+                        git_ref: String::new(),
+                        inputs: Vec::new(),
+                        values: HashMap::new(),
+                    });
+                }
+            }
+            Some(Action::Back) => {
+                self.workflow_picker = None;
+            }
+            _ if key.code == KeyCode::Char('q') => {
+                self.workflow_picker = None;
+            }
+            _ => {}
+        }
+    }
+
     async fn handle_actions_keys(&mut self, key: KeyEvent) {
         match self.view {
-            View::List => match key.code {
-                KeyCode::Char('j') | KeyCode::Down => {
+            View::List => match self.keymap.action(&key, Scope::Actions) {
+                Some(Action::NextItem) => {
                     self.next_run();
                 }
-                KeyCode::Char('k') | KeyCode::Up => {
+                Some(Action::PrevItem) => {
                     self.previous_run();
                 }
-                KeyCode::Enter => {
+                Some(Action::Select) => {
                     self.select_run();
                     self.view = View::Jobs;
                 }
-                KeyCode::Char('R') => {
+                Some(Action::Rerun) => {
                     self.rerun_workflow().await;
                 }
+                Some(Action::Dispatch) => {
+                    self.loading = true;
+                    self.loading_what = Some("Loading workflows...".to_string());
+                    self.spawn_fetch_workflows();
+                }
+                Some(Action::OpenInBrowser) => {
+                    self.open_run_in_browser();
+                }
+                Some(Action::CopyUrl) => {
+                    self.copy_run_url_to_clipboard();
+                }
+                Some(Action::CycleFilter) => {
+                    self.run_filter.status = match self.run_filter.status {
+                        RunStatusFilter::All => RunStatusFilter::InProgress,
+                        RunStatusFilter::InProgress => RunStatusFilter::Failure,
+                        RunStatusFilter::Failure => RunStatusFilter::Success,
+                        RunStatusFilter::Success => RunStatusFilter::All,
+                    };
+                    self.reload_runs();
+                }
+                Some(Action::FilterRunBranch) => {
+                    if self.run_filter.branch.is_some() {
+                        self.run_filter.branch = None;
+                    } else if let Some(head) = self.local_head.as_ref().map(|h| h.branch.clone()) {
+                        self.run_filter.branch = Some(head);
+                    } else {
+                        self.set_message("No branch checked out to filter by");
+                        return;
+                    }
+                    self.reload_runs();
+                }
+                Some(Action::CycleRunEvent) => {
+                    self.run_filter.event = match self.run_filter.event {
+                        None => Some(RUN_EVENTS[0]),
+                        Some(event) => RUN_EVENTS
+                            .iter()
+                            .position(|e| *e == event)
+                            .and_then(|i| RUN_EVENTS.get(i + 1))
+                            .copied(),
+                    };
+                    self.reload_runs();
+                }
                 _ => {}
             },
-            View::Jobs => match key.code {
-                KeyCode::Char('j') | KeyCode::Down => {
+            View::Jobs => match self.keymap.action(&key, Scope::Actions) {
+                Some(Action::NextItem) if self.artifacts_focused => {
+                    self.next_artifact();
+                }
+                Some(Action::PrevItem) if self.artifacts_focused => {
+                    self.previous_artifact();
+                }
+                Some(Action::Select | Action::DownloadArtifact) if self.artifacts_focused => {
+                    self.download_artifact();
+                }
+                Some(Action::NextItem) => {
                     self.next_job();
                 }
-                KeyCode::Char('k') | KeyCode::Up => {
+                Some(Action::PrevItem) => {
                     self.previous_job();
                 }
-                KeyCode::Enter | KeyCode::Char('L') => {
+                Some(Action::FocusRight) if !self.artifacts.is_empty() => {
+                    self.artifacts_focused = true;
+                }
+                Some(Action::FocusLeft) => {
+                    self.artifacts_focused = false;
+                }
+                Some(Action::DownloadArtifact) => {
+                    self.download_artifact();
+                }
+                Some(Action::Select | Action::ViewLogs) => {
                     self.fetch_logs();
                     self.tab = Tab::Logs;
                 }
-                KeyCode::Esc => {
+                Some(Action::ApproveDeployment) => {
+                    self.start_deployment_review(InputMode::ApproveDeployment, "Approve");
+                }
+                Some(Action::RejectDeployment) => {
+                    self.start_deployment_review(InputMode::RejectDeployment, "Reject");
+                }
+                Some(Action::ToggleSteps) => {
+                    if let Some(id) = self.selected_job().map(|job| job.id) {
+                        if !self.expanded_jobs.remove(&id) {
+                            self.expanded_jobs.insert(id);
+                        }
+                    }
+                }
+                Some(Action::Back) => {
                     self.view = View::List;
                 }
-                KeyCode::Char('R') => {
+                Some(Action::Rerun) => {
                     self.rerun_workflow().await;
                 }
+                Some(Action::OpenInBrowser) => {
+                    self.open_run_in_browser();
+                }
+                Some(Action::CopyUrl) => {
+                    self.copy_run_url_to_clipboard();
+                }
                 _ => {}
             },
             _ => {}
@@ -904,47 +2505,106 @@ impl App {
     }
 
     fn handle_logs_keys(&mut self, key: KeyEvent) {
-        match key.code {
-            KeyCode::Char('j') | KeyCode::Down => {
+        // Vim-style `za` toggles the fold under the cursor
+        if std::mem::take(&mut self.log_pending_z) && key.code == KeyCode::Char('a') {
+            self.toggle_log_group();
+            return;
+        }
+        if key.code == KeyCode::Char('z') && self.keymap.action(&key, Scope::Logs).is_none() {
+            self.log_pending_z = true;
+            return;
+        }
+
+        match self.keymap.action(&key, Scope::Logs) {
+            Some(Action::ToggleFold) => {
+                self.toggle_log_group();
+            }
+            Some(Action::NextItem) => {
                 self.log_scroll = self.log_scroll.saturating_add(1);
             }
-            KeyCode::Char('k') | KeyCode::Up => {
+            Some(Action::PrevItem) => {
                 self.log_scroll = self.log_scroll.saturating_sub(1);
             }
-            KeyCode::Char('h') => {
+            Some(Action::ScrollLeft) => {
                 self.log_h_scroll = self.log_h_scroll.saturating_sub(10);
             }
-            KeyCode::Char('l') => {
+            Some(Action::ScrollRight) => {
                 self.log_h_scroll = self.log_h_scroll.saturating_add(10);
             }
-            KeyCode::PageDown => {
+            Some(Action::PageDown) => {
                 self.log_scroll = self.log_scroll.saturating_add(20);
             }
-            KeyCode::PageUp => {
+            Some(Action::PageUp) => {
                 self.log_scroll = self.log_scroll.saturating_sub(20);
             }
-            KeyCode::Char('g') => {
+            Some(Action::HalfPageDown) => {
+                self.log_scroll = self.log_scroll.saturating_add((self.log_height / 2).max(1));
+            }
+            Some(Action::HalfPageUp) => {
+                self.log_scroll = self.log_scroll.saturating_sub((self.log_height / 2).max(1));
+            }
+            Some(Action::Top) => {
                 self.log_scroll = 0;
                 self.log_h_scroll = 0;
             }
-            KeyCode::Char('G') => {
-                let line_count = self.logs.lines().count() as u16;
-                self.log_scroll = line_count.saturating_sub(20);
+            Some(Action::Bottom) => {
+                self.log_scroll = self.log_bottom();
+            }
+            Some(Action::LineStart) => {
+                self.log_h_scroll = 0;
+            }
+            Some(Action::ToggleColors) => {
+                self.log_plain = !self.log_plain;
+            }
+            Some(Action::ToggleProblems) => {
+                self.toggle_log_problems();
+            }
+            Some(Action::ToggleLineNumbers) => {
+                self.log_line_numbers = !self.log_line_numbers;
             }
-            KeyCode::Char('0') => {
+            Some(Action::ToggleWrap) => {
+                self.log_wrap = !self.log_wrap;
                 self.log_h_scroll = 0;
             }
-            KeyCode::Char('/') => {
+            Some(Action::SaveLogs) => {
+                self.save_logs();
+            }
+            Some(Action::SaveArchive) => {
+                self.save_log_archive();
+            }
+            Some(Action::Follow) => {
+                if self.log_follow {
+                    self.log_follow = false;
+                    self.set_message("Stopped following logs");
+                } else if self.selected_job().is_some_and(|j| j.status == "in_progress") {
+                    self.log_follow = true;
+                    self.last_log_poll = None;
+                    self.set_message("Following logs...");
+                } else {
+                    self.set_message("Job is not running");
+                }
+            }
+            Some(Action::Search) => {
                 self.input_mode = Some(InputMode::Search);
-                self.status_message = Some(StatusMessage::prompt("Search:"));
+                self.show_prompt("Search:");
+            }
+            Some(Action::GotoLine) => {
+                self.input_mode = Some(InputMode::GotoLine);
+                self.show_prompt(format!("Go to line (1-{}):", self.log_line_count()));
             }
-            KeyCode::Char('n') => {
+            Some(Action::NextMatch) => {
                 self.next_log_match();
             }
-            KeyCode::Char('N') => {
+            Some(Action::PrevMatch) => {
                 self.prev_log_match();
             }
-            KeyCode::Esc => {
+            Some(Action::NextUrl) => {
+                self.next_url();
+            }
+            Some(Action::OpenUrl) => {
+                self.open_selected_url();
+            }
+            Some(Action::Back) => {
                 self.tab = Tab::Actions;
                 self.log_search = None;
                 self.log_matches.clear();
@@ -953,6 +2613,34 @@ impl App {
         }
     }
 
+    fn handle_notification_keys(&mut self, key: KeyEvent) {
+        match self.keymap.action(&key, Scope::Notifications) {
+            Some(Action::NextItem) => {
+                self.next_notification();
+            }
+            Some(Action::PrevItem) => {
+                self.previous_notification();
+            }
+            Some(Action::Select) => {
+                self.open_notification();
+            }
+            Some(Action::OpenInBrowser) => {
+                self.open_notification_in_browser();
+            }
+            Some(Action::MarkRead) => {
+                self.mark_notification_read();
+            }
+            Some(Action::ToggleAllRepos) => {
+                self.notifications_all_repos = !self.notifications_all_repos;
+                self.notification_list_state.select(None);
+                self.loading = true;
+                self.loading_what = Some("Loading notifications...".to_string());
+                self.spawn_fetch_notifications();
+            }
+            _ => {}
+        }
+    }
+
     // Navigation helpers
     fn next_pr(&mut self) {
         let len = self.prs.len();
@@ -962,6 +2650,7 @@ impl App {
             None => 0,
         };
         self.pr_list_state.select(Some(i));
+        self.mark_pr_seen();
     }
 
     fn previous_pr(&mut self) {
@@ -972,10 +2661,18 @@ impl App {
             None => 0,
         };
         self.pr_list_state.select(Some(i));
+        self.mark_pr_seen();
+    }
+
+    /// Drop the updated marker of the PR under the cursor
+    fn mark_pr_seen(&mut self) {
+        if let Some(pr) = self.pr_list_state.selected().and_then(|i| self.prs.get(i)) {
+            self.updated_prs.remove(&pr.number);
+        }
     }
 
     fn next_pr_check(&mut self) {
-        let len = self.pr_checks.len();
+        let len = self.pr_check_rows().len();
         if len == 0 { return; }
         let i = match self.pr_checks_state.selected() {
             Some(i) => (i + 1) % len,
@@ -985,7 +2682,7 @@ impl App {
     }
 
     fn previous_pr_check(&mut self) {
-        let len = self.pr_checks.len();
+        let len = self.pr_check_rows().len();
         if len == 0 { return; }
         let i = match self.pr_checks_state.selected() {
             Some(i) => (i + len - 1) % len,
@@ -1002,6 +2699,11 @@ impl App {
             None => 0,
         };
         self.run_list_state.select(Some(i));
+
+        // Reaching the bottom of the list lazily loads the next page
+        if i == len - 1 {
+            self.spawn_fetch_more_runs();
+        }
     }
 
     fn previous_run(&mut self) {
@@ -1014,6 +2716,25 @@ impl App {
         self.run_list_state.select(Some(i));
     }
 
+    /// Whether the Actions tab shows a run or job that is still running
+    fn shows_running_durations(&self) -> bool {
+        self.tab == Tab::Actions
+            && (self.runs.iter().any(|run| !run.is_finished())
+                || (self.view == View::Jobs && self.jobs.iter().any(|job| job.completed_at.is_none())))
+    }
+
+    /// Job drawn at `row` of the jobs list, counting the step lines of expanded jobs
+    fn job_at_row(&self, row: usize) -> Option<usize> {
+        let mut top = 0;
+        for (i, job) in self.jobs.iter().enumerate().skip(self.job_list_state.offset()) {
+            top += 1 + if self.expanded_jobs.contains(&job.id) { job.steps.len() } else { 0 };
+            if row < top {
+                return Some(i);
+            }
+        }
+        None
+    }
+
     fn next_job(&mut self) {
         let len = self.jobs.len();
         if len == 0 { return; }
@@ -1034,6 +2755,26 @@ impl App {
         self.job_list_state.select(Some(i));
     }
 
+    fn next_artifact(&mut self) {
+        let len = self.artifacts.len();
+        if len == 0 { return; }
+        let i = match self.artifact_list_state.selected() {
+            Some(i) => (i + 1) % len,
+            None => 0,
+        };
+        self.artifact_list_state.select(Some(i));
+    }
+
+    fn previous_artifact(&mut self) {
+        let len = self.artifacts.len();
+        if len == 0 { return; }
+        let i = match self.artifact_list_state.selected() {
+            Some(i) => (i + len - 1) % len,
+            None => 0,
+        };
+        self.artifact_list_state.select(Some(i));
+    }
+
     fn next_commit(&mut self) {
         let len = self.pr_commits.len();
         if len == 0 { return; }
@@ -1057,8 +2798,16 @@ impl App {
     fn toggle_diff_mode(&mut self) {
         self.diff_mode = match self.diff_mode {
             DiffMode::Full => {
-                // Switch to commit mode
-                if !self.pr_commits.is_empty() {
+                // Switch to commit mode, fetching the commits on first use
+                if !self.commits_loaded {
+                    if let Some(pr_number) = self.selected_pr.as_ref().map(|pr| pr.number) {
+                        self.commits_loaded = true;
+                        self.loading = true;
+                        self.loading_what = Some("Loading commits...".to_string());
+                        self.spawn_fetch_commits(pr_number);
+                    }
+                    DiffMode::ByCommit
+                } else if !self.pr_commits.is_empty() {
                     if self.pr_commits_state.selected().is_none() {
                         self.pr_commits_state.select(Some(0));
                     }
@@ -1078,10 +2827,10 @@ impl App {
 
     fn load_selected_commit_diff(&mut self) {
         if let Some(i) = self.pr_commits_state.selected() {
-            if let Some(commit) = self.pr_commits.get(i) {
+            if let Some(commit) = self.pr_commits.get(i).cloned() {
                 self.loading = true;
                 self.loading_what = Some(format!("Loading commit {}...", commit.short_sha()));
-                self.spawn_fetch_commit_diff(&commit.sha);
+                self.spawn_fetch_commit_diff(&commit.sha, false);
             }
         }
     }
@@ -1090,12 +2839,26 @@ impl App {
         self.pr_filter = match self.pr_filter {
             PrFilter::All => PrFilter::Mine,
             PrFilter::Mine => PrFilter::ReviewRequested,
-            PrFilter::ReviewRequested => PrFilter::All,
+            PrFilter::ReviewRequested => PrFilter::Closed,
+            PrFilter::Closed => PrFilter::All,
         };
+
+        // Closed PRs are only fetched when the filter is first selected
+        if self.pr_filter == PrFilter::Closed && self.closed_prs.is_empty() {
+            self.loading = true;
+            self.loading_what = Some("Loading closed PRs...".to_string());
+            self.spawn_fetch_closed_prs();
+        }
         self.apply_pr_filter();
     }
 
     fn apply_pr_filter(&mut self) {
+        // Follow the selected PR by number, not index, as the list changes under it
+        let selected_number = self
+            .pr_list_state
+            .selected()
+            .and_then(|i| self.prs.get(i))
+            .map(|pr| pr.number);
         let current_user = self.current_user.as_deref();
 
         self.prs = match self.pr_filter {
@@ -1113,19 +2876,46 @@ impl App {
             }
             PrFilter::ReviewRequested => {
                 if let Some(user) = current_user {
+                    // Requested directly, or through one of the user's teams
+                    let owner = self.owner.to_lowercase();
                     self.all_prs
                         .iter()
-                        .filter(|pr| pr.requested_reviewers.iter().any(|r| r.login == user))
+                        .filter(|pr| {
+                            pr.requested_reviewers.iter().any(|r| r.login == user)
+                                || pr.requested_teams.iter().any(|team| {
+                                    self.user_teams.contains(&format!("{}/{}", owner, team.to_lowercase()))
+                                })
+                        })
                         .cloned()
                         .collect()
                 } else {
                     self.all_prs.clone()
                 }
             }
+            PrFilter::Closed => self.closed_prs.clone(),
         };
 
+        if !self.pr_query.is_empty() {
+            let query = self.pr_query.to_lowercase();
+            self.prs.retain(|pr| {
+                [&pr.title, &pr.user.login, &pr.head.ref_name]
+                    .iter()
+                    .any(|field| field.to_lowercase().contains(&query))
+            });
+        }
+
+        // Timestamps are ISO 8601, so they sort as strings
+        match self.pr_sort {
+            PrSort::Created => self.prs.sort_by(|a, b| b.created_at.cmp(&a.created_at)),
+            PrSort::Updated => self.prs.sort_by(|a, b| b.updated_at.cmp(&a.updated_at)),
+            PrSort::Number => self.prs.sort_by_key(|pr| std::cmp::Reverse(pr.number)),
+            PrSort::Title => self.prs.sort_by_cached_key(|pr| pr.title.to_lowercase()),
+        }
+
         // Reset selection if needed
-        if self.prs.is_empty() {
+        if let Some(idx) = selected_number.and_then(|n| self.prs.iter().position(|pr| pr.number == n)) {
+            self.pr_list_state.select(Some(idx));
+        } else if self.prs.is_empty() {
             self.pr_list_state.select(None);
         } else if self
             .pr_list_state
@@ -1136,6 +2926,43 @@ impl App {
         }
     }
 
+    /// Store an aggregate CI status on every PR whose head is at `sha`
+    fn set_ci_status(&mut self, sha: &str, status: Option<String>) {
+        let prs = self.all_prs.iter_mut()
+            .chain(self.closed_prs.iter_mut())
+            .chain(self.prs.iter_mut())
+            .chain(self.selected_pr.iter_mut());
+        for pr in prs.filter(|pr| pr.head.sha == sha) {
+            pr.ci_status = status.clone();
+        }
+    }
+
+    /// Point the detail panel at refreshed data for its PR. The diff and scroll
+    /// positions are kept unless the PR got new commits.
+    fn sync_selected_pr(&mut self) {
+        let Some(selected) = &self.selected_pr else {
+            return;
+        };
+        let Some(updated) = self.all_prs.iter()
+            .chain(self.closed_prs.iter())
+            .find(|pr| pr.number == selected.number)
+        else {
+            return;
+        };
+
+        if updated.head.sha == selected.head.sha {
+            let mut updated = updated.clone();
+            updated.mergeable = updated.mergeable.or(selected.mergeable);
+            updated.ci_status = updated.ci_status.clone().or(selected.ci_status.clone());
+            self.selected_pr = Some(updated);
+        } else if self.pr_list_state.selected()
+            .and_then(|i| self.prs.get(i))
+            .is_some_and(|pr| pr.number == updated.number)
+        {
+            self.select_pr();
+        }
+    }
+
     fn select_pr_by_number(&mut self, pr_number: u64) {
         // Find the PR in the filtered list
         if let Some(idx) = self.prs.iter().position(|pr| pr.number == pr_number) {
@@ -1151,43 +2978,86 @@ impl App {
     // Data fetching (now async)
     fn select_pr(&mut self) {
         if let Some(i) = self.pr_list_state.selected() {
-            if let Some(pr) = self.prs.get(i) {
+            if let Some(pr) = self.prs.get(i).cloned() {
+                // Reviews and commits of the same PR are kept when it is re-selected
+                // without new commits
+                let same_pr = self.selected_pr.as_ref()
+                    .is_some_and(|p| p.number == pr.number && p.head.sha == pr.head.sha);
                 self.selected_pr = Some(pr.clone());
+                if !same_pr {
+                    self.pr_generation += 1;
+                    self.pr_reviews.clear();
+                    self.reviews_loaded = false;
+        self.reviews_known = false;
+                    self.reviews_known = false;
+                    self.pr_commits.clear();
+                    self.commits_loaded = false;
+                    self.pr_commits_state.select(None);
+                    self.commit_diff = None;
+                    self.commit_diff_view = ui::RenderedDiff::default();
+                    self.diff_full = false;
+                    self.diff_search = None;
+                }
+                self.pr_diff = None;
+                self.pr_diff_view = ui::RenderedDiff::default();
+                self.pr_diff_stat = None;
                 self.diff_scroll = 0;
+                self.body_scroll = 0;
                 self.pr_checks.clear();
                 self.pr_checks_state.select(None);
-                self.pr_reviews.clear();
-                self.pr_commits.clear();
-                self.pr_commits_state.select(None);
-                self.commit_diff = None;
                 self.diff_mode = DiffMode::Full;
 
-                // Spawn async fetch for diff, checks, reviews, and commits
+                // Spawn async fetch for diff and checks. Commits wait until the
+                // commit view is opened.
                 self.loading = true;
                 self.loading_what = Some("Loading diff...".to_string());
-                self.spawn_fetch_diff(pr.number);
+                self.spawn_fetch_diff(&pr, false);
+                self.pr_auto_merge = None;
+                if pr.state == "open" {
+                    self.spawn_fetch_pr(pr.number);
+                    self.spawn_fetch_auto_merge(pr.number);
+                }
                 self.spawn_fetch_pr_checks(&pr.head.sha);
-                self.spawn_fetch_reviews(pr.number);
-                self.spawn_fetch_commits(pr.number);
+                if pr.state == "open" {
+                    self.spawn_fetch_required_approvals(&pr.base.ref_name);
+                }
+                // Reviews show in the detail panel, which opens with the selection
+                if !self.reviews_loaded {
+                    self.reviews_loaded = true;
+                    self.spawn_fetch_reviews(pr.number);
+                }
             }
         }
     }
 
     fn select_run(&mut self) {
         if let Some(i) = self.run_list_state.selected() {
-            if let Some(run) = self.runs.get(i) {
-                self.selected_run = Some(run.clone());
+            if let Some(run) = self.runs.get(i).cloned() {
                 self.job_list_state.select(Some(0));
+                self.artifacts.clear();
+                self.artifact_list_state.select(None);
+                self.artifacts_focused = false;
+                self.pending_deployments.clear();
 
                 // Spawn async fetch for jobs
                 self.loading = true;
                 self.loading_what = Some("Loading jobs...".to_string());
                 self.spawn_fetch_jobs(run.id);
+                self.spawn_fetch_artifacts(run.id);
+                if run.status == "waiting" {
+                    self.spawn_fetch_pending_deployments(run.id);
+                }
+                self.selected_run = Some(run);
             }
         }
     }
 
+    fn selected_job(&self) -> Option<&Job> {
+        self.job_list_state.selected().and_then(|i| self.jobs.get(i))
+    }
+
     fn fetch_logs(&mut self) {
+        self.log_follow = false;
         if let Some(run) = &self.selected_run {
             let job_id = self.job_list_state.selected()
                 .and_then(|i| self.jobs.get(i))
@@ -1195,42 +3065,126 @@ impl App {
 
             self.loading = true;
             self.loading_what = Some("Loading logs...".to_string());
-            self.spawn_fetch_logs(run.id, job_id);
+            self.spawn_fetch_logs(run.id, job_id, false);
         }
     }
 
-    fn view_pr_check_jobs(&mut self) {
-        if let Some(i) = self.pr_checks_state.selected() {
-            if let Some(check) = self.pr_checks.get(i) {
-                self.selected_run = Some(check.clone());
-                self.job_list_state.select(Some(0));
-
-                // Find and select this run in the runs list
-                if let Some(run_idx) = self.runs.iter().position(|r| r.id == check.id) {
-                    self.run_list_state.select(Some(run_idx));
-                } else {
-                    // Run not in list - add it at the top and select it
-                    self.runs.insert(0, check.clone());
-                    self.run_list_state.select(Some(0));
-                }
+    /// Checks grouped by workflow, each group under a header when it has several runs
+    pub fn pr_check_rows(&self) -> Vec<CheckRow> {
+        let mut rows = Vec::new();
+        let mut start = 0;
+        for group in self.pr_checks.chunk_by(|a, b| a.name == b.name) {
+            let end = start + group.len();
+            let grouped = group.len() > 1;
+            if grouped {
+                rows.push(CheckRow::Group(start..end));
+            }
+            if !grouped || !self.collapsed_workflows.contains(&group[0].name) {
+                rows.extend((start..end).map(|index| CheckRow::Check { index, grouped }));
+            }
+            start = end;
+        }
+        rows
+    }
 
-                self.loading = true;
-                self.loading_what = Some("Loading jobs...".to_string());
-                self.spawn_fetch_jobs(check.id);
-                self.tab = Tab::Actions;
-                self.view = View::Jobs;
+    /// The check under the cursor; on a workflow header, its latest run
+    fn selected_pr_check(&self) -> Option<&Check> {
+        let index = match self.pr_check_rows().into_iter().nth(self.pr_checks_state.selected()?)? {
+            CheckRow::Group(range) => range.start,
+            CheckRow::Check { index, .. } => index,
+        };
+        self.pr_checks.get(index)
+    }
+
+    /// Collapse or expand the workflow header under the cursor; false on a check
+    fn toggle_check_group(&mut self) -> bool {
+        let rows = self.pr_check_rows();
+        let Some(CheckRow::Group(range)) = self.pr_checks_state.selected().and_then(|i| rows.get(i)) else {
+            return false;
+        };
+        let name = self.pr_checks[range.start].name.clone();
+        if !self.collapsed_workflows.remove(&name) {
+            self.collapsed_workflows.insert(name);
+        }
+        true
+    }
+
+    fn view_pr_check_jobs(&mut self) {
+        let Some(check) = self.selected_pr_check().cloned() else {
+            return;
+        };
+        // Checks from outside Actions have no jobs here, only their own page
+        let Some(run) = check.run else {
+            let url = check.html_url;
+            if url.is_empty() {
+                self.set_message(format!("{} has no details page", check.name));
+            } else if Self::open_url(&url) {
+                self.set_message(format!("Opened {}", url));
+            } else {
+                self.error = Some("Failed to open browser".to_string());
             }
+            return;
+        };
+
+        self.open_run_jobs(run);
+    }
+
+    /// Show the jobs of a run in the Actions tab
+    fn open_run_jobs(&mut self, run: WorkflowRun) {
+        self.job_list_state.select(Some(0));
+
+        // Find and select this run in the runs list
+        if let Some(run_idx) = self.runs.iter().position(|r| r.id == run.id) {
+            self.run_list_state.select(Some(run_idx));
+        } else {
+            // Run not in list - add it at the top and select it
+            self.runs.insert(0, run.clone());
+            self.run_list_state.select(Some(0));
         }
+
+        self.loading = true;
+        self.loading_what = Some("Loading jobs...".to_string());
+        self.spawn_fetch_jobs(run.id);
+        self.pending_deployments.clear();
+        if run.status == "waiting" {
+            self.spawn_fetch_pending_deployments(run.id);
+        }
+        self.selected_run = Some(run);
+        self.tab = Tab::Actions;
+        self.view = View::Jobs;
+    }
+
+    /// Open the logs of the first failed job at its first error: for the selected
+    /// check if it failed, otherwise for the PR's first failed Actions run
+    fn jump_to_failure(&mut self) {
+        let failed = |c: &&Check| {
+            c.run.is_some() && matches!(c.conclusion.as_deref(), Some("failure" | "timed_out" | "startup_failure"))
+        };
+        let run = self.selected_pr_check()
+            .filter(failed)
+            .or_else(|| self.pr_checks.iter().find(failed))
+            .and_then(|c| c.run.clone());
+        let Some(run) = run else {
+            self.set_message("No failed Actions checks");
+            return;
+        };
+        self.jobs.clear();
+        self.failure_jump = true;
+        self.open_run_jobs(run);
+        self.tab = Tab::Logs;
     }
 
     // Actions
-    async fn approve_pr(&mut self) {
-        if let Some(pr) = &self.selected_pr {
+    /// Approve the selected PR, with `body` as the review comment if given
+    async fn approve_pr(&mut self, body: Option<String>) {
+        if let Some(pr) = self.selected_pr.clone() {
             if let Some(client) = &self.client {
                 self.loading = true;
                 self.loading_what = Some("Approving PR...".to_string());
-                match client.approve_pr(&self.owner, &self.repo_name, pr.number).await {
+                match client.approve_pr(&self.owner, &self.repo_name, pr.number, body.as_deref()).await {
                     Ok(_) => {
+                        // Update the Reviews line with the new approval
+                        self.spawn_fetch_reviews(pr.number);
                         self.set_message(format!("Approved PR #{}", pr.number));
                     }
                     Err(e) => {
@@ -1243,70 +3197,278 @@ impl App {
         }
     }
 
-    async fn merge_pr(&mut self) {
-        if let Some(pr) = &self.selected_pr {
-            if let Some(client) = &self.client {
-                self.loading = true;
-                self.loading_what = Some("Merging PR...".to_string());
-                match client.merge_pr(&self.owner, &self.repo_name, pr.number).await {
-                    Ok(_) => {
-                        self.set_message(format!("Merged PR #{}", pr.number));
-                        self.spawn_fetch_prs();
+    async fn run_pending_action(&mut self, action: PendingAction) {
+        match action {
+            PendingAction::MergePr(pr_number, method, _) => self.merge_pr(pr_number, method).await,
+            PendingAction::ClosePr(pr_number) => self.set_pr_open(pr_number, false).await,
+            PendingAction::UpdateBranch(pr_number) => self.update_pr_branch(pr_number).await,
+            PendingAction::EnableAutoMerge(pr_number, method) => self.set_auto_merge(pr_number, Some(method)).await,
+            PendingAction::DeleteBranch(_) => self.delete_merged_branch().await,
+            PendingAction::SearchClosed(pr_number) => self.find_closed_pr(pr_number),
+            PendingAction::ApproveSelected(_) => self.run_batch(None),
+            PendingAction::MergeSelected(_, method) => self.run_batch(Some(method)),
+        }
+    }
+
+    /// Mark or unmark the PR under the cursor, then move to the next one
+    fn toggle_pr_selection(&mut self) {
+        let Some(pr) = self.pr_list_state.selected().and_then(|i| self.prs.get(i)) else {
+            return;
+        };
+        if !self.selected_prs.remove(&pr.number) {
+            self.selected_prs.insert(pr.number);
+        }
+        self.next_pr();
+    }
+
+    /// Approve, or merge with `method`, every PR in `selected_prs` one after another
+    fn run_batch(&mut self, merge: Option<MergeMethod>) {
+        let mut numbers: Vec<u64> = self.selected_prs.iter().copied().collect();
+        numbers.sort_unstable();
+
+        if let (Some(client), Some(tx)) = (self.client.clone(), self.async_tx.clone()) {
+            let owner = self.owner.clone();
+            let repo = self.repo_name.clone();
+            let verb = if merge.is_some() { "Merged" } else { "Approved" };
+            self.loading = true;
+            self.loading_what = Some(format!(
+                "{} {} PRs...",
+                if merge.is_some() { "Merging" } else { "Approving" },
+                numbers.len()
+            ));
+            tokio::spawn(async move {
+                let mut failed = Vec::new();
+                let mut errors = Vec::new();
+                for &number in &numbers {
+                    let result = match merge {
+                        Some(method) => client.merge_pr(&owner, &repo, number, method).await,
+                        None => client.approve_pr(&owner, &repo, number, None).await,
+                    };
+                    if let Err(e) = result {
+                        failed.push(number);
+                        errors.push(format!("#{}: {}", number, e));
                     }
-                    Err(e) => {
-                        self.error = Some(format!("Failed to merge: {}", e));
-                        self.loading = false;
-                        self.loading_what = None;
+                }
+
+                let done = numbers.len() - failed.len();
+                let mut summary = format!("{} {} of {} PRs", verb, done, numbers.len());
+                if !errors.is_empty() {
+                    summary.push_str(&format!(" - failed {}", errors.join("; ")));
+                }
+                let _ = tx.send(AsyncMsg::BatchDone(summary, failed));
+            });
+        }
+    }
+
+    async fn merge_pr(&mut self, pr_number: u64, method: MergeMethod) {
+        if let Some(client) = &self.client {
+            self.loading = true;
+            self.loading_what = Some("Merging PR...".to_string());
+            // Branches of forks live in another repo, out of reach for deleting
+            let branch = self.all_prs.iter()
+                .chain(self.selected_pr.iter())
+                .find(|pr| pr.number == pr_number)
+                .filter(|pr| pr.head.owner.as_deref() == Some(self.owner.as_str()))
+                .map(|pr| pr.head.ref_name.clone());
+            match client.merge_pr(&self.owner, &self.repo_name, pr_number, method).await {
+                Ok(_) => {
+                    self.set_message(format!("Merged PR #{}", pr_number));
+                    self.spawn_fetch_prs();
+                    if let Some(branch) = branch {
+                        self.merged_branch = Some(branch);
+                        match self.config.delete_branch_on_merge {
+                            Some(true) => self.delete_merged_branch().await,
+                            Some(false) => self.merged_branch = None,
+                            None => self.pending_action = Some(PendingAction::DeleteBranch(pr_number)),
+                        }
                     }
                 }
+                Err(e) => {
+                    self.error = Some(format!("Failed to merge: {}", e));
+                    self.loading = false;
+                    self.loading_what = None;
+                }
             }
         }
     }
 
-    fn checkout_pr(&mut self) {
-        if let Some(pr) = &self.selected_pr {
-            let branch = pr.head.ref_name.clone();
-            let pr_number = pr.number;
-            let tx = self.async_tx.clone();
+    async fn update_pr_branch(&mut self, pr_number: u64) {
+        if let Some(client) = &self.client {
+            self.loading = true;
+            self.loading_what = Some("Updating branch...".to_string());
+            match client.update_pr_branch(&self.owner, &self.repo_name, pr_number).await {
+                Ok(true) => {
+                    // GitHub merges in the background; the new head shows up on refresh
+                    self.set_message(format!("Updating PR #{} from its base branch", pr_number));
+                }
+                Ok(false) => {
+                    self.set_message(format!("PR #{} is already up to date with its base", pr_number));
+                }
+                Err(e) => {
+                    self.error = Some(e.to_string());
+                }
+            }
+            self.loading = false;
+            self.loading_what = None;
+        }
+    }
 
-            tokio::spawn(async move {
-                // First fetch the branch from origin
-                let fetch = std::process::Command::new("git")
-                    .args(["fetch", "origin", &branch])
-                    .output();
+    async fn delete_merged_branch(&mut self) {
+        let Some(branch) = self.merged_branch.take() else {
+            return;
+        };
+        if let Some(client) = &self.client {
+            match client.delete_branch(&self.owner, &self.repo_name, &branch).await {
+                Ok(true) => self.set_message(format!("Deleted branch {}", branch)),
+                Ok(false) => self.set_message(format!("Branch {} was already deleted", branch)),
+                Err(e) => self.error = Some(e.to_string()),
+            }
+        }
+    }
 
-                if let Err(e) = fetch {
-                    if let Some(tx) = &tx {
-                        let _ = tx.send(AsyncMsg::Error(format!("Fetch failed: {}", e)));
+    /// Enable auto-merge with `method`, or disable it with None
+    async fn set_auto_merge(&mut self, pr_number: u64, method: Option<MergeMethod>) {
+        if let Some(client) = &self.client {
+            self.loading = true;
+            self.loading_what = Some("Updating auto-merge...".to_string());
+            let result = match method {
+                Some(method) => client.enable_auto_merge(&self.owner, &self.repo_name, pr_number, method).await,
+                None => client.disable_auto_merge(&self.owner, &self.repo_name, pr_number).await,
+            };
+            match result {
+                Ok(()) => {
+                    if self.selected_pr.as_ref().is_some_and(|pr| pr.number == pr_number) {
+                        self.pr_auto_merge = method.map(|m| m.as_str().to_string());
                     }
-                    return;
+                    self.set_message(match method {
+                        Some(method) => format!("PR #{} will be merged ({}) once it can be", pr_number, method.as_str()),
+                        None => format!("Disabled auto-merge for PR #{}", pr_number),
+                    });
                 }
+                Err(e) => {
+                    self.error = Some(e.to_string());
+                }
+            }
+            self.loading = false;
+            self.loading_what = None;
+        }
+    }
 
-                // Then checkout the branch (create tracking branch if needed)
-                let checkout = std::process::Command::new("git")
-                    .args(["checkout", "-B", &branch, &format!("origin/{}", branch)])
-                    .output();
-
-                if let Some(tx) = tx {
-                    match checkout {
-                        Ok(o) if o.status.success() => {
-                            let _ = tx.send(AsyncMsg::Message(format!("Checked out PR #{} ({})", pr_number, branch)));
-                        }
-                        Ok(o) => {
-                            let _ = tx.send(AsyncMsg::Error(format!(
-                                "Checkout failed: {}",
-                                String::from_utf8_lossy(&o.stderr)
-                            )));
-                        }
-                        Err(e) => {
-                            let _ = tx.send(AsyncMsg::Error(format!("Checkout failed: {}", e)));
+    async fn set_pr_open(&mut self, pr_number: u64, open: bool) {
+        if let Some(client) = &self.client {
+            self.loading = true;
+            self.loading_what = Some(if open { "Reopening PR..." } else { "Closing PR..." }.to_string());
+            let result = if open {
+                client.reopen_pr(&self.owner, &self.repo_name, pr_number).await
+            } else {
+                client.close_pr(&self.owner, &self.repo_name, pr_number).await
+            };
+            match result {
+                Ok(_) => {
+                    let state = if open { "open" } else { "closed" };
+                    let prs = self.all_prs.iter_mut()
+                        .chain(self.closed_prs.iter_mut())
+                        .chain(self.selected_pr.iter_mut());
+                    for pr in prs.filter(|pr| pr.number == pr_number) {
+                        pr.state = state.to_string();
+                    }
+                    // Move it between the open and closed lists. Closed PRs not
+                    // fetched yet stay empty, so the Closed filter still loads them.
+                    let (from, to) = if open {
+                        (&mut self.closed_prs, &mut self.all_prs)
+                    } else {
+                        (&mut self.all_prs, &mut self.closed_prs)
+                    };
+                    if let Some(index) = from.iter().position(|pr| pr.number == pr_number) {
+                        let pr = from.remove(index);
+                        if open || !to.is_empty() {
+                            to.insert(0, pr);
                         }
                     }
+                    self.apply_pr_filter();
+                    self.set_message(format!("{} PR #{}", if open { "Reopened" } else { "Closed" }, pr_number));
                 }
-            });
+                Err(e) => {
+                    self.error = Some(format!("Failed to {} PR: {}", if open { "reopen" } else { "close" }, e));
+                }
+            }
+            self.loading = false;
+            self.loading_what = None;
         }
     }
 
+    /// Approvals and change requests among each reviewer's standing review of the selected PR
+    pub fn review_tally(&self) -> (usize, usize) {
+        let latest = latest_reviews(&self.pr_reviews);
+        let count = |state| latest.values().filter(|r| r.state == state).count();
+        (count("APPROVED"), count("CHANGES_REQUESTED"))
+    }
+
+    /// What may keep the selected PR from merging, shown in the confirmation.
+    /// GitHub has the final say: failing checks may be optional, and reviews may
+    /// not be required. Review state counts only once the reviews have arrived.
+    fn merge_warnings(&self, pr: &PullRequest) -> Vec<&'static str> {
+        let mut warnings = Vec::new();
+        if pr.ci_status.as_deref() == Some("failure") {
+            warnings.push("failing checks");
+        }
+        if self.reviews_known {
+            let (approvals, changes) = self.review_tally();
+            let required = self.required_approvals.get(&pr.base.ref_name).copied().flatten();
+            if changes > 0 {
+                warnings.push("changes requested");
+            } else if required.is_some_and(|required| approvals < required as usize) {
+                warnings.push("review required");
+            }
+        }
+        warnings
+    }
+
+    /// Whether the PR's branch is the one checked out locally, under its own
+    /// name or the `pr-<number>` that `checkout_pr` uses for forks
+    pub fn is_checked_out(&self, pr: &PullRequest) -> bool {
+        self.local_head.as_ref().is_some_and(|head| {
+            head.branch == format!("pr-{}", pr.number)
+                || (head.branch == pr.head.ref_name && pr.head.owner.as_deref() == Some(self.owner.as_str()))
+        })
+    }
+
+    /// Check out the PR's head with plain git. Branches of this repo track their
+    /// remote branch; a fork's head is fetched from `pull/<number>/head` into `pr-<number>`.
+    fn checkout_pr(&mut self) {
+        let Some(pr) = &self.selected_pr else {
+            return;
+        };
+        let Some(remote) = git::remote_for(&self.repo) else {
+            self.error = Some(format!("No git remote for {} in the current directory", self.repo));
+            return;
+        };
+        let pr_number = pr.number;
+        let (refspec, branch, start) = if pr.head.owner.as_deref() == Some(self.owner.as_str()) {
+            let branch = pr.head.ref_name.clone();
+            (branch.clone(), branch.clone(), format!("{}/{}", remote, branch))
+        } else {
+            (format!("pull/{}/head", pr_number), format!("pr-{}", pr_number), "FETCH_HEAD".to_string())
+        };
+        let tx = self.async_tx.clone();
+
+        tokio::spawn(async move {
+            let result = checkout_branch(&remote, &refspec, &branch, &start).await;
+
+            if let Some(tx) = tx {
+                let _ = tx.send(match result {
+                    Ok(()) => AsyncMsg::CheckedOut(pr_number, branch),
+                    Err(e) => AsyncMsg::Error(e),
+                });
+            }
+        });
+    }
+
+    /// Web root for links: github.com, or the Enterprise host of `--github-host`
+    fn web_base(&self) -> String {
+        self.client.as_ref().map(|client| client.web_base()).unwrap_or_default()
+    }
+
     fn create_pr(&mut self) {
         // Get current branch name to pre-fill the PR creation URL
         let branch = std::process::Command::new("git")
@@ -1318,9 +3480,9 @@ impl App {
             .unwrap_or_default();
 
         let url = if branch.is_empty() {
-            format!("https://github.com/{}/{}/compare", self.owner, self.repo_name)
+            format!("{}/{}/compare", self.web_base(), self.repo)
         } else {
-            format!("https://github.com/{}/{}/compare/{}?expand=1", self.owner, self.repo_name, branch)
+            format!("{}/{}/compare/{}?expand=1", self.web_base(), self.repo, branch)
         };
 
         if Self::open_url(&url) {
@@ -1336,10 +3498,7 @@ impl App {
             return;
         };
 
-        let url = format!(
-            "https://github.com/{}/{}/compare/{}?expand=1",
-            self.owner, self.repo_name, branch.name
-        );
+        let url = format!("{}/{}/compare/{}?expand=1", self.web_base(), self.repo, branch.name);
 
         if Self::open_url(&url) {
             self.set_message(format!("Opened PR creation for branch '{}'", branch.name));
@@ -1466,6 +3625,181 @@ impl App {
         }
     }
 
+    /// Reviews popup: `d` dismisses the highlighted review (asking why),
+    /// `r` re-requests a review from its author
+    async fn handle_review_picker_keys(&mut self, key: KeyEvent) {
+        let len = self.pr_reviews.len();
+        let Some(state) = self.review_picker.as_mut() else {
+            return;
+        };
+        let review = state.selected().and_then(|i| self.pr_reviews.get(i));
+        let (review_id, reviewer, state_name) = match review {
+            Some(r) => (r.id, r.user.login.clone(), r.state.clone()),
+            None => (0, String::new(), String::new()),
+        };
+
+        match self.keymap.action(&key, Scope::Common) {
+            Some(Action::NextItem) if len > 0 => {
+                let i = state.selected().map(|i| (i + 1) % len).unwrap_or(0);
+                state.select(Some(i));
+            }
+            Some(Action::PrevItem) if len > 0 => {
+                let i = state.selected().map(|i| (i + len - 1) % len).unwrap_or(0);
+                state.select(Some(i));
+            }
+            Some(Action::Back) => {
+                self.review_picker = None;
+            }
+            _ => match key.code {
+                KeyCode::Char('q') => {
+                    self.review_picker = None;
+                }
+                KeyCode::Char('d') if !reviewer.is_empty() => {
+                    if state_name == "APPROVED" || state_name == "CHANGES_REQUESTED" {
+                        self.review_picker = None;
+                        self.dismissing_review = Some(review_id);
+                        self.input_mode = Some(InputMode::DismissReview);
+                        self.show_prompt(format!("Dismiss {}'s review, because:", reviewer));
+                    } else {
+                        self.set_message("Only approvals and change requests can be dismissed");
+                    }
+                }
+                KeyCode::Char('r') if !reviewer.is_empty() => {
+                    self.review_picker = None;
+                    self.request_review_again(&reviewer).await;
+                }
+                _ => {}
+            },
+        }
+    }
+
+    /// Ask for the optional comment of a deployment approval or rejection
+    fn start_deployment_review(&mut self, mode: InputMode, verb: &str) {
+        let environments: Vec<&str> = self.pending_deployments.iter()
+            .filter(|d| d.can_approve)
+            .map(|d| d.environment.as_str())
+            .collect();
+        if environments.is_empty() {
+            let message = if self.pending_deployments.is_empty() {
+                "No deployments waiting for approval"
+            } else {
+                "You are not a required reviewer for these environments"
+            };
+            self.set_message(message);
+            return;
+        }
+        let prompt = format!("{} deployment to {} (optional comment):", verb, environments.join(", "));
+        self.input_mode = Some(mode);
+        self.show_prompt(prompt);
+    }
+
+    /// Approve or reject every waiting deployment the user may review
+    async fn submit_deployment_review(&mut self, approve: bool) {
+        let Some(run_id) = self.selected_run.as_ref().map(|r| r.id) else {
+            return;
+        };
+        let (ids, names): (Vec<u64>, Vec<&str>) = self.pending_deployments.iter()
+            .filter(|d| d.can_approve)
+            .map(|d| (d.environment_id, d.environment.as_str()))
+            .unzip();
+        let names = names.join(", ");
+        let comment = self.input_buffer.trim().to_string();
+
+        if let Some(client) = &self.client {
+            self.loading = true;
+            self.loading_what = Some("Reviewing deployment...".to_string());
+            match client.review_pending_deployment(&self.owner, &self.repo_name, run_id, &ids, approve, &comment).await {
+                Ok(()) => {
+                    let verb = if approve { "Approved" } else { "Rejected" };
+                    self.set_message(format!("{} deployment to {}", verb, names));
+                    self.spawn_fetch_pending_deployments(run_id);
+                    self.spawn_fetch_jobs(run_id);
+                    self.spawn_fetch_runs();
+                }
+                Err(e) => {
+                    self.error = Some(e.to_string());
+                }
+            }
+            self.loading = false;
+            self.loading_what = None;
+        }
+    }
+
+    async fn submit_dismiss_review(&mut self) {
+        let (Some(pr_number), Some(review_id)) = (self.selected_pr.as_ref().map(|pr| pr.number), self.dismissing_review.take()) else {
+            return;
+        };
+        let message = self.input_buffer.trim().to_string();
+        if message.is_empty() {
+            self.error = Some("A dismissal needs a message".to_string());
+            return;
+        }
+
+        if let Some(client) = &self.client {
+            self.loading = true;
+            self.loading_what = Some("Dismissing review...".to_string());
+            match client.dismiss_review(&self.owner, &self.repo_name, pr_number, review_id, &message).await {
+                Ok(()) => {
+                    self.set_message(format!("Dismissed review on PR #{}", pr_number));
+                    self.spawn_fetch_reviews(pr_number);
+                }
+                Err(e) => {
+                    self.error = Some(e.to_string());
+                }
+            }
+            self.loading = false;
+            self.loading_what = None;
+        }
+    }
+
+    async fn request_review_again(&mut self, reviewer: &str) {
+        let Some(pr_number) = self.selected_pr.as_ref().map(|pr| pr.number) else {
+            return;
+        };
+        if let Some(client) = &self.client {
+            self.loading = true;
+            self.loading_what = Some("Requesting review...".to_string());
+            match client.request_review_again(&self.owner, &self.repo_name, pr_number, reviewer).await {
+                Ok(()) => {
+                    self.set_message(format!("Re-requested review from {}", reviewer));
+                    self.spawn_fetch_prs();
+                }
+                Err(e) => {
+                    self.error = Some(format!("Failed to re-request review: {}", e));
+                }
+            }
+            self.loading = false;
+            self.loading_what = None;
+        }
+    }
+
+    async fn remove_label(&mut self, label: &str) {
+        let pr_number = match &self.selected_pr {
+            Some(pr) => pr.number,
+            None => return,
+        };
+
+        if let Some(client) = &self.client {
+            self.loading = true;
+            self.loading_what = Some("Removing label...".to_string());
+            match client.remove_pr_label(&self.owner, &self.repo_name, pr_number, label).await {
+                Ok(_) => {
+                    self.set_message(format!("Removed label '{}' from PR #{}", label, pr_number));
+                    if let Some(ref mut pr) = self.selected_pr {
+                        pr.labels.retain(|l| l.name != label);
+                    }
+                    // Refresh PRs to get updated labels
+                    self.spawn_fetch_prs();
+                }
+                Err(e) => {
+                    self.error = Some(format!("Failed to remove label: {}", e));
+                }
+            }
+            self.loading = false;
+            self.loading_what = None;
+        }
+    }
+
     async fn submit_add_reviewer(&mut self) {
         let pr_number = match &self.selected_pr {
             Some(pr) => pr.number,
@@ -1496,13 +3830,212 @@ impl App {
         }
     }
 
+    /// Run a `:` command: `1234`, `#1234` or `pr 1234` jumps to a PR,
+    /// `repo owner/name` switches repositories
+    fn submit_command(&mut self) {
+        let input = self.input_buffer.trim().to_string();
+        let words: Vec<&str> = input.split_whitespace().collect();
+        match words.as_slice() {
+            [] => {}
+            ["repo", repo] => self.switch_repo(repo),
+            ["pr", number] | [number] => match number.trim_start_matches('#').parse() {
+                Ok(pr_number) => self.jump_to_pr(pr_number),
+                Err(_) => self.error = Some(format!("Unknown command: {}", input)),
+            },
+            _ => self.error = Some(format!("Unknown command: {}", input)),
+        }
+    }
+
+    fn jump_to_pr(&mut self, pr_number: u64) {
+        self.tab = Tab::PRs;
+        let known = self.all_prs.iter().chain(self.closed_prs.iter()).any(|pr| pr.number == pr_number);
+        if !known && self.pr_filter != PrFilter::Closed {
+            // Not among the open PRs - it may be closed or merged
+            self.pending_action = Some(PendingAction::SearchClosed(pr_number));
+        } else {
+            self.select_pr_by_number(pr_number);
+        }
+    }
+
+    /// Refetch closed PRs and select `pr_number` once they arrive
+    fn find_closed_pr(&mut self, pr_number: u64) {
+        self.pr_filter = PrFilter::Closed;
+        self.apply_pr_filter();
+        self.initial_pr = Some(pr_number);
+        self.loading = true;
+        self.loading_what = Some("Loading closed PRs...".to_string());
+        self.spawn_fetch_closed_prs();
+    }
+
+    /// Point the app at another repository, keeping the client and settings
+    fn switch_repo(&mut self, repo: &str) {
+        if !util::is_valid_repo(repo) {
+            self.error = Some(format!("Invalid repository '{}', expected owner/repo", repo));
+            return;
+        }
+
+        // A fresh message channel drops results still in flight for the old
+        // repository instead of mixing them in; the loads behind them stop too
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.async_tx = Some(tx);
+        self.async_rx = Some(rx);
+        for load in self.loads.drain(..) {
+            load.cancel();
+        }
+
+        // Only what belongs to the old repository is reset; settings and
+        // toggles stay as they are
+        let (owner, repo_name) = repo.split_once('/').unwrap_or_default();
+        self.repo = repo.to_string();
+        self.owner = owner.to_string();
+        self.repo_name = repo_name.to_string();
+        if self.tab == Tab::Logs {
+            self.tab = Tab::Actions;
+        }
+        self.view = View::List;
+        self.focus = Focus::List;
+
+        self.all_prs.clear();
+        self.closed_prs.clear();
+        self.prs.clear();
+        self.recent_branch = None;
+        self.pr_list_state = ListState::default();
+        self.selected_pr = None;
+        self.selected_prs.clear();
+        self.pr_generation += 1;
+        self.pr_diff = None;
+        self.pr_diff_stat = None;
+        self.pr_diff_view = Default::default();
+        self.commit_diff_view = Default::default();
+        self.diff_search = None;
+        self.diff_full = false;
+        self.viewed_files.clear();
+        self.pr_query.clear();
+        self.updated_prs.clear();
+        self.diff_scroll = 0;
+        self.body_scroll = 0;
+        self.pr_checks.clear();
+        self.pr_checks_state = ListState::default();
+        self.collapsed_workflows.clear();
+        self.pr_reviews.clear();
+        self.diff_mode = DiffMode::default();
+        self.pr_commits.clear();
+        self.pr_commits_state = ListState::default();
+        self.pr_auto_merge = None;
+        self.required_approvals.clear();
+        self.merged_branch = None;
+        self.commit_ci.clear();
+        self.commits_loaded = false;
+        self.reviews_loaded = false;
+        self.commit_diff = None;
+
+        self.runs.clear();
+        self.run_list_state = ListState::default();
+        self.runs_page = 0;
+        self.runs_loading_more = false;
+        self.runs_exhausted = false;
+        self.runs_reloading = false;
+        self.selected_run = None;
+        self.jobs.clear();
+        self.job_list_state = ListState::default();
+        self.artifacts.clear();
+        self.artifact_list_state = ListState::default();
+        self.artifacts_focused = false;
+        self.expanded_jobs.clear();
+        self.pending_deployments.clear();
+        self.failure_jump = false;
+        self.collapsed_groups.clear();
+        self.set_logs(String::new());
+        self.log_scroll = 0;
+        self.log_h_scroll = 0;
+        self.log_search = None;
+        self.log_matches.clear();
+        self.log_match_index = 0;
+        self.selected_url = None;
+        self.log_follow = false;
+        self.last_log_poll = None;
+
+        if !self.notifications_all_repos {
+            self.notifications.clear();
+            self.notification_list_state = ListState::default();
+            self.notifications_loaded = false;
+        }
+        self.last_poll = None;
+
+        self.error = None;
+        self.error_popup = false;
+        self.input_mode = None;
+        self.input_buffer.clear();
+        self.pending_action = None;
+        self.label_picker = None;
+        self.review_picker = None;
+        self.dismissing_review = None;
+        self.workflows.clear();
+        self.workflow_picker = None;
+        self.default_branch.clear();
+        self.dispatch = None;
+        self.editing_description = false;
+        self.description_editor = None;
+        self.initial_pr = None;
+
+        self.loading = true;
+        self.loading_what = Some(format!("Loading {}...", self.repo));
+        self.spawn_fetch_prs();
+        self.spawn_fetch_runs();
+        if self.pr_filter == PrFilter::Closed {
+            self.spawn_fetch_closed_prs();
+        }
+    }
+
+    async fn submit_assignee(&mut self) {
+        let pr_number = match &self.selected_pr {
+            Some(pr) => pr.number,
+            None => return,
+        };
+
+        let input = self.input_buffer.trim().to_string();
+        let (remove, login) = match input.strip_prefix('-') {
+            Some(login) => (true, login.trim().to_string()),
+            None => (false, input),
+        };
+        if login.is_empty() {
+            self.error = Some("Assignee username cannot be empty".to_string());
+            return;
+        }
+
+        if let Some(client) = &self.client {
+            self.loading = true;
+            self.loading_what = Some(if remove { "Removing assignee..." } else { "Adding assignee..." }.to_string());
+            let result = if remove {
+                client.remove_assignees(&self.owner, &self.repo_name, pr_number, &[login.as_str()]).await
+            } else {
+                client.add_assignees(&self.owner, &self.repo_name, pr_number, &[login.as_str()]).await
+            };
+            match result {
+                Ok(_) => {
+                    if remove {
+                        self.set_message(format!("Unassigned '{}' from PR #{}", login, pr_number));
+                    } else {
+                        self.set_message(format!("Assigned '{}' to PR #{}", login, pr_number));
+                    }
+                    // Refresh PRs to get updated assignees
+                    self.spawn_fetch_prs();
+                }
+                Err(e) => {
+                    self.error = Some(format!("Failed to update assignees: {}", e));
+                }
+            }
+            self.loading = false;
+            self.loading_what = None;
+        }
+    }
+
     fn copy_branch_to_clipboard(&mut self) {
         if let Some(pr) = &self.selected_pr {
-            let branch = &pr.head.ref_name;
-            if Self::copy_to_clipboard(branch) {
-                self.set_message(format!("Copied branch: {}", branch));
-            } else {
-                self.error = Some("Failed to copy to clipboard".to_string());
+            let branch = pr.head.ref_name.clone();
+            match self.copy_to_clipboard(&branch) {
+                Ok(()) => self.set_message(format!("Copied branch: {}", branch)),
+                Err(e) => self.error = Some(format!("Failed to copy to clipboard: {}", e)),
             }
         }
     }
@@ -1510,31 +4043,91 @@ impl App {
     fn copy_checkout_command_to_clipboard(&mut self) {
         if let Some(pr) = &self.selected_pr {
             let cmd = format!("git checkout {}", pr.head.ref_name);
-            if Self::copy_to_clipboard(&cmd) {
-                self.set_message(format!("Copied: {}", cmd));
-            } else {
-                self.error = Some("Failed to copy to clipboard".to_string());
+            match self.copy_to_clipboard(&cmd) {
+                Ok(()) => self.set_message(format!("Copied: {}", cmd)),
+                Err(e) => self.error = Some(format!("Failed to copy to clipboard: {}", e)),
             }
         }
     }
 
     fn copy_pr_url_to_clipboard(&mut self) {
         if let Some(pr) = &self.selected_pr {
-            let url = format!("https://github.com/{}/pull/{}", self.repo, pr.number);
-            if Self::copy_to_clipboard(&url) {
-                self.set_message(format!("Copied: {}", url));
-            } else {
-                self.error = Some("Failed to copy to clipboard".to_string());
+            let url = format!("{}/{}/pull/{}", self.web_base(), self.repo, pr.number);
+            match self.copy_to_clipboard(&url) {
+                Ok(()) => self.set_message(format!("Copied: {}", url)),
+                Err(e) => self.error = Some(format!("Failed to copy to clipboard: {}", e)),
+            }
+        }
+    }
+
+    fn selected_commit(&self) -> Option<&Commit> {
+        self.pr_commits_state.selected().and_then(|i| self.pr_commits.get(i))
+    }
+
+    fn copy_commit_sha_to_clipboard(&mut self) {
+        if let Some(commit) = self.selected_commit() {
+            let (sha, short) = (commit.sha.clone(), commit.short_sha().to_string());
+            match self.copy_to_clipboard(&sha) {
+                Ok(()) => self.set_message(format!("Copied SHA of {}", short)),
+                Err(e) => self.error = Some(format!("Failed to copy to clipboard: {}", e)),
+            }
+        }
+    }
+
+    fn copy_commit_message_to_clipboard(&mut self) {
+        if let Some(commit) = self.selected_commit() {
+            let (summary, short) = (commit.first_line().to_string(), commit.short_sha().to_string());
+            match self.copy_to_clipboard(&summary) {
+                Ok(()) => self.set_message(format!("Copied message of {}", short)),
+                Err(e) => self.error = Some(format!("Failed to copy to clipboard: {}", e)),
+            }
+        }
+    }
+
+    /// Copy text using the native clipboard, falling back to platform commands
+    /// if it can't be initialized (e.g. no display server available)
+    fn copy_to_clipboard(&mut self, text: &str) -> Result<(), String> {
+        if self.clipboard.is_none() {
+            match arboard::Clipboard::new() {
+                // Keep the handle alive - on X11 the contents vanish when it is dropped
+                Ok(clipboard) => self.clipboard = Some(clipboard),
+                Err(e) => {
+                    return Self::copy_with_command(text)
+                        .map_err(|cmd_err| format!("{} ({})", e, cmd_err));
+                }
             }
         }
+
+        match self.clipboard.as_mut() {
+            Some(clipboard) => clipboard.set_text(text).map_err(|e| e.to_string()),
+            None => Err("clipboard unavailable".to_string()),
+        }
     }
 
-    fn copy_to_clipboard(text: &str) -> bool {
-        // Try different clipboard commands based on platform
+    /// Pipe text into the first clipboard command that succeeds
+    fn copy_with_command(text: &str) -> Result<(), String> {
         #[cfg(target_os = "macos")]
-        {
-            std::process::Command::new("pbcopy")
+        let commands: &[(&str, &[&str])] = &[("pbcopy", &[])];
+
+        #[cfg(target_os = "linux")]
+        let commands: &[(&str, &[&str])] = &[
+            ("wl-copy", &[]),
+            ("xclip", &["-selection", "clipboard"]),
+            ("xsel", &["--clipboard", "--input"]),
+        ];
+
+        #[cfg(target_os = "windows")]
+        let commands: &[(&str, &[&str])] = &[("clip", &[])];
+
+        #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+        let commands: &[(&str, &[&str])] = &[];
+
+        for (program, args) in commands {
+            let result = std::process::Command::new(program)
+                .args(*args)
                 .stdin(std::process::Stdio::piped())
+                .stdout(std::process::Stdio::null())
+                .stderr(std::process::Stdio::null())
                 .spawn()
                 .and_then(|mut child| {
                     use std::io::Write;
@@ -1542,59 +4135,295 @@ impl App {
                         stdin.write_all(text.as_bytes())?;
                     }
                     child.wait()
-                })
-                .map(|s| s.success())
-                .unwrap_or(false)
+                });
+
+            if matches!(result, Ok(status) if status.success()) {
+                return Ok(());
+            }
         }
 
-        #[cfg(target_os = "linux")]
-        {
-            // Try xclip first, then xsel
-            std::process::Command::new("xclip")
-                .args(["-selection", "clipboard"])
-                .stdin(std::process::Stdio::piped())
-                .spawn()
-                .and_then(|mut child| {
-                    use std::io::Write;
-                    if let Some(stdin) = child.stdin.as_mut() {
-                        stdin.write_all(text.as_bytes())?;
+        let tried: Vec<&str> = commands.iter().map(|(program, _)| *program).collect();
+        if tried.is_empty() {
+            Err("no clipboard command available on this platform".to_string())
+        } else {
+            Err(format!("no working clipboard command (tried {})", tried.join(", ")))
+        }
+    }
+
+    fn open_pr_in_browser(&mut self) {
+        if let Some(pr) = &self.selected_pr {
+            let url = format!("{}/{}/pull/{}", self.web_base(), self.repo, pr.number);
+            if Self::open_url(&url) {
+                self.set_message(format!("Opened PR #{} in browser", pr.number));
+            } else {
+                self.error = Some("Failed to open browser".to_string());
+            }
+        }
+    }
+
+    /// Web page of the selected job in the jobs view, else of the selected run,
+    /// with a name for messages
+    fn selected_run_url(&self) -> Option<(String, String)> {
+        if self.view == View::Jobs {
+            if let Some(job) = self.selected_job().filter(|j| !j.html_url.is_empty()) {
+                return Some((format!("job {}", job.name), job.html_url.clone()));
+            }
+        }
+        let run = match self.view {
+            View::Jobs => self.selected_run.as_ref(),
+            _ => self.run_list_state.selected().and_then(|i| self.runs.get(i)),
+        }?;
+        (!run.html_url.is_empty()).then(|| (format!("run #{}", run.run_number), run.html_url.clone()))
+    }
+
+    fn open_run_in_browser(&mut self) {
+        if let Some((name, url)) = self.selected_run_url() {
+            if Self::open_url(&url) {
+                self.set_message(format!("Opened {} in browser", name));
+            } else {
+                self.error = Some("Failed to open browser".to_string());
+            }
+        }
+    }
+
+    fn copy_run_url_to_clipboard(&mut self) {
+        if let Some((_, url)) = self.selected_run_url() {
+            match self.copy_to_clipboard(&url) {
+                Ok(()) => self.set_message(format!("Copied: {}", url)),
+                Err(e) => self.error = Some(format!("Failed to copy to clipboard: {}", e)),
+            }
+        }
+    }
+
+    /// URLs in the lines on screen: the log pane on the Logs tab, the PR
+    /// description on the PRs tab
+    fn visible_urls(&self) -> Vec<String> {
+        let lines: Vec<String> = match self.tab {
+            Tab::Logs => {
+                self.visible_log_lines()
+                    .iter()
+                    .skip(self.log_scroll as usize)
+                    .take(self.log_height as usize)
+                    .map(|&i| ui::strip_ansi(self.log_line(i)))
+                    .collect()
+            }
+            Tab::PRs => match self.selected_pr.as_ref().and_then(|pr| pr.body.as_deref()) {
+                Some(body) if self.view != View::Diff => {
+                    // The description wraps, so count rows rather than lines
+                    let (width, height) = (self.body_area.0.max(1) as usize, self.body_area.1 as usize);
+                    let (top, bottom) = (self.body_scroll as usize, self.body_scroll as usize + height);
+                    let mut row = 0;
+                    let mut visible = Vec::new();
+                    for line in ui::render_markdown(body, &self.theme).lines {
+                        let rows = line.width().div_ceil(width).max(1);
+                        if row + rows > top && row < bottom {
+                            visible.push(line.spans.iter().map(|s| s.content.as_ref()).collect());
+                        }
+                        row += rows;
                     }
-                    child.wait()
-                })
-                .map(|s| s.success())
-                .unwrap_or(false)
+                    visible
+                }
+                _ => Vec::new(),
+            },
+            Tab::Actions | Tab::Notifications => Vec::new(),
+        };
+
+        let mut urls: Vec<String> = Vec::new();
+        for url in lines.iter().flat_map(|line| util::find_urls(line)) {
+            if !urls.iter().any(|u| u == url) {
+                urls.push(url.to_string());
+            }
+        }
+        urls
+    }
+
+    fn next_url(&mut self) {
+        let urls = self.visible_urls();
+        if urls.is_empty() {
+            self.selected_url = None;
+            self.set_message("No URLs on screen");
+            return;
+        }
+
+        let next = self.selected_url.as_ref()
+            .and_then(|current| urls.iter().position(|u| u == current))
+            .map_or(0, |i| (i + 1) % urls.len());
+        self.set_message(format!("URL {}/{}: {}", next + 1, urls.len(), urls[next]));
+        self.selected_url = Some(urls[next].clone());
+    }
+
+    /// Open the URL picked with next_url, or the first one on screen
+    fn open_selected_url(&mut self) {
+        let urls = self.visible_urls();
+        let url = self.selected_url.as_ref()
+            .filter(|url| urls.contains(url))
+            .or(urls.first())
+            .cloned();
+        match url {
+            Some(url) if Self::open_url(&url) => self.set_message(format!("Opened {}", url)),
+            Some(_) => self.error = Some("Failed to open browser".to_string()),
+            None => self.set_message("No URLs on screen"),
+        }
+    }
+
+    fn submit_dispatch_ref(&mut self) {
+        let git_ref = self.input_buffer.trim().to_string();
+        let Some(form) = self.dispatch.as_mut() else {
+            return;
+        };
+        if git_ref.is_empty() {
+            self.error = Some("Ref cannot be empty".to_string());
+            self.dispatch = None;
+            return;
+        }
+        form.git_ref = git_ref.clone();
+
+        // The inputs are declared in the workflow file at that ref
+        if let (Some(client), Some(tx)) = (self.client.clone(), self.async_tx.clone()) {
+            let owner = self.owner.clone();
+            let repo = self.repo_name.clone();
+            let path = form.workflow.path.clone();
+            self.loading = true;
+            self.loading_what = Some("Reading workflow inputs...".to_string());
+            self.spawn_load(async move {
+                match client.get_dispatch_inputs(&owner, &repo, &path, &git_ref).await {
+                    Ok(inputs) => { let _ = tx.send(AsyncMsg::DispatchInputsLoaded(inputs)); }
+                    Err(e) => { let _ = tx.send(AsyncMsg::Error(e.to_string())); }
+                }
+            });
+        }
+    }
+
+    fn submit_dispatch_input(&mut self) {
+        let value = std::mem::take(&mut self.input_buffer).trim().to_string();
+        let Some(form) = self.dispatch.as_mut() else {
+            return;
+        };
+        let Some(input) = form.inputs.get(form.values.len()) else {
+            return;
+        };
+        if input.required && value.is_empty() {
+            self.error = Some(format!("'{}' is required", input.name));
+        } else {
+            form.values.insert(input.name.clone(), value);
+        }
+        self.prompt_dispatch_input();
+    }
+
+    /// Prompt for the next unfilled input, or dispatch once all are filled
+    fn prompt_dispatch_input(&mut self) {
+        let Some(form) = self.dispatch.as_ref() else {
+            return;
+        };
+        let Some(input) = form.inputs.get(form.values.len()) else {
+            self.dispatch_workflow();
+            return;
+        };
+
+        let mut prompt = input.name.clone();
+        if let Some(description) = &input.description {
+            prompt.push_str(&format!(" - {}", description));
+        }
+        if !input.options.is_empty() {
+            prompt.push_str(&format!(" [{}]", input.options.join("/")));
+        }
+        prompt.push(':');
+        self.input_buffer = input.default.clone().unwrap_or_default();
+        self.input_mode = Some(InputMode::DispatchInput);
+        self.show_prompt(prompt);
+    }
+
+    fn dispatch_workflow(&mut self) {
+        let Some(form) = self.dispatch.take() else {
+            return;
+        };
+        if let (Some(client), Some(tx)) = (self.client.clone(), self.async_tx.clone()) {
+            let owner = self.owner.clone();
+            let repo = self.repo_name.clone();
+            self.loading = true;
+            self.loading_what = Some(format!("Dispatching {}...", form.workflow.name));
+            tokio::spawn(async move {
+                match client.dispatch_workflow(&owner, &repo, form.workflow.id, &form.git_ref, &form.values).await {
+                    Ok(()) => {
+                        let message = format!("Dispatched '{}' on {}", form.workflow.name, form.git_ref);
+                        let _ = tx.send(AsyncMsg::WorkflowDispatched(message));
+                    }
+                    Err(e) => { let _ = tx.send(AsyncMsg::Error(e.to_string())); }
+                }
+            });
+        }
+    }
+
+    /// Load the inbox the first time its tab is shown; `r` reloads it
+    fn load_notifications_once(&mut self) {
+        if self.tab == Tab::Notifications && !self.notifications_loaded {
+            self.notifications_loaded = true;
+            self.loading = true;
+            self.loading_what = Some("Loading notifications...".to_string());
+            self.spawn_fetch_notifications();
         }
+    }
 
-        #[cfg(target_os = "windows")]
-        {
-            std::process::Command::new("clip")
-                .stdin(std::process::Stdio::piped())
-                .spawn()
-                .and_then(|mut child| {
-                    use std::io::Write;
-                    if let Some(stdin) = child.stdin.as_mut() {
-                        stdin.write_all(text.as_bytes())?;
-                    }
-                    child.wait()
-                })
-                .map(|s| s.success())
-                .unwrap_or(false)
+    fn selected_notification(&self) -> Option<&Notification> {
+        self.notification_list_state.selected().and_then(|i| self.notifications.get(i))
+    }
+
+    fn next_notification(&mut self) {
+        let len = self.notifications.len();
+        if len == 0 { return; }
+        let i = match self.notification_list_state.selected() {
+            Some(i) => (i + 1) % len,
+            None => 0,
+        };
+        self.notification_list_state.select(Some(i));
+    }
+
+    fn previous_notification(&mut self) {
+        let len = self.notifications.len();
+        if len == 0 { return; }
+        let i = match self.notification_list_state.selected() {
+            Some(i) => (i + len - 1) % len,
+            None => 0,
+        };
+        self.notification_list_state.select(Some(i));
+    }
+
+    /// PRs of this repo open in the PRs tab; everything else in the browser
+    fn open_notification(&mut self) {
+        let Some(notification) = self.selected_notification() else {
+            return;
+        };
+        match notification.pr_number() {
+            Some(number) if notification.repo == self.repo => self.jump_to_pr(number),
+            _ => self.open_notification_in_browser(),
         }
+    }
 
-        #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
-        {
-            false
+    fn open_notification_in_browser(&mut self) {
+        let Some(notification) = self.selected_notification() else {
+            return;
+        };
+        // Subjects without a page of their own (e.g. CI runs) open the repo
+        let url = notification.url.clone()
+            .unwrap_or_else(|| format!("{}/{}", self.web_base(), notification.repo));
+        if Self::open_url(&url) {
+            self.set_message(format!("Opened {}", url));
+        } else {
+            self.error = Some("Failed to open browser".to_string());
         }
     }
 
-    fn open_pr_in_browser(&mut self) {
-        if let Some(pr) = &self.selected_pr {
-            let url = format!("https://github.com/{}/{}/pull/{}", self.owner, self.repo_name, pr.number);
-            if Self::open_url(&url) {
-                self.set_message(format!("Opened PR #{} in browser", pr.number));
-            } else {
-                self.error = Some("Failed to open browser".to_string());
-            }
+    fn mark_notification_read(&mut self) {
+        let Some(id) = self.selected_notification().map(|n| n.id.clone()) else {
+            return;
+        };
+        if let (Some(client), Some(tx)) = (self.client.clone(), self.async_tx.clone()) {
+            tokio::spawn(async move {
+                match client.mark_notification_read(&id).await {
+                    Ok(()) => { let _ = tx.send(AsyncMsg::NotificationRead(id)); }
+                    Err(e) => { let _ = tx.send(AsyncMsg::Error(e.to_string())); }
+                }
+            });
         }
     }
 
@@ -1654,47 +4483,67 @@ impl App {
     }
 
     async fn rerun_pr_check(&mut self) {
-        if let Some(i) = self.pr_checks_state.selected() {
-            if let Some(check) = self.pr_checks.get(i).cloned() {
-                if let Some(client) = &self.client {
-                    self.loading = true;
-                    self.loading_what = Some("Triggering rerun...".to_string());
-                    match client.rerun_workflow(&self.owner, &self.repo_name, check.id).await {
-                        Ok(_) => {
-                            self.set_message(format!("Rerun triggered for {}", check.name));
-                            // Refresh PR checks
-                            if let Some(pr) = &self.selected_pr {
-                                self.spawn_fetch_pr_checks(&pr.head.sha);
-                            }
-                        }
-                        Err(e) => {
-                            self.error = Some(format!("Failed to rerun: {}", e));
-                        }
+        let Some(check) = self.selected_pr_check().cloned() else {
+            return;
+        };
+        let Some(run) = &check.run else {
+            self.error = Some(format!("{} is reported by {}; rerun it there", check.name, check.source));
+            return;
+        };
+        if let Some(client) = &self.client {
+            self.loading = true;
+            self.loading_what = Some("Triggering rerun...".to_string());
+            match client.rerun_workflow(&self.owner, &self.repo_name, run.id).await {
+                Ok(_) => {
+                    self.set_message(format!("Rerun triggered for {}", check.name));
+                    // Refresh PR checks
+                    if let Some(sha) = self.selected_pr.as_ref().map(|pr| pr.head.sha.clone()) {
+                        self.spawn_fetch_pr_checks(&sha);
                     }
-                    self.loading = false;
-                    self.loading_what = None;
+                }
+                Err(e) => {
+                    self.error = Some(format!("Failed to rerun: {}", e));
                 }
             }
+            self.loading = false;
+            self.loading_what = None;
         }
     }
 
     fn refresh(&mut self) {
         self.error = None;
-        self.status_message = None;
+        self.status_messages.clear();
         self.loading = true;
+        // The branch may have been switched outside the TUI
+        self.local_head = git::local_head();
 
         match self.tab {
             Tab::PRs => {
                 self.loading_what = Some("Refreshing PRs...".to_string());
                 self.spawn_fetch_prs();
+                if self.pr_filter == PrFilter::Closed {
+                    self.spawn_fetch_closed_prs();
+                }
                 self.spawn_fetch_recent_branch();
-                if let Some(pr) = &self.selected_pr {
+                if let Some(pr) = self.selected_pr.clone() {
                     self.spawn_fetch_pr_checks(&pr.head.sha);
+                    // `r` always re-downloads, bypassing the client's diff caches
+                    self.spawn_fetch_diff(&pr, true);
+                }
+                let commit = self.pr_commits_state.selected().and_then(|i| self.pr_commits.get(i)).cloned();
+                if let (DiffMode::ByCommit, Some(commit)) = (self.diff_mode, commit) {
+                    self.spawn_fetch_commit_diff(&commit.sha, true);
                 }
             }
             Tab::Actions => {
                 self.loading_what = Some("Refreshing workflows...".to_string());
                 self.spawn_fetch_runs();
+                // Artifacts are uploaded as the run progresses, and it may reach or
+                // pass an environment that needs approval
+                if let (View::Jobs, Some(run_id)) = (self.view, self.selected_run.as_ref().map(|r| r.id)) {
+                    self.spawn_fetch_artifacts(run_id);
+                    self.spawn_fetch_pending_deployments(run_id);
+                }
             }
             Tab::Logs => {
                 self.loading_what = Some("Refreshing logs...".to_string());
@@ -1702,9 +4551,402 @@ impl App {
                     let job_id = self.job_list_state.selected()
                         .and_then(|i| self.jobs.get(i))
                         .map(|j| j.id);
-                    self.spawn_fetch_logs(run.id, job_id);
+                    self.spawn_fetch_logs(run.id, job_id, true);
+                }
+            }
+            Tab::Notifications => {
+                self.loading_what = Some("Refreshing notifications...".to_string());
+                self.spawn_fetch_notifications();
+            }
+        }
+    }
+
+    /// Whether any visible run (Actions list or PR checks) hasn't finished yet
+    pub fn has_active_runs(&self) -> bool {
+        self.runs.iter().map(|r| &r.status)
+            .chain(self.pr_checks.iter().map(|c| &c.status))
+            .any(|status| status == "in_progress" || status == "queued")
+    }
+
+    /// Re-fetch runs and the selected PR's checks on an interval while auto-refresh
+    /// is enabled and something is still running
+    fn poll_in_progress_runs(&mut self) {
+        const POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+        if !self.auto_refresh || !self.has_active_runs() {
+            return;
+        }
+        if self.last_poll.is_some_and(|t| t.elapsed() < POLL_INTERVAL) {
+            return;
+        }
+        self.last_poll = Some(Instant::now());
+
+        self.spawn_poll_runs();
+        if let Some(sha) = self.selected_pr.as_ref().map(|pr| pr.head.sha.clone()) {
+            self.spawn_fetch_pr_checks(&sha);
+        }
+    }
+
+    /// Start over from the first page of runs, after the filter changed.
+    /// The old list stays up until the new one arrives.
+    fn reload_runs(&mut self) {
+        self.run_list_state.select(None);
+        self.runs_exhausted = true;  // No paging past a list that is about to be replaced
+        self.runs_reloading = true;
+        self.spawn_fetch_runs();
+    }
+
+    /// The reload failed or was cancelled: page the list that stays up again
+    fn stop_reloading_runs(&mut self) {
+        if self.runs_reloading {
+            self.runs_reloading = false;
+            self.runs_exhausted = false;
+        }
+    }
+
+    /// Update runs in place from a poll, adding new ones at the top so the
+    /// selection and any extra loaded pages are preserved
+    fn merge_polled_runs(&mut self, runs: Vec<WorkflowRun>) {
+        let selected_id = self.run_list_state.selected()
+            .and_then(|i| self.runs.get(i))
+            .map(|r| r.id);
+
+        let mut new_runs = Vec::new();
+        for run in runs {
+            match self.runs.iter_mut().find(|r| r.id == run.id) {
+                Some(existing) => *existing = run,
+                // A poll started before the filter changed may bring runs it hides
+                None if self.run_filter.matches(&run) => new_runs.push(run),
+                None => {}
+            }
+        }
+        self.runs.splice(0..0, new_runs);
+        // A run that changed state may no longer pass a status filter
+        self.runs.retain(|run| self.run_filter.matches(run));
+        if self.runs.is_empty() {
+            self.run_list_state.select(None);
+        } else if let Some(i) = self.run_list_state.selected() {
+            self.run_list_state.select(Some(i.min(self.runs.len() - 1)));
+        }
+
+        if let Some(id) = selected_id {
+            if let Some(idx) = self.runs.iter().position(|r| r.id == id) {
+                self.run_list_state.select(Some(idx));
+            }
+        }
+        if let Some(run) = &mut self.selected_run {
+            if let Some(updated) = self.runs.iter().find(|r| r.id == run.id) {
+                *run = updated.clone();
+            }
+        }
+    }
+
+    /// File name for saved logs, e.g. `CI-42-build` - without extension
+    fn log_file_stem(&self) -> String {
+        let Some(run) = &self.selected_run else {
+            return "logs".to_string();
+        };
+        let mut stem = format!("{}-{}", run.name, run.run_number);
+        if let Some(job) = self.selected_job() {
+            stem.push('-');
+            stem.push_str(&job.name);
+        }
+        sanitize_file_name(&stem)
+    }
+
+    /// Write the log buffer without ANSI codes to the current directory
+    fn save_logs(&mut self) {
+        if self.logs.is_empty() {
+            self.set_message("No logs to save");
+            return;
+        }
+
+        let path = format!("./{}.log", self.log_file_stem());
+        let mut contents: String = self.log_lines()
+            .map(|line| ui::strip_ansi(line) + "\n")
+            .collect();
+        if contents.is_empty() {
+            contents.push('\n');
+        }
+
+        if let Some(tx) = self.async_tx.clone() {
+            tokio::spawn(async move {
+                match tokio::fs::write(&path, contents).await {
+                    Ok(()) => { let _ = tx.send(AsyncMsg::Message(format!("Saved logs to {}", path))); }
+                    Err(e) => { let _ = tx.send(AsyncMsg::Error(format!("Failed to save {}: {}", path, e))); }
+                }
+            });
+        }
+    }
+
+    /// Download the run's log zip and save it unmodified
+    fn save_log_archive(&mut self) {
+        let Some(run) = &self.selected_run else {
+            return;
+        };
+        let run_id = run.id;
+        let path = format!("./{}.zip", sanitize_file_name(&format!("{}-{}-logs", run.name, run.run_number)));
+
+        if let (Some(client), Some(tx)) = (self.client.clone(), self.async_tx.clone()) {
+            let owner = self.owner.clone();
+            let repo = self.repo_name.clone();
+            self.set_message("Downloading log archive...");
+            tokio::spawn(async move {
+                let result = match client.download_run_logs_archive(&owner, &repo, run_id).await {
+                    Ok(bytes) => tokio::fs::write(&path, bytes).await.map_err(anyhow::Error::from),
+                    Err(e) => Err(e),
+                };
+                match result {
+                    Ok(()) => { let _ = tx.send(AsyncMsg::Message(format!("Saved log archive to {}", path))); }
+                    Err(e) => { let _ = tx.send(AsyncMsg::Error(format!("Failed to save {}: {}", path, e))); }
+                }
+            });
+        }
+    }
+
+    /// Download the selected artifact and extract it into `./<artifact>-<run number>/`
+    fn download_artifact(&mut self) {
+        let (Some(run), Some(artifact)) = (
+            &self.selected_run,
+            self.artifact_list_state.selected().and_then(|i| self.artifacts.get(i)),
+        ) else {
+            self.set_message("No artifacts for this run");
+            return;
+        };
+        if artifact.expired {
+            self.set_message(format!("Artifact {} has expired", artifact.name));
+            return;
+        }
+        let artifact = artifact.clone();
+        let dir = format!("./{}", sanitize_file_name(&format!("{}-{}", artifact.name, run.run_number)));
+
+        if let (Some(client), Some(tx)) = (self.client.clone(), self.async_tx.clone()) {
+            self.set_message(format!("Downloading {} ({})...", artifact.name, artifact.size()));
+            tokio::spawn(async move {
+                match client.download_artifact(&artifact, dir.clone().into()).await {
+                    Ok(files) => {
+                        let _ = tx.send(AsyncMsg::Message(format!("Saved {} to {}/ ({} files)", artifact.name, dir, files)));
+                    }
+                    Err(e) => { let _ = tx.send(AsyncMsg::Error(format!("Failed to save {}: {}", artifact.name, e))); }
                 }
+            });
+        }
+    }
+
+    /// Re-fetch logs on an interval while following a running job
+    fn poll_followed_logs(&mut self) {
+        const FOLLOW_INTERVAL: Duration = Duration::from_secs(5);
+
+        if !self.log_follow || self.last_log_poll.is_some_and(|t| t.elapsed() < FOLLOW_INTERVAL) {
+            return;
+        }
+        let (Some(run), Some(job)) = (&self.selected_run, self.selected_job()) else {
+            self.log_follow = false;
+            return;
+        };
+        let (run_id, job_id) = (run.id, job.id);
+
+        // One final fetch after the job finishes picks up the complete log
+        if job.status != "in_progress" {
+            self.log_follow = false;
+            self.set_message("Job finished - stopped following");
+        }
+
+        self.last_log_poll = Some(Instant::now());
+        self.spawn_follow_logs(run_id, job_id);
+    }
+
+    /// Replace the log buffer with a newer snapshot, keeping folds and staying
+    /// pinned to the bottom unless the user has scrolled up
+    fn update_logs_in_place(&mut self, logs: String) {
+        let was_at_bottom = self.log_scroll >= self.log_bottom();
+
+        // Keep existing folds; groups that appeared while following stay open
+        self.set_logs(logs);
+        let starts: HashSet<usize> = self.log_groups.iter().map(|g| g.start).collect();
+        self.collapsed_groups.retain(|start| starts.contains(start));
+        self.refresh_visible_log_lines();
+
+        if was_at_bottom {
+            self.log_scroll = self.log_bottom();
+        }
+    }
+
+    /// Replace the log buffer, splitting it into lines once rather than on every frame
+    fn set_logs(&mut self, logs: String) {
+        self.log_groups = LogGroup::parse(&logs);
+        self.log_spans = logs
+            .lines()
+            .map(|line| {
+                let start = line.as_ptr() as usize - logs.as_ptr() as usize;
+                start..start + line.len()
+            })
+            .collect();
+        self.log_levels = logs.lines().map(|line| ui::line_level(&ui::strip_ansi(line))).collect();
+        self.logs = logs;
+        self.refresh_visible_log_lines();
+    }
+
+    pub fn log_line_count(&self) -> usize {
+        self.log_spans.len()
+    }
+
+    /// Line `i` of the log, without its line ending
+    pub fn log_line(&self, i: usize) -> &str {
+        &self.logs[self.log_spans[i].clone()]
+    }
+
+    pub fn log_lines(&self) -> impl Iterator<Item = &str> {
+        self.log_spans.iter().map(|span| &self.logs[span.clone()])
+    }
+
+    /// Scroll position that shows the end of the log. Wrapped lines take several
+    /// rows each, so fewer of them fit (estimated from their width).
+    fn log_bottom(&self) -> u16 {
+        let visible = self.visible_log_lines();
+        if !self.log_wrap || self.log_width == 0 {
+            return (visible.len() as u16).saturating_sub(self.log_height);
+        }
+        let mut rows = 0;
+        let mut top = visible.len();
+        while top > 0 {
+            rows += ui::strip_ansi(self.log_line(visible[top - 1])).width().div_ceil(self.log_width as usize).max(1);
+            if rows > self.log_height as usize {
+                break;
+            }
+            top -= 1;
+        }
+        top as u16
+    }
+
+    /// Indices of log lines that are shown, with collapsed groups folded to their header.
+    /// When showing problems only, error and warning lines with their context instead.
+    pub fn visible_log_lines(&self) -> &[usize] {
+        &self.log_visible
+    }
+
+    /// The group whose `##[group]` header is at raw log line `line`
+    pub fn log_group_at(&self, line: usize) -> Option<&LogGroup> {
+        // Groups don't nest, so they are sorted by start
+        let index = self.log_groups.binary_search_by_key(&line, |g| g.start).ok()?;
+        self.log_groups.get(index)
+    }
+
+    /// Recompute the visible lines after the log, its folds or the problems filter changed
+    fn refresh_visible_log_lines(&mut self) {
+        self.log_visible = self.compute_visible_log_lines();
+    }
+
+    fn compute_visible_log_lines(&self) -> Vec<usize> {
+        if self.log_problems_only {
+            return self.log_problem_lines();
+        }
+        let line_count = self.log_line_count();
+        let mut visible = Vec::with_capacity(line_count);
+        let mut groups = self.log_groups.iter().peekable();
+        let mut i = 0;
+
+        while i < line_count {
+            while groups.peek().is_some_and(|g| g.start < i) {
+                groups.next();
+            }
+            visible.push(i);
+            match groups.peek() {
+                Some(g) if g.start == i && self.collapsed_groups.contains(&g.start) => i = g.end + 1,
+                _ => i += 1,
+            }
+        }
+
+        visible
+    }
+
+    /// Error and warning lines, each with a few lines of context around it, folds ignored
+    fn log_problem_lines(&self) -> Vec<usize> {
+        const CONTEXT: usize = 2;
+        let line_count = self.log_line_count();
+        let mut visible: Vec<usize> = Vec::new();
+        for (i, level) in self.log_levels.iter().enumerate() {
+            if level.is_none() {
+                continue;
+            }
+            let from = i.saturating_sub(CONTEXT).max(visible.last().map_or(0, |&l| l + 1));
+            visible.extend(from..(i + CONTEXT + 1).min(line_count));
+        }
+        visible
+    }
+
+    /// Number of error and warning lines in the log
+    pub fn log_problem_counts(&self) -> (usize, usize) {
+        self.log_levels.iter().fold((0, 0), |(errors, warnings), level| {
+            match level {
+                Some(ui::LineLevel::Error) => (errors + 1, warnings),
+                Some(ui::LineLevel::Warning) => (errors, warnings + 1),
+                None => (errors, warnings),
+            }
+        })
+    }
+
+    /// Switch between the full log and errors/warnings only, keeping the top line in view
+    fn toggle_log_problems(&mut self) {
+        let top = self.visible_log_lines().get(self.log_scroll as usize).copied().unwrap_or(0);
+        self.log_problems_only = !self.log_problems_only;
+        self.refresh_visible_log_lines();
+        if self.log_problems_only && self.log_visible.is_empty() {
+            self.log_problems_only = false;
+            self.refresh_visible_log_lines();
+            self.set_message("No errors or warnings in this log");
+            return;
+        }
+        let visible = self.visible_log_lines();
+        // The first shown line at or after the old top, else the last one
+        let pos = visible.iter().position(|&l| l >= top).unwrap_or(visible.len().saturating_sub(1));
+        self.log_scroll = pos as u16;
+    }
+
+    /// The group whose header is at the top of the viewer, or that contains that line
+    fn current_log_group(&self) -> Option<&LogGroup> {
+        let line = *self.visible_log_lines().get(self.log_scroll as usize)?;
+        self.log_groups.iter().find(|g| g.start <= line && line <= g.end)
+    }
+
+    fn toggle_log_group(&mut self) {
+        let Some(start) = self.current_log_group().map(|g| g.start) else {
+            return;
+        };
+        if !self.collapsed_groups.remove(&start) {
+            self.collapsed_groups.insert(start);
+        }
+        self.refresh_visible_log_lines();
+        // Keep the toggled group's header in view
+        if let Some(pos) = self.visible_log_lines().iter().position(|&l| l == start) {
+            self.log_scroll = pos as u16;
+        }
+    }
+
+    /// Scroll so a raw log line is at the top, unfolding its group if needed
+    fn scroll_to_log_line(&mut self, line: usize) {
+        if let Some(group) = self.log_groups.iter().find(|g| g.start < line && line <= g.end) {
+            self.collapsed_groups.remove(&group.start);
+            self.refresh_visible_log_lines();
+        }
+        if let Some(pos) = self.visible_log_lines().iter().position(|&l| l == line) {
+            self.log_scroll = pos as u16;
+        }
+    }
+
+    /// Scroll to the 1-based line number typed at the prompt, clamped to the log
+    fn goto_log_line(&mut self) {
+        let line_count = self.log_line_count();
+        if line_count == 0 {
+            return;
+        }
+        match self.input_buffer.trim().parse::<usize>() {
+            Ok(line) => {
+                let line = line.clamp(1, line_count);
+                self.scroll_to_log_line(line - 1);
+                self.set_message(format!("Line {} of {}", line, line_count));
             }
+            Err(_) => self.error = Some(format!("Not a line number: {}", self.input_buffer.trim())),
         }
     }
 
@@ -1712,14 +4954,14 @@ impl App {
         self.log_matches.clear();
         if let Some(ref search) = self.log_search {
             let search_lower = search.to_lowercase();
-            for (i, line) in self.logs.lines().enumerate() {
-                if line.to_lowercase().contains(&search_lower) {
-                    self.log_matches.push(i);
-                }
-            }
+            self.log_matches = self.log_lines()
+                .enumerate()
+                .filter(|(_, line)| line.to_lowercase().contains(&search_lower))
+                .map(|(i, _)| i)
+                .collect();
             self.log_match_index = 0;
             if let Some(&line) = self.log_matches.first() {
-                self.log_scroll = line as u16;
+                self.scroll_to_log_line(line);
             }
         }
     }
@@ -1727,7 +4969,7 @@ impl App {
     fn next_log_match(&mut self) {
         if !self.log_matches.is_empty() {
             self.log_match_index = (self.log_match_index + 1) % self.log_matches.len();
-            self.log_scroll = self.log_matches[self.log_match_index] as u16;
+            self.scroll_to_log_line(self.log_matches[self.log_match_index]);
         }
     }
 
@@ -1735,13 +4977,183 @@ impl App {
         if !self.log_matches.is_empty() {
             self.log_match_index = (self.log_match_index + self.log_matches.len() - 1)
                 % self.log_matches.len();
-            self.log_scroll = self.log_matches[self.log_match_index] as u16;
+            self.scroll_to_log_line(self.log_matches[self.log_match_index]);
         }
     }
 
-    /// Set a notification message that auto-dismisses after 3 seconds
+    /// Add a notification message that auto-dismisses (see `notification_duration`).
+    /// Up to `MAX_NOTIFICATIONS` stack in the footer; the oldest makes way for a new one.
     fn set_message(&mut self, msg: impl Into<String>) {
-        self.status_message = Some(StatusMessage::notification(msg, Duration::from_secs(3)));
+        const MAX_NOTIFICATIONS: usize = 3;
+
+        let msg = msg.into();
+        let duration = self.notification_duration(&msg);
+
+        let notifications = self.status_messages.iter().filter(|m| !m.is_prompt()).count();
+        if notifications >= MAX_NOTIFICATIONS {
+            if let Some(oldest) = self.status_messages.iter().position(|m| !m.is_prompt()) {
+                self.status_messages.remove(oldest);
+            }
+        }
+        self.status_messages.push_back(StatusMessage::notification(msg, duration));
+    }
+
+    /// `notification_secs` from the config, or else 3 seconds plus one for every
+    /// 30 characters past the first 30, up to 10
+    fn notification_duration(&self, text: &str) -> Duration {
+        let secs = self.config.notification_secs.unwrap_or_else(|| {
+            let extra = text.chars().count().saturating_sub(30) / 30;
+            (3 + extra as u64).min(10)
+        });
+        Duration::from_secs(secs)
+    }
+
+    /// Show the prompt of an input mode, replacing any earlier one
+    fn show_prompt(&mut self, text: impl Into<String>) {
+        self.clear_prompt();
+        self.status_messages.push_back(StatusMessage::prompt(text));
+    }
+
+    fn clear_prompt(&mut self) {
+        self.status_messages.retain(|m| !m.is_prompt());
+    }
+}
+
+/// Replace characters that are awkward in file names (spaces, slashes) with `-`
+fn sanitize_file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' || c == '.' { c } else { '-' })
+        .collect()
+}
+
+/// Order checks so runs of the same workflow are adjacent, workflows in order of first appearance
+fn group_checks(checks: Vec<Check>) -> Vec<Check> {
+    let mut groups: Vec<Vec<Check>> = Vec::new();
+    for check in checks {
+        match groups.iter_mut().find(|g| g[0].name == check.name) {
+            Some(group) => group.push(check),
+            None => groups.push(vec![check]),
+        }
+    }
+    groups.into_iter().flatten().collect()
+}
+
+/// Fetch `refspec` from `remote` and check out `branch` at `start`. A new
+/// branch is created there; an existing one is only fast-forwarded, so local
+/// commits are never reset away.
+async fn checkout_branch(remote: &str, refspec: &str, branch: &str, start: &str) -> Result<(), String> {
+    run_git(&["fetch", remote, refspec]).await.map_err(|e| format!("Fetch failed: {}", e))?;
+
+    let local_ref = format!("refs/heads/{}", branch);
+    if run_git(&["rev-parse", "--verify", "--quiet", &local_ref]).await.is_err() {
+        return run_git(&["checkout", "-b", branch, start]).await.map_err(|e| format!("Checkout failed: {}", e));
+    }
+    run_git(&["checkout", branch]).await.map_err(|e| format!("Checkout failed: {}", e))?;
+    run_git(&["merge", "--ff-only", start]).await.map_err(|_| {
+        format!("Checked out {}, but it has diverged from the PR; update it by hand", branch)
+    })
+}
+
+/// Why a PR can't be merged at all: closed, a draft, or conflicting.
+/// Checks that are still running don't block; auto-merge waits for them.
+fn merge_blockers(pr: &PullRequest) -> Vec<&'static str> {
+    if pr.merged || pr.state == "closed" {
+        return vec![if pr.merged { "already merged" } else { "closed" }];
+    }
+    let mut blockers = Vec::new();
+    if pr.draft {
+        blockers.push("draft");
+    }
+    if pr.mergeable == Some(false) {
+        blockers.push("conflicts");
+    }
+    blockers
+}
+
+/// Run git in the current directory, with its stderr as the error
+async fn run_git(args: &[&str]) -> Result<(), String> {
+    let output = tokio::process::Command::new("git")
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| e.to_string())?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
     }
 }
 
+/// Index of the list item under `at` in a bordered list drawn in `area`
+/// and scrolled by `offset`, if `at` is on one of its rows
+fn list_row(area: Rect, at: Position, offset: usize) -> Option<usize> {
+    let inner = area.inner(Margin::new(1, 1));
+    inner.contains(at).then(|| offset + (at.y - inner.y) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOG: &str = "setup\n##[group]Build\ncargo build\nCompiling\n##[endgroup]\n##[group]Test\ncargo test\n##[error]failed";
+
+    #[test]
+    fn parses_groups_with_and_without_end_marker() {
+        let groups = LogGroup::parse(LOG);
+        let spans: Vec<(usize, usize, &str)> = groups.iter().map(|g| (g.start, g.end, g.name.as_str())).collect();
+        assert_eq!(spans, [(1, 4, "Build"), (5, 7, "Test")]);
+
+        // A new group closes one that is still open
+        let groups = LogGroup::parse("##[group]A\na\n##[group]B\nb");
+        let spans: Vec<(usize, usize)> = groups.iter().map(|g| (g.start, g.end)).collect();
+        assert_eq!(spans, [(0, 1), (2, 3)]);
+    }
+
+    #[test]
+    fn collapsed_groups_fold_to_their_header() {
+        let mut app = App::default();
+        app.set_logs(LOG.to_string());
+        assert_eq!(app.visible_log_lines(), [0, 1, 2, 3, 4, 5, 6, 7]);
+
+        app.collapsed_groups.insert(1);
+        app.refresh_visible_log_lines();
+        assert_eq!(app.visible_log_lines(), [0, 1, 5, 6, 7]);
+        assert_eq!(app.log_group_at(5).map(|g| g.name.as_str()), Some("Test"));
+        assert!(app.log_group_at(2).is_none());
+
+        // Jumping into a folded group unfolds it
+        app.scroll_to_log_line(3);
+        assert!(app.collapsed_groups.is_empty());
+        assert_eq!(app.visible_log_lines().len(), 8);
+        assert_eq!(app.log_scroll, 3);
+    }
+
+    fn run(id: u64, status: &str, conclusion: Option<&str>) -> WorkflowRun {
+        WorkflowRun {
+            id,
+            name: "CI".to_string(),
+            head_branch: "main".to_string(),
+            head_sha: "abc".to_string(),
+            status: status.to_string(),
+            conclusion: conclusion.map(str::to_string),
+            run_number: id,
+            event: "push".to_string(),
+            created_at: String::new(),
+            updated_at: String::new(),
+            html_url: String::new(),
+        }
+    }
+
+    #[test]
+    fn polled_runs_that_leave_the_filter_are_dropped() {
+        let mut app = App::default();
+        app.run_filter.status = RunStatusFilter::InProgress;
+        app.runs = vec![run(2, "in_progress", None), run(1, "in_progress", None)];
+        app.run_list_state.select(Some(1));
+
+        app.merge_polled_runs(vec![run(3, "in_progress", None), run(2, "completed", Some("success")), run(1, "in_progress", None)]);
+        let ids: Vec<u64> = app.runs.iter().map(|r| r.id).collect();
+        assert_eq!(ids, [3, 1]);
+        assert_eq!(app.run_list_state.selected(), Some(1));
+    }
+}
@@ -0,0 +1,196 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::keymap::KeyList;
+
+/// User defaults from `~/.config/github-tui/config.toml`. Every key is optional:
+///
+/// ```toml
+/// default_repo = "owner/repo"    # when --repo/--pr don't name one and cwd has no GitHub remote
+/// merge_method = "squash"        # merge | squash | rebase
+/// per_page = 30                  # workflow runs fetched per page (1-100)
+/// max_pr_pages = 5               # pages of 100 PRs fetched per listing (1-50)
+/// loading_style = "matrix"       # matrix | popup (plain) | spinner (in the footer) | none
+/// delete_branch_on_merge = true  # after merging: true deletes, false keeps, unset asks
+/// notification_secs = 5          # how long messages stay; unset scales with their length
+/// tick_ms = 250                  # idle event-loop tick (10-5000); loading animates at 100 at most
+/// error_alert = "flash"          # flash (the footer border) | bell | both | none
+///
+/// [keys]                         # rebind actions, see keymap.rs for the names
+/// approve = "alt-v"
+/// next_item = ["j", "down", "ctrl-n"]
+///
+/// [theme]                        # colors by name, "#rrggbb" or 256-color index
+/// border_inactive = "gray"
+/// success = "#2da44e"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub default_repo: Option<String>,
+    pub merge_method: MergeMethod,
+    pub per_page: Option<u8>,
+    pub max_pr_pages: Option<usize>,
+    pub loading_style: LoadingStyle,
+    /// Older spelling of `loading_style = "popup"`, still read
+    disable_matrix_rain: bool,
+    pub delete_branch_on_merge: Option<bool>,
+    pub notification_secs: Option<u64>,
+    pub tick_ms: Option<u64>,
+    pub error_alert: ErrorAlert,
+    pub keys: HashMap<String, KeyList>,
+    pub theme: HashMap<String, String>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeMethod {
+    Merge,
+    #[default]
+    Squash,
+    Rebase,
+}
+
+/// How a fetch in progress is shown
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum LoadingStyle {
+    /// Matrix rain overlay
+    #[default]
+    Matrix,
+    /// Plain popup overlay with the description
+    Popup,
+    /// Spinner and description in the footer, the view stays usable
+    Spinner,
+    /// No indicator
+    None,
+}
+
+/// How a newly shown error gets attention
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ErrorAlert {
+    /// Briefly color the footer border
+    #[default]
+    Flash,
+    /// Ring the terminal bell
+    Bell,
+    Both,
+    None,
+}
+
+impl ErrorAlert {
+    pub fn flashes(self) -> bool {
+        matches!(self, ErrorAlert::Flash | ErrorAlert::Both)
+    }
+
+    pub fn rings(self) -> bool {
+        matches!(self, ErrorAlert::Bell | ErrorAlert::Both)
+    }
+}
+
+impl LoadingStyle {
+    /// Shown over the view, which waits for the fetch
+    pub fn is_overlay(self) -> bool {
+        matches!(self, LoadingStyle::Matrix | LoadingStyle::Popup)
+    }
+}
+
+impl MergeMethod {
+    /// Value of the `merge_method` field in the merge API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MergeMethod::Merge => "merge",
+            MergeMethod::Squash => "squash",
+            MergeMethod::Rebase => "rebase",
+        }
+    }
+}
+
+impl Config {
+    pub fn path() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| home.join(".config/github-tui/config.toml"))
+    }
+
+    /// Load the config file, or built-in defaults if there is none.
+    /// A file that exists but doesn't parse is an error rather than ignored.
+    pub fn load() -> Result<Self> {
+        match Self::path() {
+            Some(path) => Self::load_from(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let mut config: Config = toml::from_str(&content)
+            .with_context(|| format!("Invalid config file {}", path.display()))?;
+        if config.disable_matrix_rain && config.loading_style == LoadingStyle::Matrix {
+            config.loading_style = LoadingStyle::Popup;
+        }
+
+        if config.per_page.is_some_and(|n| n == 0 || n > 100) {
+            anyhow::bail!("Invalid config file {}: per_page must be between 1 and 100", path.display());
+        }
+        if config.max_pr_pages.is_some_and(|n| n == 0 || n > 50) {
+            anyhow::bail!("Invalid config file {}: max_pr_pages must be between 1 and 50", path.display());
+        }
+        if config.tick_ms.is_some_and(|ms| !(10..=5000).contains(&ms)) {
+            anyhow::bail!("Invalid config file {}: tick_ms must be between 10 and 5000", path.display());
+        }
+
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Load `content` as a config file from a scratch path
+    fn load_str(name: &str, content: &str) -> Result<Config> {
+        let path = std::env::temp_dir().join(format!("github-tui-test-{}-{}.toml", name, std::process::id()));
+        std::fs::write(&path, content).unwrap();
+        let config = Config::load_from(&path);
+        let _ = std::fs::remove_file(&path);
+        config
+    }
+
+    #[test]
+    fn missing_file_gives_defaults() {
+        let config = Config::load_from(Path::new("/nonexistent/github-tui/config.toml")).unwrap();
+        assert_eq!(config.merge_method, MergeMethod::Squash);
+        assert!(config.default_repo.is_none());
+    }
+
+    #[test]
+    fn reads_known_keys() {
+        let config = load_str("known", "merge_method = \"rebase\"\nper_page = 50\n[keys]\napprove = \"alt-v\"\n").unwrap();
+        assert_eq!(config.merge_method, MergeMethod::Rebase);
+        assert_eq!(config.per_page, Some(50));
+        assert!(config.keys.contains_key("approve"));
+    }
+
+    #[test]
+    fn disable_matrix_rain_means_popup() {
+        let config = load_str("rain", "disable_matrix_rain = true\n").unwrap();
+        assert_eq!(config.loading_style, LoadingStyle::Popup);
+        let config = load_str("rain-spinner", "disable_matrix_rain = true\nloading_style = \"spinner\"\n").unwrap();
+        assert_eq!(config.loading_style, LoadingStyle::Spinner);
+    }
+
+    #[test]
+    fn rejects_bad_toml_unknown_keys_and_out_of_range_values() {
+        assert!(load_str("syntax", "merge_method = ").is_err());
+        assert!(load_str("unknown", "merge_methd = \"merge\"").is_err());
+        assert!(load_str("variant", "merge_method = \"fast-forward\"").is_err());
+        assert!(load_str("range", "per_page = 0").is_err());
+    }
+}
@@ -1,24 +1,27 @@
-use crossterm::event::{Event as CrosstermEvent, EventStream, KeyEvent};
+use crossterm::event::{Event as CrosstermEvent, EventStream, KeyEvent, MouseEvent};
 use futures::StreamExt;
 use std::time::Duration;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, watch};
 
 #[derive(Debug)]
 pub enum Event {
     Tick,
     Key(KeyEvent),
+    Mouse(MouseEvent),
     Resize(u16, u16),
 }
 
 pub struct EventHandler {
     rx: mpsc::UnboundedReceiver<Event>,
     _tx: mpsc::UnboundedSender<Event>,
+    tick_rate: watch::Sender<Duration>,
 }
 
 impl EventHandler {
     pub fn new(tick_rate: Duration) -> Self {
         let (tx, rx) = mpsc::unbounded_channel();
         let tx_clone = tx.clone();
+        let (rate_tx, mut rate_rx) = watch::channel(tick_rate);
 
         tokio::spawn(async move {
             let mut reader = EventStream::new();
@@ -26,6 +29,9 @@ impl EventHandler {
 
             loop {
                 tokio::select! {
+                    Ok(()) = rate_rx.changed() => {
+                        tick = tokio::time::interval(*rate_rx.borrow_and_update());
+                    }
                     _ = tick.tick() => {
                         if tx_clone.send(Event::Tick).is_err() {
                             break;
@@ -34,6 +40,7 @@ impl EventHandler {
                     Some(Ok(event)) = reader.next() => {
                         let send_result = match event {
                             CrosstermEvent::Key(key) => tx_clone.send(Event::Key(key)),
+                            CrosstermEvent::Mouse(mouse) => tx_clone.send(Event::Mouse(mouse)),
                             CrosstermEvent::Resize(w, h) => tx_clone.send(Event::Resize(w, h)),
                             _ => Ok(()),
                         };
@@ -45,7 +52,12 @@ impl EventHandler {
             }
         });
 
-        Self { rx, _tx: tx }
+        Self { rx, _tx: tx, tick_rate: rate_tx }
+    }
+
+    /// Change how often `Tick` is sent, from the next tick on
+    pub fn set_tick_rate(&self, tick_rate: Duration) {
+        self.tick_rate.send_if_modified(|rate| std::mem::replace(rate, tick_rate) != tick_rate);
     }
 
     pub async fn next(&mut self) -> Option<Event> {
@@ -0,0 +1,321 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Remotes tried first, in order. Other remotes are used after these.
+const PREFERRED_REMOTES: &[&str] = &["upstream", "origin"];
+
+/// `owner/repo` of the GitHub remote of the enclosing git repository. Reads the
+/// git config directly, so it works without git on PATH and with any remote name.
+/// With an Enterprise `api_base`, remotes on its host count as GitHub too.
+pub fn detect_repo(api_base: Option<&str>) -> Option<String> {
+    let enterprise_host = api_base.and_then(api_host);
+    let is_github_host = |host: &str| is_github_host(host, enterprise_host.as_deref());
+    let config = read_config()?;
+    let parsed: Vec<(String, String)> = config
+        .sorted_remotes()
+        .iter()
+        .filter_map(|(_, url)| parse_remote_url(&config.rewrite_url(url)))
+        .collect();
+
+    // GitHub itself, then SSH aliases pointing at it (`Host gh-work` in
+    // ~/.ssh/config), then any dotless host as a best-effort alias guess
+    parsed
+        .iter()
+        .find(|(host, _)| is_github_host(host))
+        .or_else(|| parsed.iter().find(|(host, _)| ssh_host_name(host).is_some_and(|name| is_github_host(&name))))
+        .or_else(|| parsed.iter().find(|(host, _)| !host.contains('.')))
+        .map(|(_, repo)| repo.clone())
+}
+
+/// Name of the remote pointing at `repo` (`owner/name`) in the enclosing git
+/// repository, preferring the usual names when several do
+pub fn remote_for(repo: &str) -> Option<String> {
+    let config = read_config()?;
+    config
+        .sorted_remotes()
+        .into_iter()
+        .find(|(_, url)| {
+            parse_remote_url(&config.rewrite_url(url)).is_some_and(|(_, found)| found.eq_ignore_ascii_case(repo))
+        })
+        .map(|(name, _)| name.clone())
+}
+
+fn read_config() -> Option<GitConfig> {
+    let cwd = std::env::current_dir().ok()?;
+    let (_, common_dir) = find_git_dirs(&cwd)?;
+    let text = fs::read_to_string(common_dir.join("config")).ok()?;
+    Some(GitConfig::parse(&text))
+}
+
+/// The branch checked out in the enclosing git repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalHead {
+    pub branch: String,
+    /// Commit the branch points at, if its ref could be read
+    pub sha: Option<String>,
+}
+
+/// The checked-out branch, read from `HEAD` like the config is, without running
+/// git. None outside a repository and on a detached HEAD.
+pub fn local_head() -> Option<LocalHead> {
+    let cwd = std::env::current_dir().ok()?;
+    let (git_dir, common_dir) = find_git_dirs(&cwd)?;
+    let head = fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    let full_ref = head.trim().strip_prefix("ref:")?.trim();
+    let branch = full_ref.strip_prefix("refs/heads/")?.to_string();
+
+    // A loose ref file, or a line of packed-refs: "<sha> refs/heads/<branch>"
+    let sha = fs::read_to_string(common_dir.join(full_ref))
+        .ok()
+        .map(|sha| sha.trim().to_string())
+        .or_else(|| {
+            let packed = fs::read_to_string(common_dir.join("packed-refs")).ok()?;
+            packed.lines().find_map(|line| {
+                let (sha, name) = line.split_once(' ')?;
+                (name == full_ref).then(|| sha.to_string())
+            })
+        });
+
+    Some(LocalHead { branch, sha })
+}
+
+/// Split a remote URL into host and `owner/repo`. Handles scp-style SSH
+/// (`git@github.com:owner/repo.git`) and URLs with a scheme
+/// (`https://github.com/owner/repo`, `ssh://git@github.com:22/owner/repo.git`).
+fn parse_remote_url(url: &str) -> Option<(String, String)> {
+    let url = url.trim();
+    let (authority, path) = match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/')?,
+        None => url.split_once(':')?,
+    };
+    // Drop the user and the port
+    let host = authority.rsplit('@').next()?.split(':').next()?;
+
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    let (owner, name) = path.split_once('/')?;
+    if host.is_empty() || owner.is_empty() || name.is_empty() || name.contains('/') {
+        return None;
+    }
+
+    Some((host.to_lowercase(), format!("{}/{}", owner, name)))
+}
+
+fn is_github_host(host: &str, enterprise_host: Option<&str>) -> bool {
+    // Enterprise Cloud serves the API from `api.<host>`
+    let enterprise = enterprise_host.is_some_and(|api| api == host || api.strip_prefix("api.") == Some(host));
+    host == "github.com" || host.ends_with(".github.com") || enterprise
+}
+
+/// Lowercase host of an API base URL (`https://ghe.example.com/api/v3` -> `ghe.example.com`)
+fn api_host(api_base: &str) -> Option<String> {
+    let rest = api_base.split_once("://").map_or(api_base, |(_, rest)| rest);
+    let host = rest.split('/').next()?.split(':').next()?;
+    (!host.is_empty()).then(|| host.to_lowercase())
+}
+
+/// Find the git dir and common dir of the repository containing `start`,
+/// walking up parent directories. Worktrees and submodules have a `.git` file
+/// pointing elsewhere; linked worktrees keep their own `HEAD` in the git dir
+/// and the shared config and refs in the common dir.
+fn find_git_dirs(start: &Path) -> Option<(PathBuf, PathBuf)> {
+    for dir in start.ancestors() {
+        let dot_git = dir.join(".git");
+        if dot_git.is_dir() {
+            return Some((dot_git.clone(), dot_git));
+        }
+        if dot_git.is_file() {
+            let contents = fs::read_to_string(&dot_git).ok()?;
+            let git_dir = dir.join(contents.trim().strip_prefix("gitdir:")?.trim());
+            let common_dir = fs::read_to_string(git_dir.join("commondir"))
+                .map(|common| git_dir.join(common.trim()))
+                .unwrap_or_else(|_| git_dir.clone());
+            return Some((git_dir, common_dir));
+        }
+    }
+    None
+}
+
+/// The parts of a git config needed to resolve remotes
+#[derive(Default)]
+struct GitConfig {
+    /// (remote name, url)
+    remotes: Vec<(String, String)>,
+    /// (insteadOf prefix, replacement) from `[url "..."]` sections
+    rewrites: Vec<(String, String)>,
+}
+
+impl GitConfig {
+    fn parse(text: &str) -> Self {
+        let mut config = GitConfig::default();
+        // (section, subsection) of the current `[section "subsection"]` header
+        let mut section: Option<(String, String)> = None;
+
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+
+            if let Some(header) = line.strip_prefix('[').and_then(|l| l.split(']').next()) {
+                let (name, sub) = header.split_once(' ').unwrap_or((header, ""));
+                section = Some((name.trim().to_lowercase(), unquote(sub.trim()).to_string()));
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let key = key.trim().to_lowercase();
+            let value = unquote(value.trim()).to_string();
+
+            match section.as_ref().map(|(name, sub)| (name.as_str(), sub)) {
+                Some(("remote", name)) if key == "url" => config.remotes.push((name.clone(), value)),
+                Some(("url", base)) if key == "insteadof" => config.rewrites.push((value, base.clone())),
+                _ => {}
+            }
+        }
+
+        config
+    }
+
+    /// Remotes with `PREFERRED_REMOTES` first
+    fn sorted_remotes(&self) -> Vec<&(String, String)> {
+        let mut remotes: Vec<&(String, String)> = self.remotes.iter().collect();
+        remotes.sort_by_key(|(name, _)| {
+            PREFERRED_REMOTES
+                .iter()
+                .position(|preferred| preferred == name)
+                .unwrap_or(PREFERRED_REMOTES.len())
+        });
+        remotes
+    }
+
+    /// Apply the longest matching `insteadOf` rewrite, as git does
+    fn rewrite_url(&self, url: &str) -> String {
+        self.rewrites
+            .iter()
+            .filter(|(prefix, _)| url.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(prefix, base)| format!("{}{}", base, &url[prefix.len()..]))
+            .unwrap_or_else(|| url.to_string())
+    }
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+/// `HostName` configured for an SSH alias in ~/.ssh/config
+fn ssh_host_name(alias: &str) -> Option<String> {
+    let text = fs::read_to_string(dirs::home_dir()?.join(".ssh").join("config")).ok()?;
+    let mut matching = false;
+
+    for line in text.lines() {
+        let line = line.trim();
+        let Some((key, value)) = line.split_once(|c: char| c.is_whitespace() || c == '=') else {
+            continue;
+        };
+        let value = value.trim_start_matches(|c: char| c.is_whitespace() || c == '=').trim();
+        match key.to_lowercase().as_str() {
+            "host" => matching = value.split_whitespace().any(|pattern| pattern == alias),
+            "match" => matching = false,
+            "hostname" if matching => return Some(value.to_lowercase()),
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{api_host, is_github_host, parse_remote_url, GitConfig};
+
+    fn remote(host: &str, repo: &str) -> Option<(String, String)> {
+        Some((host.to_string(), repo.to_string()))
+    }
+
+    #[test]
+    fn ssh_scp_style() {
+        assert_eq!(parse_remote_url("git@github.com:owner/repo.git"), remote("github.com", "owner/repo"));
+        assert_eq!(parse_remote_url("git@github.com:owner/repo"), remote("github.com", "owner/repo"));
+    }
+
+    #[test]
+    fn https_and_ssh_urls() {
+        assert_eq!(parse_remote_url("https://github.com/owner/repo.git"), remote("github.com", "owner/repo"));
+        assert_eq!(parse_remote_url("https://github.com/owner/repo/"), remote("github.com", "owner/repo"));
+        assert_eq!(parse_remote_url("https://user@GitHub.com/owner/repo"), remote("github.com", "owner/repo"));
+        assert_eq!(
+            parse_remote_url("ssh://git@ssh.github.com:443/owner/repo.git"),
+            remote("ssh.github.com", "owner/repo")
+        );
+    }
+
+    #[test]
+    fn host_alias() {
+        assert_eq!(parse_remote_url("git@gh-work:owner/repo.git"), remote("gh-work", "owner/repo"));
+    }
+
+    #[test]
+    fn dots_in_repo_name_are_kept() {
+        assert_eq!(parse_remote_url("git@github.com:owner/my.repo.git"), remote("github.com", "owner/my.repo"));
+    }
+
+    #[test]
+    fn malformed_urls() {
+        assert_eq!(parse_remote_url(""), None);
+        assert_eq!(parse_remote_url("/home/me/repo"), None);
+        assert_eq!(parse_remote_url("https://github.com/owner"), None);
+        assert_eq!(parse_remote_url("https://github.com/a/b/c"), None);
+        assert_eq!(parse_remote_url("git@github.com:"), None);
+    }
+
+    #[test]
+    fn enterprise_hosts() {
+        assert_eq!(api_host("https://ghe.example.com/api/v3"), Some("ghe.example.com".to_string()));
+        assert_eq!(api_host("https://GHE.example.com:8443/api/v3/"), Some("ghe.example.com".to_string()));
+        assert_eq!(api_host("https://"), None);
+
+        assert!(is_github_host("github.com", None));
+        assert!(is_github_host("ssh.github.com", None));
+        assert!(!is_github_host("ghe.example.com", None));
+        assert!(is_github_host("ghe.example.com", Some("ghe.example.com")));
+        assert!(is_github_host("corp.ghe.com", Some("api.corp.ghe.com")));
+        assert!(!is_github_host("gitlab.com", Some("ghe.example.com")));
+    }
+
+    #[test]
+    fn config_remotes_and_rewrites() {
+        let config = GitConfig::parse(
+            r#"
+[core]
+    bare = false
+[remote "origin"]
+    url = gh:me/fork.git
+    fetch = +refs/heads/*:refs/remotes/origin/*
+[remote "upstream"]
+    url = "https://github.com/org/project.git"
+[url "git@github.com:"]
+    insteadOf = gh:
+[url "git@github.com:org/"]
+    insteadOf = gh:org/
+"#,
+        );
+
+        assert_eq!(
+            config.remotes,
+            vec![
+                ("origin".to_string(), "gh:me/fork.git".to_string()),
+                ("upstream".to_string(), "https://github.com/org/project.git".to_string()),
+            ]
+        );
+        assert_eq!(config.rewrite_url("gh:me/fork.git"), "git@github.com:me/fork.git");
+        // The longest matching prefix wins
+        assert_eq!(config.rewrite_url("gh:org/x"), "git@github.com:org/x");
+        assert_eq!(config.rewrite_url("https://example.com/a/b"), "https://example.com/a/b");
+    }
+}
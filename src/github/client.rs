@@ -1,19 +1,37 @@
 use anyhow::{Context, Result};
 use octocrab::Octocrab;
-use reqwest::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE, USER_AGENT};
+use reqwest::header::{HeaderMap, ACCEPT, AUTHORIZATION, RETRY_AFTER, USER_AGENT};
+use reqwest::{Method, RequestBuilder, StatusCode};
+use serde::Serialize;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use tokio::sync::RwLock;
 
-use super::types::{Commit, Job, PullRequest, Review, WorkflowRun};
+use super::dispatch::{self, DispatchInput};
+use super::types::{format_duration, Artifact, Check, Commit, Job, Notification, PendingDeployment, PullRequest, RateLimit, Review, RunFilter, Workflow, WorkflowRun};
+use crate::config::MergeMethod;
 
-const API_BASE: &str = "https://api.github.com";
+/// Public GitHub API, used unless an Enterprise base URL is configured
+const DEFAULT_API_BASE: &str = "https://api.github.com";
+
+/// System keyring entry holding a token stored with `github-tui --login`
+const KEYRING_SERVICE: &str = "github-tui";
+const KEYRING_USER: &str = "token";
+
+/// PRs requested per page when listing
+const PR_PAGE_SIZE: u8 = 100;
+/// Pages fetched per PR listing (PR_PAGE_SIZE PRs each), unless `max_pr_pages` is set in the config
+pub const MAX_PR_PAGES: usize = 5;
+/// Workflow runs requested per page, unless `per_page` is set in the config
+pub const RUN_PAGE_SIZE: u8 = 30;
 
 /// In-memory cache for immutable data
 #[derive(Default)]
 struct Cache {
     /// Commit diffs by SHA - immutable, cache forever
     commit_diffs: HashMap<String, String>,
+    /// Full PR diffs by head SHA - a push moves the head, so no invalidation needed
+    pr_diffs: HashMap<String, String>,
     /// Completed job logs by job_id - immutable once completed
     job_logs: HashMap<u64, String>,
 }
@@ -23,20 +41,37 @@ pub struct Client {
     octocrab: Arc<Octocrab>,
     http: reqwest::Client,
     token: String,
+    api_base: String,
     cache: Arc<RwLock<Cache>>,
+    /// Quota reported by the last raw API response, shared by all clones
+    rate_limit: Arc<Mutex<Option<RateLimit>>>,
 }
 
 impl Client {
-    pub async fn new() -> Result<Self> {
-        // Try to get token from: env vars -> .env.local -> gh config
-        let token = std::env::var("GITHUB_TOKEN")
-            .or_else(|_| std::env::var("GH_TOKEN"))
+    /// Create a client for the public API, or for a GitHub Enterprise
+    /// instance when `api_base` is set (e.g. `https://ghe.example.com/api/v3`)
+    pub async fn new(api_base: Option<String>) -> Result<Self> {
+        // Try to get token from: env vars -> system keyring -> .env.local -> gh config
+        let env_token = std::env::var("GITHUB_TOKEN").or_else(|_| std::env::var("GH_TOKEN"));
+        let token = match env_token {
+            Ok(token) => Ok(token),
+            Err(_) => tokio::task::spawn_blocking(Self::get_keyring_token)
+                .await
+                .unwrap_or(Err(std::env::VarError::NotPresent)),
+        };
+        let token = token
             .or_else(|_| Self::get_token_from_env_file())
             .or_else(|_| Self::get_gh_config_token())
-            .context("No GitHub token found. Set GITHUB_TOKEN env var or login with `gh auth login`")?;
+            .context("No GitHub token found. Set GITHUB_TOKEN env var, run `github-tui --login`, or login with `gh auth login`")?;
+
+        let api_base = api_base
+            .map(|url| url.trim_end_matches('/').to_string())
+            .unwrap_or_else(|| DEFAULT_API_BASE.to_string());
 
         let octocrab = Octocrab::builder()
             .personal_token(token.clone())
+            .base_uri(api_base.as_str())
+            .context("Invalid GitHub API URL")?
             .build()
             .context("Failed to create GitHub client")?;
 
@@ -46,10 +81,132 @@ impl Client {
             octocrab: Arc::new(octocrab),
             http,
             token,
+            api_base,
             cache: Arc::new(RwLock::new(Cache::default())),
+            rate_limit: Arc::new(Mutex::new(None)),
         })
     }
 
+    /// Web root of the GitHub instance, for links to open or copy
+    pub fn web_base(&self) -> String {
+        web_base(&self.api_base)
+    }
+
+    /// Remaining API quota as of the last raw request, if GitHub reported one
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        *self.rate_limit.lock().unwrap()
+    }
+
+    /// Start a raw API request with auth and user-agent set. `path` is relative
+    /// to the API base, e.g. `/repos/owner/repo/pulls/1`.
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.request_url(method, format!("{}{}", self.api_base, path))
+    }
+
+    fn request_url(&self, method: Method, url: String) -> RequestBuilder {
+        self.http
+            .request(method, url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(USER_AGENT, "github-tui")
+    }
+
+    /// Run a GraphQL query or mutation and return its `data`. GitHub answers
+    /// failures such as missing token scopes with a 200 and an `errors` list,
+    /// so those become errors here too.
+    async fn graphql(&self, query: &str, variables: serde_json::Value, what: &str) -> Result<serde_json::Value> {
+        // https://api.github.com/graphql, or https://ghe.example.com/api/graphql
+        let url = format!("{}/graphql", self.api_base.strip_suffix("/v3").unwrap_or(&self.api_base));
+        let request = self
+            .request_url(Method::POST, url)
+            .json(&serde_json::json!({ "query": query, "variables": variables }));
+        let response = self.send(request, what).await?;
+
+        let status = response.status();
+        let mut body: serde_json::Value = response.json().await.context(what.to_string())?;
+        if let Some(errors) = body["errors"].as_array() {
+            let messages: Vec<&str> = errors.iter().filter_map(|e| e["message"].as_str()).collect();
+            return Err(anyhow::anyhow!("{}: {}", what, messages.join("; ")));
+        }
+        if !status.is_success() {
+            return Err(anyhow::anyhow!("{}: {} {}", what, status, body["message"].as_str().unwrap_or_default()));
+        }
+        Ok(body["data"].take())
+    }
+
+    fn get(&self, path: &str) -> RequestBuilder {
+        self.request(Method::GET, path)
+    }
+
+    fn post<T: Serialize + ?Sized>(&self, path: &str, body: &T) -> RequestBuilder {
+        self.request(Method::POST, path).json(body)
+    }
+
+    fn patch<T: Serialize + ?Sized>(&self, path: &str, body: &T) -> RequestBuilder {
+        self.request(Method::PATCH, path).json(body)
+    }
+
+    fn put<T: Serialize + ?Sized>(&self, path: &str, body: &T) -> RequestBuilder {
+        self.request(Method::PUT, path).json(body)
+    }
+
+    fn delete(&self, path: &str) -> RequestBuilder {
+        self.request(Method::DELETE, path)
+    }
+
+    /// Send a request built by the helpers above. Records the quota from the
+    /// rate-limit headers, and turns a rate-limited 403/429 into an error that
+    /// says when to retry instead of a generic failure.
+    async fn send(&self, request: RequestBuilder, what: &str) -> Result<reqwest::Response> {
+        let response = request
+            .send()
+            .await
+            .context(what.to_string())?;
+
+        // Redirected downloads (log archives) come back without the headers
+        let rate_limit = parse_rate_limit(response.headers());
+        if rate_limit.is_some() {
+            *self.rate_limit.lock().unwrap() = rate_limit;
+        }
+
+        let status = response.status();
+        if status != StatusCode::FORBIDDEN && status != StatusCode::TOO_MANY_REQUESTS {
+            return Ok(response);
+        }
+
+        let retry_after = response
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<i64>().ok());
+        let body = response.text().await.unwrap_or_default();
+        let message = serde_json::from_str::<serde_json::Value>(&body)
+            .ok()
+            .and_then(|v| v.get("message").and_then(|m| m.as_str()).map(|m| m.to_string()))
+            .unwrap_or(body);
+
+        match rate_limit_error(status, rate_limit, retry_after, &message) {
+            Some(error) => Err(anyhow::anyhow!(error)),
+            None => Err(anyhow::anyhow!("{}: {} {}", what, status, message)),
+        }
+    }
+
+    /// Token saved with `--login`. Like `store_keyring_token` this blocks on the
+    /// keyring's own D-Bus runtime, which can't nest inside ours, so both run
+    /// under `spawn_blocking`.
+    fn get_keyring_token() -> Result<String, std::env::VarError> {
+        // No keyring (or no entry) just moves on to the next source
+        keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+            .and_then(|entry| entry.get_password())
+            .map_err(|_| std::env::VarError::NotPresent)
+    }
+
+    /// Save a token in the system keyring, where `new` will find it
+    pub fn store_keyring_token(token: &str) -> Result<()> {
+        keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+            .and_then(|entry| entry.set_password(token))
+            .context("Failed to store the token in the system keyring")
+    }
+
     fn get_token_from_env_file() -> Result<String, std::env::VarError> {
         // Try .env.local first, then .env
         let paths = [".env.local", ".env"];
@@ -108,15 +265,10 @@ impl Client {
 
     /// Get the current authenticated user
     pub async fn get_current_user(&self) -> Result<String> {
-        let url = format!("{}/user", API_BASE);
+        let path = "/user";
 
-        let user: serde_json::Value = self.http
-            .get(&url)
-            .header(AUTHORIZATION, format!("Bearer {}", self.token))
-            .header(USER_AGENT, "github-tui")
-            .send()
-            .await
-            .context("Failed to fetch current user")?
+        let request = self.get(path);
+        let user: serde_json::Value = self.send(request, "Failed to fetch current user").await?
             .json()
             .await
             .context("Failed to parse user response")?;
@@ -127,98 +279,191 @@ impl Client {
             .ok_or_else(|| anyhow::anyhow!("No login field in user response"))
     }
 
-    pub async fn list_prs(&self, owner: &str, repo: &str) -> Result<Vec<PullRequest>> {
-        let page = self
+    /// Teams the current user belongs to, as lowercase `org/slug`
+    pub async fn get_user_teams(&self) -> Result<Vec<String>> {
+        let path = "/user/teams?per_page=100";
+
+        let request = self.get(path);
+        let teams: Vec<serde_json::Value> = self.send(request, "Failed to fetch teams").await?
+            .json()
+            .await
+            .context("Failed to parse teams response")?;
+
+        Ok(teams
+            .iter()
+            .filter_map(|team| {
+                let org = team.get("organization")?.get("login")?.as_str()?;
+                let slug = team.get("slug")?.as_str()?;
+                Some(format!("{}/{}", org, slug).to_lowercase())
+            })
+            .collect())
+    }
+
+    /// List PRs, following pages up to `max_pages`. A failure past the first
+    /// page keeps what was fetched and comes back as a warning instead.
+    pub async fn list_prs(
+        &self,
+        owner: &str,
+        repo: &str,
+        state: octocrab::params::State,
+        max_pages: usize,
+    ) -> Result<(Vec<PullRequest>, Option<String>)> {
+        let mut page = self
             .octocrab
             .pulls(owner, repo)
             .list()
-            .state(octocrab::params::State::Open)
-            .per_page(50)
+            .state(state)
+            .per_page(PR_PAGE_SIZE)
             .send()
             .await
             .context("Failed to fetch PRs")?;
 
-        let prs: Vec<PullRequest> = page
-            .items
-            .into_iter()
-            .map(|pr| PullRequest {
-                number: pr.number,
-                title: pr.title.unwrap_or_default(),
-                body: pr.body.filter(|b| !b.is_empty()),
-                state: pr.state.map(|s| format!("{:?}", s).to_lowercase()).unwrap_or_default(),
-                user: super::types::User {
-                    login: pr.user.map(|u| u.login).unwrap_or_default(),
-                    avatar_url: String::new(),
-                },
-                head: super::types::Branch {
-                    ref_name: pr.head.ref_field,
-                    sha: pr.head.sha,
-                },
-                base: super::types::Branch {
-                    ref_name: pr.base.ref_field,
-                    sha: pr.base.sha,
-                },
-                draft: pr.draft.unwrap_or(false),
-                mergeable: pr.mergeable,
-                merged: pr.merged_at.is_some(),
-                created_at: pr.created_at.map(|t| t.to_string()).unwrap_or_default(),
-                updated_at: pr.updated_at.map(|t| t.to_string()).unwrap_or_default(),
-                labels: pr
-                    .labels
-                    .unwrap_or_default()
-                    .into_iter()
-                    .map(|l| super::types::Label {
-                        name: l.name,
-                        color: l.color,
-                    })
-                    .collect(),
-                requested_reviewers: pr
-                    .requested_reviewers
-                    .unwrap_or_default()
-                    .into_iter()
-                    .map(|u| super::types::User {
-                        login: u.login,
-                        avatar_url: String::new(),
-                    })
-                    .collect(),
-                ci_status: None,
-            })
-            .collect();
+        let mut prs: Vec<PullRequest> = Vec::new();
+        let mut pages_fetched = 1;
 
-        Ok(prs)
-    }
+        loop {
+            prs.extend(page.items.drain(..).map(Self::convert_pr));
 
-    pub async fn get_pr_diff(&self, owner: &str, repo: &str, number: u64) -> Result<String> {
-        let url = format!("{}/repos/{}/{}/pulls/{}", API_BASE, owner, repo, number);
+            // Follow the Link header until exhausted or the page cap is reached
+            if pages_fetched >= max_pages {
+                break;
+            }
+            match self.octocrab.get_page(&page.next).await {
+                Ok(Some(next)) => {
+                    page = next;
+                    pages_fetched += 1;
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    let warning = format!("Showing the first {} PRs, fetching more failed: {}", prs.len(), e);
+                    return Ok((prs, Some(warning)));
+                }
+            }
+        }
 
-        let response = self.http
-            .get(&url)
-            .header(AUTHORIZATION, format!("Bearer {}", self.token))
-            .header(ACCEPT, "application/vnd.github.diff")
-            .header(USER_AGENT, "github-tui")
-            .send()
+        Ok((prs, None))
+    }
+
+    /// Fetch a single PR. Unlike the list endpoint this asks GitHub to compute
+    /// `mergeable`, which stays null until that background job finishes.
+    pub async fn get_pr(&self, owner: &str, repo: &str, number: u64) -> Result<PullRequest> {
+        let pr = self
+            .octocrab
+            .pulls(owner, repo)
+            .get(number)
             .await
-            .context("Failed to fetch PR diff")?;
+            .context("Failed to fetch PR")?;
+
+        Ok(Self::convert_pr(pr))
+    }
+
+    fn convert_pr(pr: octocrab::models::pulls::PullRequest) -> PullRequest {
+        PullRequest {
+            number: pr.number,
+            title: pr.title.unwrap_or_default(),
+            body: pr.body.filter(|b| !b.is_empty()),
+            state: pr.state.map(|s| format!("{:?}", s).to_lowercase()).unwrap_or_default(),
+            user: super::types::User {
+                login: pr.user.map(|u| u.login).unwrap_or_default(),
+                avatar_url: String::new(),
+            },
+            head: super::types::Branch {
+                owner: pr.head.repo.as_ref().and_then(|r| r.owner.as_ref()).map(|o| o.login.clone()),
+                ref_name: pr.head.ref_field,
+                sha: pr.head.sha,
+            },
+            base: super::types::Branch {
+                owner: pr.base.repo.as_ref().and_then(|r| r.owner.as_ref()).map(|o| o.login.clone()),
+                ref_name: pr.base.ref_field,
+                sha: pr.base.sha,
+            },
+            draft: pr.draft.unwrap_or(false),
+            mergeable: pr.mergeable,
+            merged: pr.merged_at.is_some(),
+            created_at: pr.created_at.map(|t| t.to_string()).unwrap_or_default(),
+            updated_at: pr.updated_at.map(|t| t.to_string()).unwrap_or_default(),
+            labels: pr
+                .labels
+                .unwrap_or_default()
+                .into_iter()
+                .map(|l| super::types::Label {
+                    name: l.name,
+                    color: l.color,
+                })
+                .collect(),
+            requested_reviewers: pr
+                .requested_reviewers
+                .unwrap_or_default()
+                .into_iter()
+                .map(|u| super::types::User {
+                    login: u.login,
+                    avatar_url: String::new(),
+                })
+                .collect(),
+            requested_teams: pr
+                .requested_teams
+                .unwrap_or_default()
+                .into_iter()
+                .map(|t| t.slug)
+                .collect(),
+            assignees: pr
+                .assignees
+                .unwrap_or_default()
+                .into_iter()
+                .map(|u| super::types::User {
+                    login: u.login,
+                    avatar_url: String::new(),
+                })
+                .collect(),
+            ci_status: None,
+        }
+    }
 
-        if !response.status().is_success() {
-            return Err(anyhow::anyhow!("Failed to fetch PR diff: {}", response.status()));
+    /// Full diff of a PR at `head_sha`. A `refresh` skips the cached copy and
+    /// replaces it.
+    pub async fn get_pr_diff(&self, owner: &str, repo: &str, number: u64, head_sha: &str, refresh: bool) -> Result<String> {
+        if !refresh {
+            let cache = self.cache.read().await;
+            if let Some(diff) = cache.pr_diffs.get(head_sha) {
+                return Ok(diff.clone());
+            }
+        }
+
+        let path = format!("/repos/{}/{}/pulls/{}", owner, repo, number);
+        let diff = self.fetch_diff(&path, "Failed to fetch PR diff").await?;
+
+        {
+            let mut cache = self.cache.write().await;
+            cache.pr_diffs.insert(head_sha.to_string(), diff.clone());
         }
 
-        response.text().await.context("Failed to read diff response")
+        Ok(diff)
     }
 
-    pub async fn approve_pr(&self, owner: &str, repo: &str, number: u64) -> Result<()> {
-        let url = format!("{}/repos/{}/{}/pulls/{}/reviews", API_BASE, owner, repo, number);
+    /// Fetch a PR or commit as a unified diff, shared by both diff views
+    async fn fetch_diff(&self, path: &str, what: &str) -> Result<String> {
+        let request = self.get(path).header(ACCEPT, "application/vnd.github.diff");
+        let response = self.send(request, what).await?;
+
+        match response.status() {
+            status if status.is_success() => response.text().await.context("Failed to read diff response"),
+            StatusCode::NOT_FOUND => Err(anyhow::anyhow!("{}: not found", what)),
+            // GitHub refuses diffs above its size limits with 406
+            StatusCode::NOT_ACCEPTABLE => Err(anyhow::anyhow!("{}: diff is too large", what)),
+            status => Err(anyhow::anyhow!("{}: {}", what, status)),
+        }
+    }
 
-        let response = self.http
-            .post(&url)
-            .header(AUTHORIZATION, format!("Bearer {}", self.token))
-            .header(USER_AGENT, "github-tui")
-            .header(CONTENT_TYPE, "application/json")
-            .json(&serde_json::json!({ "event": "APPROVE" }))
-            .send()
-            .await
-            .context("Failed to approve PR")?;
+    /// Submit an approving review, with `body` as its comment if given
+    pub async fn approve_pr(&self, owner: &str, repo: &str, number: u64, body: Option<&str>) -> Result<()> {
+        let path = format!("/repos/{}/{}/pulls/{}/reviews", owner, repo, number);
+
+        let mut review = serde_json::json!({ "event": "APPROVE" });
+        if let Some(body) = body {
+            review["body"] = body.into();
+        }
+        let request = self.post(&path, &review);
+        let response = self.send(request, "Failed to approve PR").await?;
 
         if response.status().is_success() {
             Ok(())
@@ -227,39 +472,136 @@ impl Client {
         }
     }
 
-    pub async fn merge_pr(&self, owner: &str, repo: &str, number: u64) -> Result<()> {
-        let url = format!("{}/repos/{}/{}/pulls/{}/merge", API_BASE, owner, repo, number);
+    pub async fn merge_pr(&self, owner: &str, repo: &str, number: u64, method: MergeMethod) -> Result<()> {
+        let path = format!("/repos/{}/{}/pulls/{}/merge", owner, repo, number);
 
-        let response = self.http
-            .put(&url)
-            .header(AUTHORIZATION, format!("Bearer {}", self.token))
-            .header(USER_AGENT, "github-tui")
-            .header(CONTENT_TYPE, "application/json")
-            .json(&serde_json::json!({ "merge_method": "squash" }))
-            .send()
-            .await
-            .context("Failed to merge PR")?;
+        let request = self.put(&path, &serde_json::json!({ "merge_method": method.as_str() }));
+        let response = self.send(request, "Failed to merge PR").await?;
 
         if response.status().is_success() {
             Ok(())
+        } else {
+            // 405 and 409 explain why in the message, e.g. "Required status check is failing"
+            let status = response.status();
+            let message = response.json::<serde_json::Value>().await.ok()
+                .and_then(|body| body["message"].as_str().map(|m| m.to_string()))
+                .unwrap_or_default();
+            Err(anyhow::anyhow!("Failed to merge PR: {} {}", status, message))
+        }
+    }
+
+    /// Merge the base branch into the PR's head, like the "Update branch" button.
+    /// Returns false when the head already has everything from the base.
+    pub async fn update_pr_branch(&self, owner: &str, repo: &str, number: u64) -> Result<bool> {
+        let path = format!("/repos/{}/{}/pulls/{}/update-branch", owner, repo, number);
+
+        let request = self.put(&path, &serde_json::json!({}));
+        let response = self.send(request, "Failed to update PR branch").await?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(true);
+        }
+        let message = response.json::<serde_json::Value>().await.ok()
+            .and_then(|body| body["message"].as_str().map(|m| m.to_string()))
+            .unwrap_or_default();
+        // A 422 is also how conflicts with the base are reported
+        if status == StatusCode::UNPROCESSABLE_ENTITY && message.contains("no new commits") {
+            return Ok(false);
+        }
+        Err(anyhow::anyhow!("Failed to update branch: {} {}", status, message))
+    }
+
+    /// The PR's GraphQL node id, and the merge method of its auto-merge if enabled
+    async fn auto_merge_request(&self, owner: &str, repo: &str, number: u64) -> Result<(String, Option<String>)> {
+        const QUERY: &str = "query($owner: String!, $repo: String!, $number: Int!) {
+            repository(owner: $owner, name: $repo) {
+                pullRequest(number: $number) { id autoMergeRequest { mergeMethod } }
+            }
+        }";
+
+        let variables = serde_json::json!({ "owner": owner, "repo": repo, "number": number });
+        let data = self.graphql(QUERY, variables, "Failed to fetch auto-merge state").await?;
+        let pr = &data["repository"]["pullRequest"];
+        let id = pr["id"].as_str().context("PR not found")?.to_string();
+        let method = pr["autoMergeRequest"]["mergeMethod"].as_str().map(|m| m.to_lowercase());
+        Ok((id, method))
+    }
+
+    /// Approvals branch protection requires before merging into `branch`; `None` if
+    /// the branch isn't protected or doesn't require reviews. Reading protection
+    /// needs admin access to the repo.
+    pub async fn get_branch_protection(&self, owner: &str, repo: &str, branch: &str) -> Result<Option<u32>> {
+        let path = format!("/repos/{}/{}/branches/{}/protection/required_pull_request_reviews", owner, repo, branch);
+        let request = self.get(&path);
+        let response = self.send(request, "Failed to fetch branch protection").await?;
+
+        match response.status() {
+            status if status.is_success() => {
+                let reviews: serde_json::Value = response.json().await
+                    .context("Failed to parse branch protection response")?;
+                Ok(reviews["required_approving_review_count"].as_u64().map(|n| n as u32))
+            }
+            StatusCode::NOT_FOUND => Ok(None),
+            status => Err(anyhow::anyhow!("Failed to fetch branch protection: {}", status)),
+        }
+    }
+
+    /// Merge method of the PR's pending auto-merge, if one is enabled
+    pub async fn auto_merge_method(&self, owner: &str, repo: &str, number: u64) -> Result<Option<String>> {
+        Ok(self.auto_merge_request(owner, repo, number).await?.1)
+    }
+
+    /// Merge the PR with `method` once its requirements (checks, reviews) are met
+    pub async fn enable_auto_merge(&self, owner: &str, repo: &str, number: u64, method: MergeMethod) -> Result<()> {
+        const MUTATION: &str = "mutation($id: ID!, $method: PullRequestMergeMethod!) {
+            enablePullRequestAutoMerge(input: { pullRequestId: $id, mergeMethod: $method }) { clientMutationId }
+        }";
+
+        let (id, _) = self.auto_merge_request(owner, repo, number).await?;
+        let variables = serde_json::json!({ "id": id, "method": method.as_str().to_uppercase() });
+        match self.graphql(MUTATION, variables, "Failed to enable auto-merge").await {
+            Err(e) if e.to_string().contains("not allowed") => {
+                Err(anyhow::anyhow!("{} (turn on \"Allow auto-merge\" in the repository settings)", e))
+            }
+            result => result.map(|_| ()),
+        }
+    }
+
+    pub async fn disable_auto_merge(&self, owner: &str, repo: &str, number: u64) -> Result<()> {
+        const MUTATION: &str = "mutation($id: ID!) {
+            disablePullRequestAutoMerge(input: { pullRequestId: $id }) { clientMutationId }
+        }";
+
+        let (id, _) = self.auto_merge_request(owner, repo, number).await?;
+        self.graphql(MUTATION, serde_json::json!({ "id": id }), "Failed to disable auto-merge").await?;
+        Ok(())
+    }
+
+    /// Delete a branch. Returns false if it was already gone, e.g. removed by
+    /// the repository's "Automatically delete head branches" setting.
+    pub async fn delete_branch(&self, owner: &str, repo: &str, branch: &str) -> Result<bool> {
+        let path = format!("/repos/{}/{}/git/refs/heads/{}", owner, repo, branch);
+
+        let request = self.delete(&path);
+        let response = self.send(request, "Failed to delete branch").await?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(true)
+        } else if status == StatusCode::NOT_FOUND || status == StatusCode::UNPROCESSABLE_ENTITY {
+            Ok(false)
         } else {
             let body = response.text().await.unwrap_or_default();
-            Err(anyhow::anyhow!("Failed to merge PR: {}", body))
+            Err(anyhow::anyhow!("Failed to delete branch: {} {}", status, body))
         }
     }
 
     pub async fn edit_pr_title(&self, owner: &str, repo: &str, number: u64, title: &str) -> Result<()> {
-        let url = format!("{}/repos/{}/{}/pulls/{}", API_BASE, owner, repo, number);
+        let path = format!("/repos/{}/{}/pulls/{}", owner, repo, number);
 
-        let response = self.http
-            .patch(&url)
-            .header(AUTHORIZATION, format!("Bearer {}", self.token))
-            .header(USER_AGENT, "github-tui")
-            .header(CONTENT_TYPE, "application/json")
-            .json(&serde_json::json!({ "title": title }))
-            .send()
-            .await
-            .context("Failed to edit PR title")?;
+        let request = self.patch(&path, &serde_json::json!({ "title": title }));
+        let response = self.send(request, "Failed to edit PR title").await?;
 
         if response.status().is_success() {
             Ok(())
@@ -269,17 +611,10 @@ impl Client {
     }
 
     pub async fn edit_pr_body(&self, owner: &str, repo: &str, number: u64, body: &str) -> Result<()> {
-        let url = format!("{}/repos/{}/{}/pulls/{}", API_BASE, owner, repo, number);
+        let path = format!("/repos/{}/{}/pulls/{}", owner, repo, number);
 
-        let response = self.http
-            .patch(&url)
-            .header(AUTHORIZATION, format!("Bearer {}", self.token))
-            .header(USER_AGENT, "github-tui")
-            .header(CONTENT_TYPE, "application/json")
-            .json(&serde_json::json!({ "body": body }))
-            .send()
-            .await
-            .context("Failed to edit PR description")?;
+        let request = self.patch(&path, &serde_json::json!({ "body": body }));
+        let response = self.send(request, "Failed to edit PR description").await?;
 
         if response.status().is_success() {
             Ok(())
@@ -288,23 +623,38 @@ impl Client {
         }
     }
 
+    pub async fn close_pr(&self, owner: &str, repo: &str, number: u64) -> Result<()> {
+        self.set_pr_state(owner, repo, number, "closed").await
+    }
+
+    pub async fn reopen_pr(&self, owner: &str, repo: &str, number: u64) -> Result<()> {
+        self.set_pr_state(owner, repo, number, "open").await
+    }
+
+    async fn set_pr_state(&self, owner: &str, repo: &str, number: u64, state: &str) -> Result<()> {
+        let path = format!("/repos/{}/{}/pulls/{}", owner, repo, number);
+
+        let request = self.patch(&path, &serde_json::json!({ "state": state }));
+        let response = self.send(request, "Failed to update PR state").await?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            let body = response.text().await.unwrap_or_default();
+            Err(anyhow::anyhow!("Failed to update PR state: {}", body))
+        }
+    }
+
     pub async fn add_pr_labels(&self, owner: &str, repo: &str, number: u64, labels: &[&str]) -> Result<()> {
         if labels.is_empty() {
             return Ok(());
         }
 
         // PRs share issue numbers, so use issues endpoint for labels
-        let url = format!("{}/repos/{}/{}/issues/{}/labels", API_BASE, owner, repo, number);
+        let path = format!("/repos/{}/{}/issues/{}/labels", owner, repo, number);
 
-        let response = self.http
-            .post(&url)
-            .header(AUTHORIZATION, format!("Bearer {}", self.token))
-            .header(USER_AGENT, "github-tui")
-            .header(CONTENT_TYPE, "application/json")
-            .json(&serde_json::json!({ "labels": labels }))
-            .send()
-            .await
-            .context("Failed to add labels")?;
+        let request = self.post(&path, &serde_json::json!({ "labels": labels }));
+        let response = self.send(request, "Failed to add labels").await?;
 
         if response.status().is_success() {
             Ok(())
@@ -313,22 +663,32 @@ impl Client {
         }
     }
 
+    pub async fn remove_pr_label(&self, owner: &str, repo: &str, number: u64, label: &str) -> Result<()> {
+        // Percent-encode the name so spaces and slashes stay within one path segment
+        let path = format!(
+            "/repos/{}/{}/issues/{}/labels/{}",
+            owner, repo, number, encode_path_segment(label)
+        );
+
+        let request = self.delete(&path);
+        let response = self.send(request, "Failed to remove label").await?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("Failed to remove label: {}", response.status()))
+        }
+    }
+
     pub async fn add_pr_reviewers(&self, owner: &str, repo: &str, number: u64, reviewers: &[&str]) -> Result<()> {
         if reviewers.is_empty() {
             return Ok(());
         }
 
-        let url = format!("{}/repos/{}/{}/pulls/{}/requested_reviewers", API_BASE, owner, repo, number);
+        let path = format!("/repos/{}/{}/pulls/{}/requested_reviewers", owner, repo, number);
 
-        let response = self.http
-            .post(&url)
-            .header(AUTHORIZATION, format!("Bearer {}", self.token))
-            .header(USER_AGENT, "github-tui")
-            .header(CONTENT_TYPE, "application/json")
-            .json(&serde_json::json!({ "reviewers": reviewers }))
-            .send()
-            .await
-            .context("Failed to add reviewers")?;
+        let request = self.post(&path, &serde_json::json!({ "reviewers": reviewers }));
+        let response = self.send(request, "Failed to add reviewers").await?;
 
         if response.status().is_success() {
             Ok(())
@@ -337,12 +697,83 @@ impl Client {
         }
     }
 
-    pub async fn list_runs(&self, owner: &str, repo: &str) -> Result<Vec<WorkflowRun>> {
-        let runs = self
-            .octocrab
-            .workflows(owner, repo)
-            .list_all_runs()
-            .per_page(30)
+    /// Ask a reviewer who has already reviewed to review again
+    pub async fn request_review_again(&self, owner: &str, repo: &str, number: u64, reviewer: &str) -> Result<()> {
+        // Requesting an earlier reviewer is how GitHub's "Re-request review" works
+        self.add_pr_reviewers(owner, repo, number, &[reviewer]).await
+    }
+
+    /// Dismiss an approval or change request, with `message` saying why
+    pub async fn dismiss_review(&self, owner: &str, repo: &str, number: u64, review_id: u64, message: &str) -> Result<()> {
+        let path = format!("/repos/{}/{}/pulls/{}/reviews/{}/dismissals", owner, repo, number, review_id);
+
+        let request = self.put(&path, &serde_json::json!({ "message": message, "event": "DISMISS" }));
+        let response = self.send(request, "Failed to dismiss review").await?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            let status = response.status();
+            let message = response.json::<serde_json::Value>().await.ok()
+                .and_then(|body| body["message"].as_str().map(|m| m.to_string()))
+                .unwrap_or_default();
+            Err(anyhow::anyhow!("Failed to dismiss review: {} {}", status, message))
+        }
+    }
+
+    pub async fn add_assignees(&self, owner: &str, repo: &str, number: u64, assignees: &[&str]) -> Result<()> {
+        if assignees.is_empty() {
+            return Ok(());
+        }
+
+        // PRs share issue numbers, so use issues endpoint for assignees
+        let path = format!("/repos/{}/{}/issues/{}/assignees", owner, repo, number);
+
+        let request = self.post(&path, &serde_json::json!({ "assignees": assignees }));
+        let response = self.send(request, "Failed to add assignees").await?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("Failed to add assignees: {}", response.status()))
+        }
+    }
+
+    pub async fn remove_assignees(&self, owner: &str, repo: &str, number: u64, assignees: &[&str]) -> Result<()> {
+        if assignees.is_empty() {
+            return Ok(());
+        }
+
+        let path = format!("/repos/{}/{}/issues/{}/assignees", owner, repo, number);
+
+        let request = self.delete(&path).json(&serde_json::json!({ "assignees": assignees }));
+        let response = self.send(request, "Failed to remove assignees").await?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("Failed to remove assignees: {}", response.status()))
+        }
+    }
+
+    pub async fn list_runs(&self, owner: &str, repo: &str, per_page: u8, filter: &RunFilter) -> Result<Vec<WorkflowRun>> {
+        self.list_runs_paged(owner, repo, 1, per_page, filter).await
+    }
+
+    /// Fetch one page (1-based) of workflow runs matching `filter`, `per_page` runs per page
+    pub async fn list_runs_paged(&self, owner: &str, repo: &str, page: u32, per_page: u8, filter: &RunFilter) -> Result<Vec<WorkflowRun>> {
+        let workflows = self.octocrab.workflows(owner, repo);
+        let mut request = workflows.list_all_runs().per_page(per_page).page(page);
+        if let Some(branch) = &filter.branch {
+            request = request.branch(branch);
+        }
+        if let Some(status) = filter.status_param() {
+            request = request.status(status);
+        }
+        if let Some(event) = filter.event {
+            request = request.event(event);
+        }
+        let runs = request
             .send()
             .await
             .context("Failed to fetch workflow runs")?;
@@ -351,18 +782,13 @@ impl Client {
     }
 
     pub async fn list_runs_for_commit(&self, owner: &str, repo: &str, sha: &str) -> Result<Vec<WorkflowRun>> {
-        let url = format!(
-            "{}/repos/{}/{}/actions/runs?head_sha={}&per_page=20",
-            API_BASE, owner, repo, sha
+        let path = format!(
+            "/repos/{}/{}/actions/runs?head_sha={}&per_page=20",
+            owner, repo, sha
         );
 
-        let response: WorkflowRunsResponse = self.http
-            .get(&url)
-            .header(AUTHORIZATION, format!("Bearer {}", self.token))
-            .header(USER_AGENT, "github-tui")
-            .send()
-            .await
-            .context("Failed to fetch runs for commit")?
+        let request = self.get(&path);
+        let response: WorkflowRunsResponse = self.send(request, "Failed to fetch runs for commit").await?
             .json()
             .await
             .context("Failed to parse runs response")?;
@@ -382,6 +808,78 @@ impl Client {
         }).collect())
     }
 
+    /// Every check reported for a commit: Actions runs, then check runs of other
+    /// apps and commit statuses (Jenkins, CircleCI, ...). The last two are
+    /// best-effort, as not every token may read them.
+    pub async fn list_commit_checks(&self, owner: &str, repo: &str, sha: &str) -> Result<Vec<Check>> {
+        let (runs, check_runs, statuses) = tokio::join!(
+            self.list_runs_for_commit(owner, repo, sha),
+            self.list_check_runs(owner, repo, sha),
+            self.list_commit_statuses(owner, repo, sha),
+        );
+
+        let mut checks: Vec<Check> = runs?.into_iter().map(Check::from_run).collect();
+        // Actions report a check run per job; their runs are listed above
+        checks.extend(check_runs.unwrap_or_default().into_iter().filter(|c| c.source != "Actions"));
+        checks.extend(statuses.unwrap_or_default());
+        Ok(checks)
+    }
+
+    /// Check runs of every app for a commit, Actions jobs included. A single
+    /// request, enough for the CI status of commits that aren't looked at closely.
+    pub async fn list_check_runs(&self, owner: &str, repo: &str, sha: &str) -> Result<Vec<Check>> {
+        let path = format!("/repos/{}/{}/commits/{}/check-runs?per_page=100", owner, repo, sha);
+
+        let request = self.get(&path);
+        let response: CheckRunsResponse = self.send(request, "Failed to fetch check runs").await?
+            .json()
+            .await
+            .context("Failed to parse check runs response")?;
+
+        Ok(response.check_runs.into_iter()
+            .map(|c| Check {
+                name: c.name,
+                status: c.status,
+                conclusion: c.conclusion,
+                html_url: c.details_url.filter(|url| !url.is_empty()).unwrap_or(c.html_url),
+                source: match c.app {
+                    Some(app) if app.slug == "github-actions" => "Actions".to_string(),
+                    Some(app) => app.name,
+                    None => "checks".to_string(),
+                },
+                run: None,
+            })
+            .collect())
+    }
+
+    /// Commit statuses, the older API many external CI systems report through.
+    /// GitHub combines them to the latest status per context.
+    pub async fn list_commit_statuses(&self, owner: &str, repo: &str, sha: &str) -> Result<Vec<Check>> {
+        let path = format!("/repos/{}/{}/commits/{}/status", owner, repo, sha);
+
+        let request = self.get(&path);
+        let response: CombinedStatusResponse = self.send(request, "Failed to fetch commit statuses").await?
+            .json()
+            .await
+            .context("Failed to parse commit status response")?;
+
+        Ok(response.statuses.into_iter().map(|s| {
+            let conclusion = match s.state.as_str() {
+                "success" => Some("success"),
+                "failure" | "error" => Some("failure"),
+                _ => None,
+            };
+            Check {
+                name: s.context,
+                status: if conclusion.is_some() { "completed" } else { "in_progress" }.to_string(),
+                conclusion: conclusion.map(str::to_string),
+                html_url: s.target_url.unwrap_or_default(),
+                source: "status".to_string(),
+                run: None,
+            }
+        }).collect())
+    }
+
     fn convert_run(run: octocrab::models::workflows::Run) -> WorkflowRun {
         WorkflowRun {
             id: run.id.into_inner(),
@@ -398,6 +896,76 @@ impl Client {
         }
     }
 
+    /// Active workflows of the repository
+    pub async fn list_workflows(&self, owner: &str, repo: &str) -> Result<Vec<Workflow>> {
+        let path = format!("/repos/{}/{}/actions/workflows?per_page=100", owner, repo);
+
+        let request = self.get(&path);
+        let response: WorkflowsResponse = self.send(request, "Failed to fetch workflows").await?
+            .json()
+            .await
+            .context("Failed to parse workflows response")?;
+
+        Ok(response.workflows.into_iter()
+            .filter(|w| w.state == "active")
+            .map(|w| Workflow { id: w.id, name: w.name, path: w.path })
+            .collect())
+    }
+
+    pub async fn get_default_branch(&self, owner: &str, repo: &str) -> Result<String> {
+        let repository = self.octocrab.repos(owner, repo).get().await
+            .context("Failed to fetch repository")?;
+        Ok(repository.default_branch.unwrap_or_else(|| "main".to_string()))
+    }
+
+    /// The `workflow_dispatch` inputs of a workflow file at `git_ref`, or None
+    /// if it has no such trigger
+    pub async fn get_dispatch_inputs(&self, owner: &str, repo: &str, path: &str, git_ref: &str) -> Result<Option<Vec<DispatchInput>>> {
+        let path = format!(
+            "/repos/{}/{}/contents/{}?ref={}",
+            owner, repo, path, encode_path_segment(git_ref)
+        );
+
+        let request = self.get(&path).header(ACCEPT, "application/vnd.github.raw+json");
+        let response = self.send(request, "Failed to fetch workflow file").await?;
+
+        match response.status() {
+            status if status.is_success() => {
+                let yaml = response.text().await.context("Failed to read workflow file")?;
+                Ok(dispatch::parse_inputs(&yaml))
+            }
+            StatusCode::NOT_FOUND => Err(anyhow::anyhow!("Workflow file not found on '{}'", git_ref)),
+            status => Err(anyhow::anyhow!("Failed to fetch workflow file: {}", status)),
+        }
+    }
+
+    /// Start a `workflow_dispatch` run of a workflow on `git_ref`
+    pub async fn dispatch_workflow(
+        &self,
+        owner: &str,
+        repo: &str,
+        workflow_id: u64,
+        git_ref: &str,
+        inputs: &HashMap<String, String>,
+    ) -> Result<()> {
+        let path = format!("/repos/{}/{}/actions/workflows/{}/dispatches", owner, repo, workflow_id);
+
+        let body = serde_json::json!({ "ref": git_ref, "inputs": inputs });
+        let request = self.post(&path, &body);
+        let response = self.send(request, "Failed to dispatch workflow").await?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            // GitHub explains rejected refs and inputs in the body
+            let status = response.status();
+            let message = response.json::<serde_json::Value>().await.ok()
+                .and_then(|body| body["message"].as_str().map(|m| m.to_string()))
+                .unwrap_or_default();
+            Err(anyhow::anyhow!("Failed to dispatch workflow: {} {}", status, message))
+        }
+    }
+
     pub async fn list_jobs(&self, owner: &str, repo: &str, run_id: u64) -> Result<Vec<Job>> {
         let jobs = self
             .octocrab
@@ -415,17 +983,18 @@ impl Client {
                 id: job.id.into_inner(),
                 run_id: job.run_id.into_inner(),
                 name: job.name,
-                status: format!("{:?}", job.status).to_lowercase(),
-                conclusion: job.conclusion.map(|c| format!("{:?}", c).to_lowercase()),
+                status: serde_name(&job.status),
+                conclusion: job.conclusion.as_ref().map(serde_name),
                 started_at: job.started_at.to_string(),
                 completed_at: job.completed_at.map(|t| t.to_string()),
+                html_url: job.html_url.to_string(),
                 steps: job
                     .steps
                     .into_iter()
                     .map(|s| super::types::Step {
                         name: s.name,
-                        status: format!("{:?}", s.status).to_lowercase(),
-                        conclusion: s.conclusion.map(|c| format!("{:?}", c).to_lowercase()),
+                        status: serde_name(&s.status),
+                        conclusion: s.conclusion.as_ref().map(serde_name),
                         number: s.number as u64,
                     })
                     .collect(),
@@ -435,15 +1004,20 @@ impl Client {
         Ok(job_list)
     }
 
+    /// Fetch logs for a job (or a whole run). Pass `cacheable` only for completed
+    /// jobs - logs of running jobs are partial and must not be cached. A `refresh`
+    /// skips the cached copy and replaces it.
     pub async fn get_run_logs(
         &self,
         owner: &str,
         repo: &str,
         run_id: u64,
         job_id: Option<u64>,
+        cacheable: bool,
+        refresh: bool,
     ) -> Result<String> {
         // Check cache first for job logs (completed jobs are immutable)
-        if let Some(jid) = job_id {
+        if let Some(jid) = job_id.filter(|_| cacheable && !refresh) {
             let cache = self.cache.read().await;
             if let Some(logs) = cache.job_logs.get(&jid) {
                 return Ok(logs.clone());
@@ -451,49 +1025,148 @@ impl Client {
         }
 
         // If job_id specified, get job logs, otherwise get run logs
-        let url = if let Some(jid) = job_id {
-            format!("{}/repos/{}/{}/actions/jobs/{}/logs", API_BASE, owner, repo, jid)
+        let path = if let Some(jid) = job_id {
+            format!("/repos/{}/{}/actions/jobs/{}/logs", owner, repo, jid)
         } else {
-            format!("{}/repos/{}/{}/actions/runs/{}/logs", API_BASE, owner, repo, run_id)
+            format!("/repos/{}/{}/actions/runs/{}/logs", owner, repo, run_id)
         };
 
-        let response = self.http
-            .get(&url)
-            .header(AUTHORIZATION, format!("Bearer {}", self.token))
-            .header(USER_AGENT, "github-tui")
-            .send()
-            .await;
+        let request = self.get(&path);
+        let resp = self.send(request, "Failed to fetch logs").await?;
 
-        match response {
-            Ok(resp) => {
-                if resp.status() == 404 {
-                    return Ok("Logs not available yet. The run may still be in progress or queued.".to_string());
-                }
+        if resp.status() == 404 {
+            return Ok("Logs not available yet. The run may still be in progress or queued.".to_string());
+        }
 
-                if !resp.status().is_success() {
-                    return Err(anyhow::anyhow!("Failed to fetch logs: {}", resp.status()));
-                }
+        if !resp.status().is_success() {
+            return Err(anyhow::anyhow!("Failed to fetch logs: {}", resp.status()));
+        }
+
+        let bytes = resp.bytes().await.context("Failed to read logs response")?;
+
+        // The response is a zip file, try to extract it
+        let logs = if let Ok(extracted) = Self::extract_logs_from_zip(&bytes) {
+            extracted
+        } else {
+            // If not a zip, try as plain text
+            String::from_utf8_lossy(&bytes).to_string()
+        };
 
-                let bytes = resp.bytes().await.context("Failed to read logs response")?;
+        // Cache job logs (completed jobs are immutable)
+        if let Some(jid) = job_id.filter(|_| cacheable) {
+            let mut cache = self.cache.write().await;
+            cache.job_logs.insert(jid, logs.clone());
+        }
 
-                // The response is a zip file, try to extract it
-                let logs = if let Ok(extracted) = Self::extract_logs_from_zip(&bytes) {
-                    extracted
-                } else {
-                    // If not a zip, try as plain text
-                    String::from_utf8_lossy(&bytes).to_string()
-                };
+        Ok(logs)
+    }
 
-                // Cache job logs (completed jobs are immutable)
-                if let Some(jid) = job_id {
-                    let mut cache = self.cache.write().await;
-                    cache.job_logs.insert(jid, logs.clone());
-                }
+    /// Download the log archive of a run as-is (a zip with one file per step)
+    pub async fn download_run_logs_archive(&self, owner: &str, repo: &str, run_id: u64) -> Result<Vec<u8>> {
+        let path = format!("/repos/{}/{}/actions/runs/{}/logs", owner, repo, run_id);
 
-                Ok(logs)
-            }
-            Err(e) => Err(anyhow::anyhow!("Failed to fetch logs: {}", e)),
+        let request = self.get(&path);
+        let resp = self.send(request, "Failed to download logs").await?;
+
+        if !resp.status().is_success() {
+            return Err(anyhow::anyhow!("Failed to download logs: {}", resp.status()));
         }
+
+        Ok(resp.bytes().await.context("Failed to read logs response")?.to_vec())
+    }
+
+    pub async fn list_artifacts(&self, owner: &str, repo: &str, run_id: u64) -> Result<Vec<Artifact>> {
+        let path = format!("/repos/{}/{}/actions/runs/{}/artifacts?per_page=100", owner, repo, run_id);
+
+        let request = self.get(&path);
+        let response: ArtifactsResponse = self.send(request, "Failed to fetch artifacts").await?
+            .json()
+            .await
+            .context("Failed to parse artifacts response")?;
+
+        Ok(response.artifacts)
+    }
+
+    /// Environments the run is waiting on for a reviewer's approval
+    pub async fn list_pending_deployments(&self, owner: &str, repo: &str, run_id: u64) -> Result<Vec<PendingDeployment>> {
+        let path = format!("/repos/{}/{}/actions/runs/{}/pending_deployments", owner, repo, run_id);
+
+        let request = self.get(&path);
+        let deployments: Vec<PendingDeploymentResponse> = self.send(request, "Failed to fetch pending deployments").await?
+            .json()
+            .await
+            .context("Failed to parse pending deployments response")?;
+
+        Ok(deployments
+            .into_iter()
+            .map(|d| PendingDeployment {
+                environment_id: d.environment.id,
+                environment: d.environment.name,
+                can_approve: d.current_user_can_approve,
+            })
+            .collect())
+    }
+
+    /// Approve or reject the run's deployments to the given environments
+    pub async fn review_pending_deployment(
+        &self,
+        owner: &str,
+        repo: &str,
+        run_id: u64,
+        environment_ids: &[u64],
+        approve: bool,
+        comment: &str,
+    ) -> Result<()> {
+        let path = format!("/repos/{}/{}/actions/runs/{}/pending_deployments", owner, repo, run_id);
+        let body = serde_json::json!({
+            "environment_ids": environment_ids,
+            "state": if approve { "approved" } else { "rejected" },
+            "comment": comment,
+        });
+
+        let request = self.post(&path, &body);
+        let response = self.send(request, "Failed to review deployment").await?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            let status = response.status();
+            let message = response.json::<serde_json::Value>().await.ok()
+                .and_then(|body| body["message"].as_str().map(|m| m.to_string()))
+                .unwrap_or_default();
+            Err(anyhow::anyhow!("Failed to review deployment: {} {}", status, message))
+        }
+    }
+
+    /// Download an artifact's zip and extract it into `dir`, returning the
+    /// number of files written
+    pub async fn download_artifact(&self, artifact: &Artifact, dir: std::path::PathBuf) -> Result<usize> {
+        let path = artifact
+            .archive_download_url
+            .strip_prefix(&self.api_base)
+            .context("Unexpected artifact download URL")?;
+
+        let request = self.get(path);
+        let resp = self.send(request, "Failed to download artifact").await?;
+
+        if !resp.status().is_success() {
+            return Err(anyhow::anyhow!("Failed to download artifact: {}", resp.status()));
+        }
+
+        let bytes = resp.bytes().await.context("Failed to read artifact")?;
+        tokio::task::spawn_blocking(move || Self::extract_zip(&bytes, &dir))
+            .await
+            .context("Failed to extract artifact")?
+    }
+
+    /// Unpack a zip into `dir`. Entries with paths escaping `dir` are rejected.
+    fn extract_zip(data: &[u8], dir: &std::path::Path) -> Result<usize> {
+        let cursor = std::io::Cursor::new(data);
+        let mut archive = zip::ZipArchive::new(cursor).context("Failed to open zip archive")?;
+        archive.extract(dir).with_context(|| format!("Failed to extract to {}", dir.display()))?;
+        Ok((0..archive.len())
+            .filter(|&i| archive.by_index(i).is_ok_and(|f| f.is_file()))
+            .count())
     }
 
     fn extract_logs_from_zip(data: &[u8]) -> Result<String> {
@@ -531,17 +1204,13 @@ impl Client {
 
     pub async fn rerun_workflow(&self, owner: &str, repo: &str, run_id: u64) -> Result<()> {
         // First try to rerun only failed jobs
-        let url_failed = format!(
-            "{}/repos/{}/{}/actions/runs/{}/rerun-failed-jobs",
-            API_BASE, owner, repo, run_id
+        let path_failed = format!(
+            "/repos/{}/{}/actions/runs/{}/rerun-failed-jobs",
+            owner, repo, run_id
         );
 
-        let response = self.http
-            .post(&url_failed)
-            .header(AUTHORIZATION, format!("Bearer {}", self.token))
-            .header(USER_AGENT, "github-tui")
-            .send()
-            .await;
+        let request = self.post(&path_failed, &serde_json::json!({}));
+        let response = self.send(request, "Failed to rerun failed jobs").await;
 
         if let Ok(resp) = response {
             if resp.status().is_success() {
@@ -550,18 +1219,13 @@ impl Client {
         }
 
         // If rerun-failed-jobs fails, try full rerun
-        let url_full = format!(
-            "{}/repos/{}/{}/actions/runs/{}/rerun",
-            API_BASE, owner, repo, run_id
+        let path_full = format!(
+            "/repos/{}/{}/actions/runs/{}/rerun",
+            owner, repo, run_id
         );
 
-        let response = self.http
-            .post(&url_full)
-            .header(AUTHORIZATION, format!("Bearer {}", self.token))
-            .header(USER_AGENT, "github-tui")
-            .send()
-            .await
-            .context("Failed to rerun workflow")?;
+        let request = self.post(&path_full, &serde_json::json!({}));
+        let response = self.send(request, "Failed to rerun workflow").await?;
 
         if response.status().is_success() {
             Ok(())
@@ -571,18 +1235,13 @@ impl Client {
     }
 
     pub async fn list_pr_commits(&self, owner: &str, repo: &str, number: u64) -> Result<Vec<Commit>> {
-        let url = format!(
-            "{}/repos/{}/{}/pulls/{}/commits?per_page=100",
-            API_BASE, owner, repo, number
+        let path = format!(
+            "/repos/{}/{}/pulls/{}/commits?per_page=100",
+            owner, repo, number
         );
 
-        let commits: Vec<CommitResponse> = self.http
-            .get(&url)
-            .header(AUTHORIZATION, format!("Bearer {}", self.token))
-            .header(USER_AGENT, "github-tui")
-            .send()
-            .await
-            .context("Failed to fetch PR commits")?
+        let request = self.get(&path);
+        let commits: Vec<CommitResponse> = self.send(request, "Failed to fetch PR commits").await?
             .json()
             .await
             .context("Failed to parse commits response")?;
@@ -596,38 +1255,25 @@ impl Client {
                 .unwrap_or_default();
             Commit {
                 sha: c.sha,
-                message: c.commit.message.lines().next().unwrap_or("").to_string(),
+                message: c.commit.message,
                 author,
                 date,
             }
         }).collect())
     }
 
-    pub async fn get_commit_diff(&self, owner: &str, repo: &str, sha: &str) -> Result<String> {
+    /// Diff of a single commit. A `refresh` skips the cached copy and replaces it.
+    pub async fn get_commit_diff(&self, owner: &str, repo: &str, sha: &str, refresh: bool) -> Result<String> {
         // Check cache first - commit diffs are immutable
-        {
+        if !refresh {
             let cache = self.cache.read().await;
             if let Some(diff) = cache.commit_diffs.get(sha) {
                 return Ok(diff.clone());
             }
         }
 
-        let url = format!("{}/repos/{}/{}/commits/{}", API_BASE, owner, repo, sha);
-
-        let response = self.http
-            .get(&url)
-            .header(AUTHORIZATION, format!("Bearer {}", self.token))
-            .header(ACCEPT, "application/vnd.github.diff")
-            .header(USER_AGENT, "github-tui")
-            .send()
-            .await
-            .context("Failed to fetch commit diff")?;
-
-        if !response.status().is_success() {
-            return Err(anyhow::anyhow!("Failed to fetch commit diff: {}", response.status()));
-        }
-
-        let diff = response.text().await.context("Failed to read commit diff response")?;
+        let path = format!("/repos/{}/{}/commits/{}", owner, repo, sha);
+        let diff = self.fetch_diff(&path, "Failed to fetch commit diff").await?;
 
         // Cache the result
         {
@@ -639,23 +1285,19 @@ impl Client {
     }
 
     pub async fn list_pr_reviews(&self, owner: &str, repo: &str, number: u64) -> Result<Vec<Review>> {
-        let url = format!(
-            "{}/repos/{}/{}/pulls/{}/reviews",
-            API_BASE, owner, repo, number
+        let path = format!(
+            "/repos/{}/{}/pulls/{}/reviews",
+            owner, repo, number
         );
 
-        let reviews: Vec<ReviewResponse> = self.http
-            .get(&url)
-            .header(AUTHORIZATION, format!("Bearer {}", self.token))
-            .header(USER_AGENT, "github-tui")
-            .send()
-            .await
-            .context("Failed to fetch PR reviews")?
+        let request = self.get(&path);
+        let reviews: Vec<ReviewResponse> = self.send(request, "Failed to fetch PR reviews").await?
             .json()
             .await
             .context("Failed to parse reviews response")?;
 
         Ok(reviews.into_iter().map(|r| Review {
+            id: r.id,
             user: super::types::User {
                 login: r.user.login,
                 avatar_url: r.user.avatar_url.unwrap_or_default(),
@@ -665,6 +1307,45 @@ impl Client {
         }).collect())
     }
 
+    /// Unread notification threads of the authenticated user, for one repository
+    /// or across all of them
+    pub async fn list_notifications(&self, repo: Option<(&str, &str)>) -> Result<Vec<Notification>> {
+        let path = match repo {
+            Some((owner, repo)) => format!("/repos/{}/{}/notifications?per_page=50", owner, repo),
+            None => "/notifications?per_page=50".to_string(),
+        };
+
+        let request = self.get(&path);
+        let threads: Vec<NotificationResponse> = self.send(request, "Failed to fetch notifications").await?
+            .json()
+            .await
+            .context("Failed to parse notifications response")?;
+
+        let web_base = web_base(&self.api_base);
+        Ok(threads.into_iter().map(|t| Notification {
+            url: t.subject.url.as_deref().and_then(|url| web_url(url, &web_base)),
+            id: t.id,
+            repo: t.repository.full_name,
+            title: t.subject.title,
+            kind: t.subject.kind,
+            reason: t.reason,
+            updated_at: t.updated_at,
+        }).collect())
+    }
+
+    pub async fn mark_notification_read(&self, thread_id: &str) -> Result<()> {
+        let path = format!("/notifications/threads/{}", thread_id);
+
+        let request = self.patch(&path, &serde_json::json!({}));
+        let response = self.send(request, "Failed to mark notification as read").await?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("Failed to mark notification as read: {}", response.status()))
+        }
+    }
+
     /// Find a recently pushed branch without an open PR
     /// Returns the most recently pushed branch by the current user that doesn't have a PR
     pub async fn find_recent_branch_without_pr(
@@ -675,15 +1356,10 @@ impl Client {
         open_pr_branches: &[String],
     ) -> Result<Option<super::types::RecentBranch>> {
         // Fetch recent events for the repo
-        let url = format!("{}/repos/{}/{}/events?per_page=30", API_BASE, owner, repo);
+        let path = format!("/repos/{}/{}/events?per_page=30", owner, repo);
 
-        let events: Vec<EventResponse> = self.http
-            .get(&url)
-            .header(AUTHORIZATION, format!("Bearer {}", self.token))
-            .header(USER_AGENT, "github-tui")
-            .send()
-            .await
-            .context("Failed to fetch events")?
+        let request = self.get(&path);
+        let events: Vec<EventResponse> = self.send(request, "Failed to fetch events").await?
             .json()
             .await
             .context("Failed to parse events response")?;
@@ -731,7 +1407,6 @@ impl Client {
                     return Ok(Some(super::types::RecentBranch {
                         name: branch,
                         pushed_at: event.created_at,
-                        minutes_ago,
                     }));
                 }
             }
@@ -741,6 +1416,86 @@ impl Client {
     }
 }
 
+/// Read the `X-RateLimit-*` headers, if all are present
+fn parse_rate_limit(headers: &HeaderMap) -> Option<RateLimit> {
+    let header = |name: &str| headers.get(name)?.to_str().ok()?.parse::<i64>().ok();
+    Some(RateLimit {
+        limit: header("x-ratelimit-limit")? as u64,
+        remaining: header("x-ratelimit-remaining")? as u64,
+        reset: header("x-ratelimit-reset")?,
+    })
+}
+
+/// The error for a 403 or 429 that is a rate limit, or None for other refusals.
+/// Primary limit: quota exhausted. Secondary limit: 429 or a 403 whose message
+/// mentions the rate limit, usually with a Retry-After header.
+fn rate_limit_error(status: StatusCode, rate_limit: Option<RateLimit>, retry_after: Option<i64>, message: &str) -> Option<String> {
+    let exhausted = rate_limit.filter(|r| r.remaining == 0);
+    if exhausted.is_none() && status != StatusCode::TOO_MANY_REQUESTS && !message.to_lowercase().contains("rate limit") {
+        return None;
+    }
+
+    Some(match retry_after.or_else(|| exhausted.map(|r| r.seconds_until_reset())) {
+        Some(secs) => format!("Rate limited; resets in {}", format_wait(secs)),
+        None => "Rate limited; try again in a minute".to_string(),
+    })
+}
+
+/// Round a wait up to whole minutes ("4m"), or seconds when under a minute
+fn format_wait(secs: i64) -> String {
+    if secs < 60 {
+        format_duration(secs)
+    } else {
+        format!("{}m", (secs + 59) / 60)
+    }
+}
+
+/// Web root matching an API base: github.com for the public API, the host
+/// itself for Enterprise (`https://ghe.example.com/api/v3`)
+fn web_base(api_base: &str) -> String {
+    let api_base = api_base.trim_end_matches('/');
+    if api_base == DEFAULT_API_BASE {
+        "https://github.com".to_string()
+    } else {
+        api_base.strip_suffix("/api/v3").unwrap_or(api_base).to_string()
+    }
+}
+
+/// Web page for a notification subject's API URL, e.g.
+/// `.../repos/o/r/pulls/1` -> `https://github.com/o/r/pull/1`
+fn web_url(api_url: &str, web_base: &str) -> Option<String> {
+    let (_, path) = api_url.split_once("/repos/")?;
+    let segments: Vec<&str> = path.split('/').collect();
+    let path = match segments.as_slice() {
+        [owner, repo, "pulls", number] => format!("{}/{}/pull/{}", owner, repo, number),
+        [owner, repo, "issues", number] => format!("{}/{}/issues/{}", owner, repo, number),
+        [owner, repo, "commits", sha] => format!("{}/{}/commit/{}", owner, repo, sha),
+        [owner, repo, "releases", ..] => format!("{}/{}/releases", owner, repo),
+        [owner, repo, ..] => format!("{}/{}", owner, repo),
+        _ => return None,
+    };
+    Some(format!("{}/{}", web_base, path))
+}
+
+/// Percent-encode everything but unreserved characters (RFC 3986) for use in a URL path
+fn encode_path_segment(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// The wire name of an octocrab enum (e.g. `Status::InProgress` -> "in_progress")
+fn serde_name<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(|s| s.to_string()))
+        .unwrap_or_default()
+}
+
 // Response types for API calls
 
 #[derive(serde::Deserialize)]
@@ -763,6 +1518,70 @@ struct WorkflowRunJson {
     html_url: String,
 }
 
+#[derive(serde::Deserialize)]
+struct ArtifactsResponse {
+    artifacts: Vec<Artifact>,
+}
+
+#[derive(serde::Deserialize)]
+struct PendingDeploymentResponse {
+    environment: EnvironmentResponse,
+    #[serde(default)]
+    current_user_can_approve: bool,
+}
+
+#[derive(serde::Deserialize)]
+struct EnvironmentResponse {
+    id: u64,
+    name: String,
+}
+
+#[derive(serde::Deserialize)]
+struct WorkflowsResponse {
+    workflows: Vec<WorkflowJson>,
+}
+
+#[derive(serde::Deserialize)]
+struct WorkflowJson {
+    id: u64,
+    name: String,
+    path: String,
+    state: String,
+}
+
+#[derive(serde::Deserialize)]
+struct CheckRunsResponse {
+    check_runs: Vec<CheckRunJson>,
+}
+
+#[derive(serde::Deserialize)]
+struct CheckRunJson {
+    name: String,
+    status: String,
+    conclusion: Option<String>,
+    html_url: String,
+    details_url: Option<String>,
+    app: Option<CheckRunApp>,
+}
+
+#[derive(serde::Deserialize)]
+struct CheckRunApp {
+    slug: String,
+    name: String,
+}
+
+#[derive(serde::Deserialize)]
+struct CombinedStatusResponse {
+    statuses: Vec<CommitStatusJson>,
+}
+
+#[derive(serde::Deserialize)]
+struct CommitStatusJson {
+    context: String,
+    state: String,
+    target_url: Option<String>,
+}
+
 #[derive(serde::Deserialize)]
 struct CommitResponse {
     sha: String,
@@ -784,6 +1603,7 @@ struct CommitAuthor {
 
 #[derive(serde::Deserialize)]
 struct ReviewResponse {
+    id: u64,
     user: ReviewUser,
     state: String,
     submitted_at: Option<String>,
@@ -795,6 +1615,28 @@ struct ReviewUser {
     avatar_url: Option<String>,
 }
 
+#[derive(serde::Deserialize)]
+struct NotificationResponse {
+    id: String,
+    reason: String,
+    updated_at: String,
+    subject: NotificationSubject,
+    repository: NotificationRepository,
+}
+
+#[derive(serde::Deserialize)]
+struct NotificationSubject {
+    title: String,
+    url: Option<String>,
+    #[serde(rename = "type")]
+    kind: String,
+}
+
+#[derive(serde::Deserialize)]
+struct NotificationRepository {
+    full_name: String,
+}
+
 #[derive(serde::Deserialize)]
 struct EventResponse {
     #[serde(rename = "type")]
@@ -815,3 +1657,70 @@ struct EventPayload {
     #[serde(rename = "ref")]
     ref_field: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use reqwest::header::HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn parses_rate_limit_headers() {
+        let limit = parse_rate_limit(&headers(&[
+            ("x-ratelimit-limit", "5000"),
+            ("x-ratelimit-remaining", "4873"),
+            ("x-ratelimit-reset", "1700000000"),
+        ]))
+        .unwrap();
+        assert_eq!((limit.limit, limit.remaining, limit.reset), (5000, 4873, 1700000000));
+
+        // Partial or garbled headers give nothing rather than a bogus quota
+        assert!(parse_rate_limit(&headers(&[("x-ratelimit-limit", "5000")])).is_none());
+        assert!(parse_rate_limit(&headers(&[
+            ("x-ratelimit-limit", "5000"),
+            ("x-ratelimit-remaining", "lots"),
+            ("x-ratelimit-reset", "1700000000"),
+        ]))
+        .is_none());
+    }
+
+    #[test]
+    fn formats_waits() {
+        assert_eq!(format_wait(0), "0s");
+        assert_eq!(format_wait(45), "45s");
+        assert_eq!(format_wait(60), "1m");
+        assert_eq!(format_wait(61), "2m");
+        assert_eq!(format_wait(3600), "60m");
+    }
+
+    #[test]
+    fn classifies_rate_limits() {
+        let exhausted = RateLimit { limit: 5000, remaining: 0, reset: Utc::now().timestamp() + 240 };
+        let left = RateLimit { remaining: 10, ..exhausted };
+
+        // Primary limit: the quota is used up, resetting at `reset`
+        let error = rate_limit_error(StatusCode::FORBIDDEN, Some(exhausted), None, "API rate limit exceeded").unwrap();
+        assert!(error == "Rate limited; resets in 4m" || error == "Rate limited; resets in 3m", "{}", error);
+
+        // Secondary limit: Retry-After wins, a 429 counts even without the headers
+        assert_eq!(
+            rate_limit_error(StatusCode::FORBIDDEN, Some(left), Some(30), "You have exceeded a secondary rate limit").as_deref(),
+            Some("Rate limited; resets in 30s")
+        );
+        assert_eq!(
+            rate_limit_error(StatusCode::TOO_MANY_REQUESTS, None, None, "").as_deref(),
+            Some("Rate limited; try again in a minute")
+        );
+
+        // Other 403s are plain permission errors
+        assert_eq!(rate_limit_error(StatusCode::FORBIDDEN, Some(left), None, "Resource not accessible by integration"), None);
+    }
+}
@@ -0,0 +1,234 @@
+/// An input declared under `on.workflow_dispatch.inputs` of a workflow file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DispatchInput {
+    pub name: String,
+    pub description: Option<String>,
+    pub default: Option<String>,
+    pub required: bool,
+    pub options: Vec<String>,  // Allowed values of a `type: choice` input
+}
+
+/// Inputs of a workflow's `workflow_dispatch` trigger, or None if the workflow
+/// can't be dispatched manually. A line-based scan of the YAML, enough for the
+/// block style workflows are written in.
+pub fn parse_inputs(yaml: &str) -> Option<Vec<DispatchInput>> {
+    let lines: Vec<(usize, &str)> = yaml
+        .lines()
+        .map(strip_comment)
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let text = line.trim_start();
+            (line.len() - text.len(), text.trim_end())
+        })
+        .collect();
+
+    // Triggers are only looked for under the top-level `on:` key
+    let on_at = lines.iter().position(|&(indent, text)| indent == 0 && key_value(text).is_some_and(|(k, _)| k == "on"))?;
+    let (_, on_value) = key_value(lines[on_at].1)?;
+    if !on_value.is_empty() {
+        // Flow style: `on: workflow_dispatch` or `on: [push, workflow_dispatch]`
+        return on_value
+            .trim_start_matches(['[', '{'])
+            .trim_end_matches([']', '}'])
+            .split(',')
+            .any(|trigger| unquote(trigger.split(':').next().unwrap_or_default().trim()) == "workflow_dispatch")
+            .then(Vec::new);
+    }
+    let triggers: Vec<(usize, &str)> = lines[on_at + 1..]
+        .iter()
+        .copied()
+        .take_while(|(indent, _)| *indent > 0)
+        .collect();
+    let trigger_indent = triggers.first().map_or(0, |(indent, _)| *indent);
+
+    let start = triggers.iter().position(|&(indent, text)| {
+        // `workflow_dispatch:`, or `- workflow_dispatch` in a list of triggers
+        indent == trigger_indent
+            && (key_value(text).is_some_and(|(k, _)| k == "workflow_dispatch")
+                || text.strip_prefix("- ").is_some_and(|t| unquote(t) == "workflow_dispatch"))
+    })?;
+    let trigger: Vec<&(usize, &str)> = triggers[start + 1..]
+        .iter()
+        .take_while(|(indent, _)| *indent > trigger_indent)
+        .collect();
+
+    let Some(inputs_at) = trigger.iter().position(|(_, text)| key_value(text).is_some_and(|(k, _)| k == "inputs")) else {
+        return Some(Vec::new());
+    };
+    let inputs_indent = trigger[inputs_at].0;
+    let body: Vec<&(usize, &str)> = trigger[inputs_at + 1..]
+        .iter()
+        .copied()
+        .take_while(|(indent, _)| *indent > inputs_indent)
+        .collect();
+    let name_indent = body.first().map_or(0, |(indent, _)| *indent);
+
+    let mut inputs: Vec<DispatchInput> = Vec::new();
+    let mut in_options = false;
+    for &&(indent, text) in &body {
+        if indent == name_indent {
+            if let Some((name, _)) = key_value(text) {
+                inputs.push(DispatchInput { name: name.to_string(), ..Default::default() });
+            }
+            in_options = false;
+            continue;
+        }
+        let Some(input) = inputs.last_mut() else {
+            continue;
+        };
+        if let Some(option) = text.strip_prefix("- ").filter(|_| in_options) {
+            input.options.push(unquote(option).to_string());
+            continue;
+        }
+
+        in_options = false;
+        match key_value(text) {
+            Some(("description", value)) => input.description = Some(unquote(value).to_string()),
+            Some(("default", value)) => input.default = Some(unquote(value).to_string()),
+            Some(("required", value)) => input.required = value == "true",
+            Some(("options", value)) if value.starts_with('[') => {
+                let list = value.trim_start_matches('[').trim_end_matches(']');
+                input.options = list.split(',').map(|o| unquote(o.trim()).to_string()).collect();
+            }
+            Some(("options", _)) => in_options = true,
+            _ => {}
+        }
+    }
+
+    Some(inputs)
+}
+
+/// `key: value` with the key unquoted; the value may be empty
+fn key_value(text: &str) -> Option<(&str, &str)> {
+    let (key, value) = text.split_once(':')?;
+    let key = unquote(key.trim());
+    (!key.is_empty() && !key.contains(' ')).then(|| (key, value.trim()))
+}
+
+fn unquote(value: &str) -> &str {
+    ['"', '\'']
+        .into_iter()
+        .find_map(|q| value.strip_prefix(q).and_then(|v| v.strip_suffix(q)))
+        .unwrap_or(value)
+}
+
+/// Drop a trailing `# comment` (one preceded by whitespace, outside quotes)
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut prev = ' ';
+    for (i, c) in line.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, '#') if prev.is_whitespace() => return &line[..i],
+            _ => {}
+        }
+        prev = c;
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_style_inputs() {
+        let yaml = r#"
+name: Deploy
+on:
+  push:
+    branches: [main]
+  workflow_dispatch:
+    inputs:
+      environment:
+        description: "Where to deploy" # staging or production
+        required: true
+        default: staging
+        type: choice
+        options:
+          - staging
+          - production
+      dry_run:
+        type: boolean
+        default: 'false'
+jobs:
+  deploy:
+    runs-on: ubuntu-latest
+"#;
+        let inputs = parse_inputs(yaml).unwrap();
+        assert_eq!(inputs.len(), 2);
+        assert_eq!(
+            inputs[0],
+            DispatchInput {
+                name: "environment".to_string(),
+                description: Some("Where to deploy".to_string()),
+                default: Some("staging".to_string()),
+                required: true,
+                options: vec!["staging".to_string(), "production".to_string()],
+            }
+        );
+        assert_eq!(inputs[1].name, "dry_run");
+        assert_eq!(inputs[1].default.as_deref(), Some("false"));
+        assert!(!inputs[1].required);
+    }
+
+    #[test]
+    fn trigger_without_inputs() {
+        assert_eq!(parse_inputs("on:\n  workflow_dispatch:\n  push:\n"), Some(Vec::new()));
+        assert_eq!(parse_inputs("on: [push, workflow_dispatch]\n"), Some(Vec::new()));
+        assert_eq!(parse_inputs("on: workflow_dispatch\n"), Some(Vec::new()));
+    }
+
+    #[test]
+    fn list_of_triggers() {
+        assert_eq!(parse_inputs("on:\n  - push\n  - workflow_dispatch\n"), Some(Vec::new()));
+    }
+
+    #[test]
+    fn not_dispatchable() {
+        assert_eq!(parse_inputs("on:\n  push:\n    branches: [main]\n"), None);
+    }
+
+    #[test]
+    fn mentions_outside_on_are_not_triggers() {
+        let yaml = r#"
+name: Mentions workflow_dispatch
+on: [push]
+jobs:
+  build:
+    steps:
+      - run: echo "no workflow_dispatch here"
+      - name: workflow_dispatch
+        uses: actions/checkout@v4
+"#;
+        assert_eq!(parse_inputs(yaml), None);
+        assert_eq!(parse_inputs("on: push\nenv:\n  workflow_dispatch: x\n"), None);
+    }
+
+    #[test]
+    fn options_end_at_the_next_input() {
+        let yaml = r#"
+on:
+  workflow_dispatch:
+    inputs:
+      level:
+        type: choice
+        options:
+          - debug
+          - info
+      target:
+        - bogus
+"#;
+        let inputs = parse_inputs(yaml).unwrap();
+        assert_eq!(inputs[0].options, ["debug", "info"]);
+        assert_eq!(inputs[1].name, "target");
+        assert!(inputs[1].options.is_empty());
+    }
+
+    #[test]
+    fn inline_options() {
+        let yaml = "on:\n  workflow_dispatch:\n    inputs:\n      level:\n        options: [debug, 'info']\n";
+        assert_eq!(parse_inputs(yaml).unwrap()[0].options, ["debug", "info"]);
+    }
+}
@@ -1,4 +1,5 @@
 mod client;
+pub mod dispatch;
 pub mod types;
 
-pub use client::Client;
+pub use client::{Client, MAX_PR_PAGES, RUN_PAGE_SIZE};
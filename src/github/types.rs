@@ -1,4 +1,6 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PullRequest {
@@ -19,6 +21,11 @@ pub struct PullRequest {
     pub labels: Vec<Label>,
     #[serde(default)]
     pub requested_reviewers: Vec<User>,
+    /// Slugs of teams asked for a review; the teams belong to the repo's owner
+    #[serde(default)]
+    pub requested_teams: Vec<String>,
+    #[serde(default)]
+    pub assignees: Vec<User>,
     #[serde(default)]
     pub ci_status: Option<String>,
 }
@@ -35,6 +42,9 @@ pub struct Branch {
     #[serde(rename = "ref")]
     pub ref_name: String,
     pub sha: String,
+    /// Owner of the repository the branch lives in; not the base's for forks
+    #[serde(default)]
+    pub owner: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -60,6 +70,88 @@ pub struct WorkflowRun {
     pub html_url: String,
 }
 
+/// A CI check on a commit: a workflow run, a check run of any app, or a
+/// commit status
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub name: String,
+    pub status: String,
+    pub conclusion: Option<String>,
+    pub html_url: String,
+    /// App or API reporting it ("Actions", "Jenkins", "status")
+    pub source: String,
+    /// The workflow run behind an Actions check, with jobs and logs of its own
+    pub run: Option<WorkflowRun>,
+}
+
+/// Status a run list can be narrowed to
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RunStatusFilter {
+    #[default]
+    All,
+    InProgress,
+    Failure,
+    Success,
+}
+
+/// Events the run list can be narrowed to, cycled in this order after "all"
+pub const RUN_EVENTS: [&str; 4] = ["push", "pull_request", "schedule", "workflow_dispatch"];
+
+/// Query for the workflow run list: `?branch=&status=&event=`, unset means any
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RunFilter {
+    pub status: RunStatusFilter,
+    pub branch: Option<String>,
+    pub event: Option<&'static str>,
+}
+
+impl RunFilter {
+    /// Value of the `status` query parameter
+    pub fn status_param(&self) -> Option<&'static str> {
+        match self.status {
+            RunStatusFilter::All => None,
+            RunStatusFilter::InProgress => Some("in_progress"),
+            RunStatusFilter::Failure => Some("failure"),
+            RunStatusFilter::Success => Some("success"),
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        *self != Self::default()
+    }
+
+    /// Whether a run would be listed under this filter
+    pub fn matches(&self, run: &WorkflowRun) -> bool {
+        let status = match self.status {
+            RunStatusFilter::All => true,
+            RunStatusFilter::InProgress => run.status == "in_progress",
+            RunStatusFilter::Failure => run.conclusion.as_deref() == Some("failure"),
+            RunStatusFilter::Success => run.conclusion.as_deref() == Some("success"),
+        };
+        status
+            && self.branch.as_ref().is_none_or(|b| *b == run.head_branch)
+            && self.event.is_none_or(|e| e == run.event)
+    }
+
+    /// Active parts for display, e.g. "failure · main · push"
+    pub fn label(&self) -> String {
+        self.status_param()
+            .into_iter()
+            .chain(self.branch.as_deref())
+            .chain(self.event)
+            .collect::<Vec<_>>()
+            .join(" · ")
+    }
+}
+
+/// A workflow file of the repository
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Workflow {
+    pub id: u64,
+    pub name: String,
+    pub path: String,  // e.g. .github/workflows/deploy.yml
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Job {
     pub id: u64,
@@ -70,6 +162,8 @@ pub struct Job {
     pub started_at: String,
     pub completed_at: Option<String>,
     #[serde(default)]
+    pub html_url: String,
+    #[serde(default)]
     pub steps: Vec<Step>,
 }
 
@@ -91,6 +185,8 @@ pub struct Commit {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Review {
+    #[serde(default)]
+    pub id: u64,
     pub user: User,
     pub state: String,  // APPROVED, CHANGES_REQUESTED, COMMENTED, PENDING, DISMISSED
     pub submitted_at: Option<String>,
@@ -109,6 +205,27 @@ impl Review {
     }
 }
 
+/// Each reviewer's standing review, by login. As on GitHub, only an approval,
+/// change request or dismissal replaces an earlier one; a comment only stands
+/// for a reviewer who hasn't done anything else. Pending reviews are skipped.
+pub fn latest_reviews(reviews: &[Review]) -> BTreeMap<&str, &Review> {
+    let mut sorted: Vec<&Review> = reviews.iter().filter(|r| r.state != "PENDING").collect();
+    // ISO 8601 timestamps sort lexicographically
+    sorted.sort_by(|a, b| a.submitted_at.cmp(&b.submitted_at));
+
+    let mut latest: BTreeMap<&str, &Review> = BTreeMap::new();
+    for review in sorted {
+        let login = review.user.login.as_str();
+        match latest.get(login) {
+            Some(standing) if review.state == "COMMENTED" && standing.state != "COMMENTED" => {}
+            _ => {
+                latest.insert(login, review);
+            }
+        }
+    }
+    latest
+}
+
 impl Commit {
     pub fn short_sha(&self) -> &str {
         if self.sha.len() >= 7 {
@@ -121,6 +238,13 @@ impl Commit {
     pub fn first_line(&self) -> &str {
         self.message.lines().next().unwrap_or(&self.message)
     }
+
+    /// Everything after the summary line, without the blank line that separates them
+    pub fn body(&self) -> &str {
+        self.message
+            .split_once('\n')
+            .map_or("", |(_, body)| body.trim())
+    }
 }
 
 impl PullRequest {
@@ -137,17 +261,218 @@ impl PullRequest {
     }
 
     pub fn ci_icon(&self) -> &'static str {
-        match self.ci_status.as_deref() {
-            Some("success") => "✓",
-            Some("failure") => "✗",
-            Some("pending") => "◷",
-            Some("error") => "⚠",
+        ci_icon(self.ci_status.as_deref())
+    }
+}
+
+/// Icon for an aggregated CI status, as set on PRs and commits
+pub fn ci_icon(ci_status: Option<&str>) -> &'static str {
+    match ci_status {
+        Some("success") => "✓",
+        Some("failure") => "✗",
+        Some("pending") => "◷",
+        Some("error") => "⚠",
+        _ => "○",
+    }
+}
+
+impl WorkflowRun {
+    pub fn is_finished(&self) -> bool {
+        self.status == "completed" || self.conclusion.is_some()
+    }
+
+    /// Elapsed time for a running workflow, total time for a finished one
+    pub fn duration(&self) -> String {
+        let Some(start) = parse_timestamp(&self.created_at) else {
+            return "-".to_string();
+        };
+        let end = if self.is_finished() {
+            match parse_timestamp(&self.updated_at) {
+                Some(end) => end,
+                None => return "-".to_string(),
+            }
+        } else {
+            Utc::now()
+        };
+        format_duration((end - start).num_seconds())
+    }
+
+    pub fn status_icon(&self) -> &'static str {
+        status_icon(&self.status, self.conclusion.as_deref())
+    }
+}
+
+impl Check {
+    pub fn from_run(run: WorkflowRun) -> Self {
+        Self {
+            name: run.name.clone(),
+            status: run.status.clone(),
+            conclusion: run.conclusion.clone(),
+            html_url: run.html_url.clone(),
+            source: "Actions".to_string(),
+            run: Some(run),
+        }
+    }
+
+    pub fn status_icon(&self) -> &'static str {
+        status_icon(&self.status, self.conclusion.as_deref())
+    }
+}
+
+fn status_icon(status: &str, conclusion: Option<&str>) -> &'static str {
+    match conclusion {
+        Some("success") => "✓",
+        Some("failure") => "✗",
+        Some("cancelled") => "⊘",
+        Some("skipped") => "⊘",
+        _ => match status {
+            "in_progress" => "◷",
+            "queued" => "◯",
             _ => "○",
+        },
+    }
+}
+
+/// A protected environment a run is waiting on for manual approval
+#[derive(Debug, Clone)]
+pub struct PendingDeployment {
+    pub environment_id: u64,
+    pub environment: String,
+    /// Whether the current user is one of the environment's required reviewers
+    pub can_approve: bool,
+}
+
+/// A file uploaded by a workflow run with `actions/upload-artifact`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Artifact {
+    pub id: u64,
+    pub name: String,
+    pub size_in_bytes: u64,
+    pub expired: bool,
+    pub expires_at: Option<String>,
+    pub archive_download_url: String,
+}
+
+impl Artifact {
+    /// Size for display: "812 B", "4.2 MB"
+    pub fn size(&self) -> String {
+        const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+        if self.size_in_bytes < 1024 {
+            return format!("{} B", self.size_in_bytes);
         }
+        let mut size = self.size_in_bytes as f64 / 1024.0;
+        let mut unit = 0;
+        while size >= 1024.0 && unit < UNITS.len() - 1 {
+            size /= 1024.0;
+            unit += 1;
+        }
+        format!("{:.1} {}", size, UNITS[unit])
     }
 }
 
-impl WorkflowRun {
+/// Parse a GitHub timestamp - RFC 3339 from the REST API, or chrono's
+/// `Display` format ("2024-01-31 12:00:00 UTC") from octocrab models
+pub fn parse_timestamp(s: &str) -> Option<DateTime<Utc>> {
+    if let Ok(t) = DateTime::parse_from_rfc3339(s) {
+        return Some(t.with_timezone(&Utc));
+    }
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f UTC")
+        .ok()
+        .map(|t| t.and_utc())
+}
+
+/// Format seconds compactly: "45s", "2m14s", "1h03m"
+pub fn format_duration(secs: i64) -> String {
+    if secs < 0 {
+        return "-".to_string();
+    }
+    let (h, m, s) = (secs / 3600, (secs % 3600) / 60, secs % 60);
+    if h > 0 {
+        format!("{}h{:02}m", h, m)
+    } else if m > 0 {
+        format!("{}m{:02}s", m, s)
+    } else {
+        format!("{}s", s)
+    }
+}
+
+/// Aggregate the checks of a commit into a single CI status.
+/// Any failure wins, then anything still running, then cancellations; otherwise success.
+pub fn aggregate_ci_status(runs: &[Check]) -> Option<String> {
+    if runs.is_empty() {
+        return None;
+    }
+
+    let status = if runs.iter().any(|r| matches!(r.conclusion.as_deref(), Some("failure") | Some("timed_out") | Some("startup_failure"))) {
+        "failure"
+    } else if runs.iter().any(|r| r.conclusion.is_none()) {
+        "pending"
+    } else if runs.iter().any(|r| r.conclusion.as_deref() == Some("cancelled")) {
+        "error"
+    } else {
+        "success"
+    };
+
+    Some(status.to_string())
+}
+
+/// A recently pushed branch that doesn't have an open PR
+#[derive(Debug, Clone)]
+pub struct RecentBranch {
+    pub name: String,
+    pub pushed_at: String,
+}
+
+/// A thread in the notification inbox
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub id: String,  // Thread ID, used to mark it as read
+    pub repo: String,  // owner/name
+    pub title: String,
+    pub kind: String,  // PullRequest, Issue, Release, CheckSuite, Discussion, ...
+    pub reason: String,  // review_requested, mention, author, ci_activity, ...
+    pub url: Option<String>,  // Web page of the subject, when it has one
+    pub updated_at: String,
+}
+
+impl Notification {
+    pub fn kind_icon(&self) -> &'static str {
+        match self.kind.as_str() {
+            "PullRequest" => "⇄",
+            "Issue" => "◉",
+            "Release" => "⚑",
+            "CheckSuite" => "⚙",
+            "Discussion" => "💬",
+            _ => "•",
+        }
+    }
+
+    /// The PR number, for pull request notifications
+    pub fn pr_number(&self) -> Option<u64> {
+        if self.kind != "PullRequest" {
+            return None;
+        }
+        self.url.as_deref()?.rsplit('/').next()?.parse().ok()
+    }
+}
+
+/// API quota from the `X-RateLimit-*` headers of the latest response
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimit {
+    pub limit: u64,
+    pub remaining: u64,
+    /// Unix timestamp at which the quota resets
+    pub reset: i64,
+}
+
+impl RateLimit {
+    /// Seconds until the quota resets, never negative
+    pub fn seconds_until_reset(&self) -> i64 {
+        (self.reset - Utc::now().timestamp()).max(0)
+    }
+}
+
+impl Step {
     pub fn status_icon(&self) -> &'static str {
         match self.conclusion.as_deref() {
             Some("success") => "✓",
@@ -163,14 +488,6 @@ impl WorkflowRun {
     }
 }
 
-/// A recently pushed branch that doesn't have an open PR
-#[derive(Debug, Clone)]
-pub struct RecentBranch {
-    pub name: String,
-    pub pushed_at: String,
-    pub minutes_ago: u64,
-}
-
 impl Job {
     pub fn status_icon(&self) -> &'static str {
         match self.conclusion.as_deref() {
@@ -186,13 +503,110 @@ impl Job {
         }
     }
 
+    /// Elapsed time for a running job, total time for a finished one
     pub fn duration(&self) -> String {
-        if self.completed_at.is_some() {
-            "completed".to_string()
-        } else if !self.started_at.is_empty() {
-            "running...".to_string()
-        } else {
-            "-".to_string()
+        // A queued job has a start time but hasn't started running
+        let start = match parse_timestamp(&self.started_at) {
+            Some(start) if self.status != "queued" => start,
+            _ => return "-".to_string(),
+        };
+        let end = match self.completed_at.as_deref() {
+            Some(end) => match parse_timestamp(end) {
+                Some(end) => end,
+                None => return "-".to_string(),
+            },
+            None => Utc::now(),
+        };
+        format_duration((end - start).num_seconds())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn run(status: &str, conclusion: Option<&str>) -> Check {
+        Check {
+            name: "CI".to_string(),
+            status: status.to_string(),
+            conclusion: conclusion.map(|c| c.to_string()),
+            html_url: String::new(),
+            source: "Actions".to_string(),
+            run: None,
         }
     }
+
+    fn review(login: &str, state: &str, submitted_at: &str) -> Review {
+        Review {
+            id: 1,
+            user: User { login: login.to_string(), avatar_url: String::new() },
+            state: state.to_string(),
+            submitted_at: Some(submitted_at.to_string()),
+        }
+    }
+
+    #[test]
+    fn comments_dont_replace_approvals_or_change_requests() {
+        let reviews = [
+            review("alice", "COMMENTED", "2024-01-03T00:00:00Z"),
+            review("alice", "APPROVED", "2024-01-02T00:00:00Z"),
+            review("bob", "CHANGES_REQUESTED", "2024-01-01T00:00:00Z"),
+            review("bob", "COMMENTED", "2024-01-02T00:00:00Z"),
+            review("carol", "COMMENTED", "2024-01-01T00:00:00Z"),
+            review("dave", "APPROVED", "2024-01-01T00:00:00Z"),
+            review("dave", "DISMISSED", "2024-01-02T00:00:00Z"),
+        ];
+        let states: Vec<(&str, &str)> = latest_reviews(&reviews)
+            .into_iter()
+            .map(|(login, r)| (login, r.state.as_str()))
+            .collect();
+        assert_eq!(states, [("alice", "APPROVED"), ("bob", "CHANGES_REQUESTED"), ("carol", "COMMENTED"), ("dave", "DISMISSED")]);
+    }
+
+    #[test]
+    fn formats_durations() {
+        assert_eq!(format_duration(0), "0s");
+        assert_eq!(format_duration(45), "45s");
+        assert_eq!(format_duration(134), "2m14s");
+        assert_eq!(format_duration(3600), "1h00m");
+        assert_eq!(format_duration(3780), "1h03m");
+        assert_eq!(format_duration(-5), "-");
+    }
+
+    #[test]
+    fn parses_both_timestamp_formats() {
+        let expected = Utc.with_ymd_and_hms(2024, 1, 31, 12, 0, 0).single();
+        assert_eq!(parse_timestamp("2024-01-31T12:00:00Z"), expected);
+        assert_eq!(parse_timestamp("2024-01-31T13:00:00+01:00"), expected);
+        assert_eq!(parse_timestamp("2024-01-31 12:00:00 UTC"), expected);
+    }
+
+    #[test]
+    fn rejects_invalid_timestamps() {
+        assert_eq!(parse_timestamp(""), None);
+        assert_eq!(parse_timestamp("yesterday"), None);
+        assert_eq!(parse_timestamp("2024-13-01T00:00:00Z"), None);
+    }
+
+    #[test]
+    fn no_runs_have_no_status() {
+        assert_eq!(aggregate_ci_status(&[]), None);
+    }
+
+    #[test]
+    fn failure_wins_over_running_and_cancelled() {
+        let runs = [run("completed", Some("success")), run("in_progress", None), run("completed", Some("timed_out"))];
+        assert_eq!(aggregate_ci_status(&runs).as_deref(), Some("failure"));
+        let runs = [run("completed", Some("cancelled")), run("queued", None)];
+        assert_eq!(aggregate_ci_status(&runs).as_deref(), Some("pending"));
+    }
+
+    #[test]
+    fn cancelled_is_an_error_and_the_rest_success() {
+        let runs = [run("completed", Some("success")), run("completed", Some("cancelled"))];
+        assert_eq!(aggregate_ci_status(&runs).as_deref(), Some("error"));
+        let runs = [run("completed", Some("success")), run("completed", Some("skipped"))];
+        assert_eq!(aggregate_ci_status(&runs).as_deref(), Some("success"));
+    }
 }
@@ -0,0 +1,503 @@
+use anyhow::{bail, Context, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Where a binding is active. Lookups check Global first (in `handle_key`),
+/// then the tab's own scope, then the Common navigation keys.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Scope {
+    Global,
+    Common,
+    Prs,
+    Diff,
+    Actions,
+    Logs,
+    Notifications,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Action {
+    // Global
+    Quit,
+    Help,
+    TabPrs,
+    TabActions,
+    TabLogs,
+    TabNotifications,
+    Refresh,
+    ToggleAutoRefresh,
+    NextTab,
+    PrevTab,
+    Command,
+    // Common navigation
+    NextItem,
+    PrevItem,
+    PageDown,
+    PageUp,
+    HalfPageDown,
+    HalfPageUp,
+    Select,
+    Back,
+    // PRs tab
+    FocusLeft,
+    FocusRight,
+    CycleFocus,
+    NewPr,
+    ToggleSelect,
+    ViewDiff,
+    Approve,
+    RequestChanges,
+    Comment,
+    Merge,
+    Checkout,
+    CloseReopen,
+    CycleFilter,
+    EditTitle,
+    EditDescription,
+    AddReviewer,
+    Assign,
+    AddLabel,
+    RemoveLabel,
+    OpenInBrowser,
+    CopyBranch,
+    CopyCheckout,
+    CopyUrl,
+    ToggleCommits,
+    CreatePrFromBranch,
+    PrevCommit,
+    NextCommit,
+    CyclePrSort,
+    // Actions tab
+    Dispatch,
+    DownloadArtifact,
+    FilterRunBranch,
+    CycleRunEvent,
+    ToggleSteps,
+    ApproveDeployment,
+    RejectDeployment,
+    // PRs and Actions tabs
+    Rerun,
+    ViewLogs,
+    // PRs and Logs tabs
+    NextUrl,
+    OpenUrl,
+    // Full diff
+    NextFile,
+    PrevFile,
+    ToggleDiffLayout,
+    ToggleWhitespace,
+    ToggleViewed,
+    LoadFullDiff,
+    CopyCommitSha,
+    CopyCommitMessage,
+    UpdateBranch,
+    FailureLogs,
+    ApproveWithComment,
+    Reviews,
+    ToggleAutoMerge,
+    // Logs tab
+    ScrollLeft,
+    ScrollRight,
+    Top,
+    Bottom,
+    LineStart,
+    ToggleColors,
+    ToggleProblems,
+    ToggleLineNumbers,
+    ToggleWrap,
+    SaveLogs,
+    SaveArchive,
+    Follow,
+    Search,
+    GotoLine,
+    NextMatch,
+    PrevMatch,
+    ToggleFold,
+    // Notifications tab
+    MarkRead,
+    ToggleAllRepos,
+}
+
+use Scope::*;
+
+/// Every action with its config name, scopes and default keys
+const ACTIONS: &[(Action, &str, &[Scope], &[&str])] = &[
+    (Action::Quit, "quit", &[Global], &["q"]),
+    (Action::Help, "help", &[Global], &["?"]),
+    (Action::TabPrs, "tab_prs", &[Global], &["1"]),
+    (Action::TabActions, "tab_actions", &[Global], &["2"]),
+    (Action::TabLogs, "tab_logs", &[Global], &["3"]),
+    (Action::TabNotifications, "tab_notifications", &[Global], &["4"]),
+    (Action::Refresh, "refresh", &[Global], &["r"]),
+    (Action::ToggleAutoRefresh, "toggle_auto_refresh", &[Global], &["W"]),
+    (Action::NextTab, "next_tab", &[Global], &["tab"]),
+    (Action::PrevTab, "prev_tab", &[Global], &["backtab"]),
+    (Action::Command, "command", &[Global], &[":"]),
+    (Action::NextItem, "next_item", &[Common], &["j", "down"]),
+    (Action::PrevItem, "prev_item", &[Common], &["k", "up"]),
+    (Action::PageDown, "page_down", &[Common], &["pagedown"]),
+    (Action::PageUp, "page_up", &[Common], &["pageup"]),
+    (Action::HalfPageDown, "half_page_down", &[Diff, Logs], &["ctrl-d"]),
+    (Action::HalfPageUp, "half_page_up", &[Diff, Logs], &["ctrl-u"]),
+    (Action::Select, "select", &[Common], &["enter"]),
+    (Action::Back, "back", &[Common], &["esc"]),
+    (Action::FocusLeft, "focus_left", &[Prs, Actions], &["h", "left"]),
+    (Action::FocusRight, "focus_right", &[Prs, Actions], &["l", "right"]),
+    (Action::CycleFocus, "cycle_focus", &[Prs], &["o"]),
+    (Action::NewPr, "new_pr", &[Prs], &["n"]),
+    (Action::ToggleSelect, "toggle_select", &[Prs], &["space"]),
+    (Action::ViewDiff, "view_diff", &[Prs], &["d"]),
+    (Action::Approve, "approve", &[Prs], &["v"]),
+    (Action::ApproveWithComment, "approve_with_comment", &[Prs], &["V"]),
+    (Action::Reviews, "reviews", &[Prs], &["alt-r"]),
+    (Action::RequestChanges, "request_changes", &[Prs], &["x"]),
+    (Action::Comment, "comment", &[Prs], &["c"]),
+    (Action::Merge, "merge", &[Prs], &["m"]),
+    (Action::Checkout, "checkout", &[Prs], &["C"]),
+    (Action::CloseReopen, "close_reopen", &[Prs], &["X"]),
+    (Action::CycleFilter, "cycle_filter", &[Prs, Actions], &["f"]),
+    (Action::UpdateBranch, "update_branch", &[Prs], &["S"]),
+    (Action::ToggleAutoMerge, "toggle_auto_merge", &[Prs], &["alt-m"]),
+    (Action::EditTitle, "edit_title", &[Prs], &["e"]),
+    (Action::EditDescription, "edit_description", &[Prs], &["E"]),
+    (Action::AddReviewer, "add_reviewer", &[Prs], &["a"]),
+    (Action::Assign, "assign", &[Prs], &["A"]),
+    (Action::AddLabel, "add_label", &[Prs], &["b"]),
+    (Action::RemoveLabel, "remove_label", &[Prs], &["B"]),
+    (Action::OpenInBrowser, "open_in_browser", &[Prs, Actions, Notifications], &["w"]),
+    (Action::CopyBranch, "copy_branch", &[Prs], &["y"]),
+    (Action::CopyCheckout, "copy_checkout", &[Prs], &["Y"]),
+    (Action::CopyUrl, "copy_url", &[Prs, Actions], &["u"]),
+    (Action::ToggleCommits, "toggle_commits", &[Prs], &["p"]),
+    (Action::CreatePrFromBranch, "create_pr_from_branch", &[Prs], &["P"]),
+    (Action::PrevCommit, "prev_commit", &[Prs], &["["]),
+    (Action::NextCommit, "next_commit", &[Prs], &["]"]),
+    (Action::CopyCommitSha, "copy_commit_sha", &[Prs], &["H"]),
+    (Action::CopyCommitMessage, "copy_commit_message", &[Prs], &["M"]),
+    (Action::CyclePrSort, "cycle_pr_sort", &[Prs], &["s"]),
+    (Action::Dispatch, "dispatch", &[Actions], &["D"]),
+    (Action::DownloadArtifact, "download_artifact", &[Actions], &["s"]),
+    (Action::FilterRunBranch, "filter_run_branch", &[Actions], &["b"]),
+    (Action::CycleRunEvent, "cycle_run_event", &[Actions], &["e"]),
+    (Action::ToggleSteps, "toggle_steps", &[Actions], &["space"]),
+    (Action::ApproveDeployment, "approve_deployment", &[Actions], &["a"]),
+    (Action::RejectDeployment, "reject_deployment", &[Actions], &["x"]),
+    (Action::Rerun, "rerun", &[Prs, Actions], &["R"]),
+    (Action::ViewLogs, "view_logs", &[Prs, Actions], &["L"]),
+    (Action::FailureLogs, "failure_logs", &[Prs], &["F"]),
+    (Action::NextUrl, "next_url", &[Prs, Logs], &["U"]),
+    (Action::OpenUrl, "open_url", &[Prs, Logs], &["O"]),
+    (Action::NextFile, "next_file", &[Diff], &["}", "]"]),
+    (Action::PrevFile, "prev_file", &[Diff], &["{", "["]),
+    (Action::ToggleDiffLayout, "toggle_split", &[Diff], &["s"]),
+    (Action::ToggleWhitespace, "toggle_whitespace", &[Prs, Diff], &["i"]),
+    (Action::ToggleViewed, "toggle_viewed", &[Diff], &["v"]),
+    (Action::LoadFullDiff, "load_full_diff", &[Prs, Diff], &["D"]),
+    (Action::ScrollLeft, "scroll_left", &[Logs], &["h"]),
+    (Action::ScrollRight, "scroll_right", &[Logs], &["l"]),
+    (Action::Top, "top", &[Logs], &["g"]),
+    (Action::Bottom, "bottom", &[Logs], &["G"]),
+    (Action::LineStart, "line_start", &[Logs], &["0"]),
+    (Action::ToggleColors, "toggle_colors", &[Logs], &["c"]),
+    (Action::ToggleProblems, "toggle_problems", &[Logs], &["e"]),
+    (Action::ToggleLineNumbers, "toggle_line_numbers", &[Logs], &["L"]),
+    (Action::ToggleWrap, "toggle_wrap", &[Logs], &["w"]),
+    (Action::SaveLogs, "save_logs", &[Logs], &["s"]),
+    (Action::SaveArchive, "save_archive", &[Logs], &["S"]),
+    (Action::Follow, "follow", &[Logs], &["f"]),
+    (Action::Search, "search", &[Prs, Diff, Logs], &["/"]),
+    (Action::GotoLine, "goto_line", &[Logs], &["#"]),
+    (Action::NextMatch, "next_match", &[Diff, Logs], &["n"]),
+    (Action::PrevMatch, "prev_match", &[Diff, Logs], &["N"]),
+    (Action::ToggleFold, "toggle_fold", &[Logs], &["enter"]),
+    (Action::MarkRead, "mark_read", &[Notifications], &["m"]),
+    (Action::ToggleAllRepos, "toggle_all_repos", &[Notifications], &["a"]),
+];
+
+/// A key plus modifiers, e.g. `j`, `G`, `ctrl-d`, `enter`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    pub fn parse(s: &str) -> Result<Self> {
+        let mut rest = s;
+        let mut modifiers = KeyModifiers::NONE;
+        loop {
+            let lower = rest.to_ascii_lowercase();
+            if let Some(prefix) = ["ctrl-", "alt-", "shift-"].iter().find(|p| lower.starts_with(*p) && lower.len() > p.len()) {
+                modifiers |= match *prefix {
+                    "ctrl-" => KeyModifiers::CONTROL,
+                    "alt-" => KeyModifiers::ALT,
+                    _ => KeyModifiers::SHIFT,
+                };
+                rest = &rest[prefix.len()..];
+            } else {
+                break;
+            }
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match rest.to_ascii_lowercase().as_str() {
+                "enter" => KeyCode::Enter,
+                "esc" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "space" => KeyCode::Char(' '),
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "pageup" | "pgup" => KeyCode::PageUp,
+                "pagedown" | "pgdn" => KeyCode::PageDown,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                f if f.starts_with('f') => match f[1..].parse::<u8>() {
+                    Ok(n @ 1..=12) => KeyCode::F(n),
+                    _ => bail!("Unknown key '{}'", s),
+                },
+                _ => bail!("Unknown key '{}'", s),
+            },
+        };
+
+        Ok(Self::normalize(code, modifiers))
+    }
+
+    fn from_event(key: &KeyEvent) -> Self {
+        Self::normalize(key.code, key.modifiers)
+    }
+
+    /// Terminals report `G` as shift+`G` and Shift+Tab as shift+BackTab; fold the
+    /// shift into the key so `G`, `shift-g` and the actual keypress all compare equal
+    fn normalize(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let shifted = modifiers.contains(KeyModifiers::SHIFT);
+        let code = match code {
+            KeyCode::Char(c) if shifted => KeyCode::Char(c.to_ascii_uppercase()),
+            KeyCode::Tab if shifted => KeyCode::BackTab,
+            code => code,
+        };
+        let modifiers = match code {
+            KeyCode::Char(_) | KeyCode::BackTab => modifiers - KeyModifiers::SHIFT,
+            _ => modifiers,
+        };
+        Self { code, modifiers }
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::BackTab => write!(f, "Shift+Tab"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Delete => write!(f, "Del"),
+            KeyCode::Up => write!(f, "Up"),
+            KeyCode::Down => write!(f, "Down"),
+            KeyCode::Left => write!(f, "Left"),
+            KeyCode::Right => write!(f, "Right"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            code => write!(f, "{:?}", code),
+        }
+    }
+}
+
+/// A config value for one action: a single key or a list of keys
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// Keys bound to each action - the built-in defaults with `[keys]` from the
+/// config file applied on top
+#[derive(Clone)]
+pub struct KeyMap {
+    bindings: HashMap<Action, Vec<KeyBinding>>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        let bindings = ACTIONS
+            .iter()
+            .map(|(action, _, _, keys)| {
+                let keys = keys
+                    .iter()
+                    .map(|k| KeyBinding::parse(k).expect("default key bindings are valid"))
+                    .collect();
+                (*action, keys)
+            })
+            .collect();
+        Self { bindings }
+    }
+}
+
+impl KeyMap {
+    /// Apply `[keys]` overrides. Each listed action gets exactly the given keys.
+    /// An override that collides with another action's key in the same view is
+    /// rejected instead of silently shadowing one of them.
+    pub fn from_config(overrides: &HashMap<String, KeyList>) -> Result<Self> {
+        let mut keymap = Self::default();
+
+        for (name, keys) in overrides {
+            let (action, ..) = ACTIONS
+                .iter()
+                .find(|(_, n, _, _)| n == name)
+                .with_context(|| format!("Unknown action '{}' in [keys]", name))?;
+            let keys = match keys {
+                KeyList::One(key) => vec![key.clone()],
+                KeyList::Many(keys) => keys.clone(),
+            };
+            let keys = keys
+                .iter()
+                .map(|k| KeyBinding::parse(k).with_context(|| format!("Invalid key for '{}'", name)))
+                .collect::<Result<Vec<_>>>()?;
+            keymap.bindings.insert(*action, keys);
+        }
+
+        for name in overrides.keys() {
+            let (action, _, scopes, _) = ACTIONS.iter().find(|(_, n, _, _)| n == name).expect("checked above");
+            for (other, other_name, other_scopes, _) in ACTIONS {
+                if other == action || !scopes_overlap(scopes, other_scopes) {
+                    continue;
+                }
+                if let Some(key) = keymap.keys(*action).iter().find(|k| keymap.keys(*other).contains(k)) {
+                    bail!("Key '{}' for '{}' is also bound to '{}' - rebind one of them in [keys]", key, name, other_name);
+                }
+            }
+        }
+
+        Ok(keymap)
+    }
+
+    /// The action `key` triggers in `scope`. Scoped actions win over Common ones,
+    /// so e.g. Enter toggles a fold in the Logs tab rather than selecting.
+    pub fn action(&self, key: &KeyEvent, scope: Scope) -> Option<Action> {
+        let pressed = KeyBinding::from_event(key);
+        let find = |scope: Scope| {
+            ACTIONS
+                .iter()
+                .filter(|(_, _, scopes, _)| scopes.contains(&scope))
+                .find(|(action, ..)| self.keys(*action).contains(&pressed))
+                .map(|(action, ..)| *action)
+        };
+        match scope {
+            Global | Common => find(scope),
+            _ => find(scope).or_else(|| find(Common)),
+        }
+    }
+
+    pub fn keys(&self, action: Action) -> &[KeyBinding] {
+        self.bindings.get(&action).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Key hint for a footer or pane title, e.g. "j/k:scroll"
+    pub fn hint(&self, actions: &[Action], what: &str) -> String {
+        format!("{}:{}", self.label(actions), what)
+    }
+
+    /// Help label for one or more actions: the first key of each, e.g. "j/k"
+    pub fn label(&self, actions: &[Action]) -> String {
+        actions
+            .iter()
+            .map(|&a| self.keys(a).first().map(|k| k.to_string()).unwrap_or_else(|| "-".to_string()))
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+/// Whether two actions can receive the same keypress
+fn scopes_overlap(a: &[Scope], b: &[Scope]) -> bool {
+    a.iter().any(|s| *s == Global || *s == Common || b.contains(s))
+        || b.iter().any(|s| *s == Global || *s == Common)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyBinding {
+        KeyBinding { code, modifiers }
+    }
+
+    fn overrides(pairs: &[(&str, &str)]) -> HashMap<String, KeyList> {
+        pairs.iter().map(|(name, k)| (name.to_string(), KeyList::One(k.to_string()))).collect()
+    }
+
+    #[test]
+    fn parses_keys_and_modifiers() {
+        assert_eq!(KeyBinding::parse("j").unwrap(), key(KeyCode::Char('j'), KeyModifiers::NONE));
+        assert_eq!(KeyBinding::parse("ctrl-d").unwrap(), key(KeyCode::Char('d'), KeyModifiers::CONTROL));
+        assert_eq!(
+            KeyBinding::parse("Ctrl-Alt-x").unwrap(),
+            key(KeyCode::Char('x'), KeyModifiers::CONTROL | KeyModifiers::ALT)
+        );
+        assert_eq!(KeyBinding::parse("enter").unwrap(), key(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(KeyBinding::parse("space").unwrap(), key(KeyCode::Char(' '), KeyModifiers::NONE));
+        assert_eq!(KeyBinding::parse("F5").unwrap(), key(KeyCode::F(5), KeyModifiers::NONE));
+        // A lone "-" is a key, not an empty modifier prefix
+        assert_eq!(KeyBinding::parse("-").unwrap(), key(KeyCode::Char('-'), KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn shift_folds_into_the_key() {
+        assert_eq!(KeyBinding::parse("shift-g").unwrap(), KeyBinding::parse("G").unwrap());
+        assert_eq!(KeyBinding::parse("shift-tab").unwrap(), KeyBinding::parse("backtab").unwrap());
+        let pressed = KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT);
+        assert_eq!(KeyBinding::from_event(&pressed), KeyBinding::parse("G").unwrap());
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        assert!(KeyBinding::parse("").is_err());
+        assert!(KeyBinding::parse("ctrl-").is_err());
+        assert!(KeyBinding::parse("f13").is_err());
+        assert!(KeyBinding::parse("hyper-x").is_err());
+    }
+
+    #[test]
+    fn overrides_replace_the_default_keys() {
+        let keymap = KeyMap::from_config(&overrides(&[("approve", "alt-v")])).unwrap();
+        let alt_v = KeyEvent::new(KeyCode::Char('v'), KeyModifiers::ALT);
+        let v = KeyEvent::new(KeyCode::Char('v'), KeyModifiers::NONE);
+        assert_eq!(keymap.action(&alt_v, Scope::Prs), Some(Action::Approve));
+        assert_eq!(keymap.action(&v, Scope::Prs), None);
+        assert_eq!(keymap.hint(&[Action::Approve], "approve"), "Alt+v:approve");
+    }
+
+    #[test]
+    fn rejects_conflicting_overrides() {
+        // `m` merges in the PRs tab
+        assert!(KeyMap::from_config(&overrides(&[("approve", "m")])).is_err());
+        // Global keys clash with every scope
+        assert!(KeyMap::from_config(&overrides(&[("follow", "q")])).is_err());
+        // The same key in views that never overlap is fine
+        assert!(KeyMap::from_config(&overrides(&[("mark_read", "v")])).is_ok());
+        assert!(KeyMap::from_config(&overrides(&[("no_such_action", "z")])).is_err());
+    }
+}
@@ -1,6 +1,8 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Parser;
 use crossterm::{
+    cursor,
+    event::{DisableMouseCapture, EnableMouseCapture},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -8,50 +10,122 @@ use ratatui::prelude::*;
 use std::io::stdout;
 
 mod app;
+mod config;
 mod event;
+mod git;
 mod github;
+mod keymap;
+mod state;
 mod ui;
+mod util;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about = "A terminal UI for GitHub workflows")]
 struct Args {
-    /// Repository in format owner/repo
+    /// Repository in format owner/repo (defaults to the git remote, then `default_repo` in the config)
     #[arg(short, long)]
     repo: Option<String>,
 
     /// PR number or URL to pre-select (e.g., 123 or https://github.com/owner/repo/pull/123)
     #[arg(long)]
     pr: Option<String>,
+
+    /// GitHub API base URL for GitHub Enterprise (e.g., https://ghe.example.com/api/v3).
+    /// Falls back to the GITHUB_API_URL env var, then the public API
+    #[arg(long)]
+    github_host: Option<String>,
+
+    /// How a fetch in progress is shown; overrides `loading_style` in the config
+    #[arg(long, value_enum)]
+    loading_style: Option<config::LoadingStyle>,
+
+    /// Idle event-loop tick in milliseconds (10-5000); overrides `tick_ms` in the config.
+    /// Slower ticks wake less often; loading animations still tick at 100ms at most
+    #[arg(long, value_parser = clap::value_parser!(u64).range(10..=5000))]
+    tick_ms: Option<u64>,
+
+    /// Prompt for a personal access token and store it in the system keyring, then exit
+    #[arg(long)]
+    login: bool,
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let args = Args::parse();
 
+    // Restore the terminal before the default hook prints the panic message
+    install_panic_hook();
+
+    if args.login {
+        return login().await;
+    }
+
+    let mut config = config::Config::load()?;
+    if let Some(style) = args.loading_style {
+        config.loading_style = style;
+    }
+    if let Some(ms) = args.tick_ms {
+        config.tick_ms = Some(ms);
+    }
+    let keymap = keymap::KeyMap::from_config(&config.keys)?;
+    let theme = ui::Theme::from_config(&config.theme)?;
+
     // Parse PR argument - can be number or URL
-    let (repo_from_pr, pr_number) = parse_pr_arg(&args.pr);
-
-    // Detect repo from git remote if not provided
-    let repo = match args.repo {
-        Some(r) => r,
-        None => repo_from_pr.unwrap_or_else(|| {
-            detect_repo().unwrap_or_else(|| "shopsys/shopsys".to_string())
-        }),
+    let (repo_from_pr, pr_number) = match args.pr.as_deref() {
+        Some(arg) => {
+            let (repo, number) = parse_pr_arg(arg)
+                .with_context(|| format!("Invalid --pr '{}', expected a PR number or URL", arg))?;
+            (repo, Some(number))
+        }
+        None => (None, None),
     };
 
-    // Setup terminal
-    enable_raw_mode()?;
-    execute!(stdout(), EnterAlternateScreen)?;
+    // Repo precedence: --repo, then --pr URL, then the git remote, then the last
+    // session's repo, then the config
+    let saved = state::State::load();
+    let api_base = args.github_host.or_else(|| std::env::var("GITHUB_API_URL").ok());
+    let repo = args
+        .repo
+        .or(repo_from_pr)
+        .or_else(|| git::detect_repo(api_base.as_deref()))
+        .or_else(|| saved.repo.clone())
+        .or_else(|| config.default_repo.clone())
+        .context(
+            "Could not determine the repository. Pass --repo owner/repo, run inside a clone \
+             with a GitHub `upstream` or `origin` remote, or set default_repo in ~/.config/github-tui/config.toml",
+        )?;
+    if !util::is_valid_repo(&repo) {
+        anyhow::bail!("Invalid repository '{}', expected owner/repo", repo);
+    }
+
+    // Setup terminal (restored when the guard is dropped, however run() exits)
+    let guard = TerminalGuard::new()?;
     let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
 
     // Create and run app
     let mut app = app::App::new(repo);
     app.initial_pr = pr_number;
+    // A PR to pre-select opens on the PRs tab, unfiltered so it's in the list
+    if pr_number.is_none() {
+        app.tab = saved.tab.unwrap_or_default();
+        app.pr_filter = saved.pr_filter.unwrap_or_default();
+    }
+    app.api_base = api_base;
+    app.config = config;
+    app.keymap = keymap;
+    app.theme = theme;
     let result = app.run(&mut terminal).await;
 
-    // Restore terminal
-    disable_raw_mode()?;
-    execute!(stdout(), LeaveAlternateScreen)?;
+    // The Logs tab would open empty, so the next session starts on Actions
+    state::State {
+        repo: Some(app.repo.clone()),
+        tab: Some(if app.tab == app::Tab::Logs { app::Tab::Actions } else { app.tab }),
+        pr_filter: Some(app.pr_filter),
+    }
+    .save();
+
+    // Restore terminal before printing any error
+    drop(guard);
 
     if let Err(ref e) = result {
         eprintln!("Error: {}", e);
@@ -60,68 +134,180 @@ async fn main() -> Result<()> {
     result
 }
 
-/// Parse PR argument which can be a number or a GitHub PR URL
-/// Returns (optional_repo, optional_pr_number)
-fn parse_pr_arg(pr_arg: &Option<String>) -> (Option<String>, Option<u64>) {
-    let pr_str = match pr_arg {
-        Some(s) => s,
-        None => return (None, None),
-    };
+/// Puts the terminal into raw mode + alternate screen and restores it on drop
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn new() -> Result<Self> {
+        // Guard first, so a failure below still restores the terminal on drop
+        let guard = Self;
+        enable_raw_mode()?;
+        execute!(stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
+/// Leave raw mode and the alternate screen and show the cursor again,
+/// ignoring errors (best effort)
+fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(stdout(), DisableMouseCapture, LeaveAlternateScreen, cursor::Show);
+}
+
+/// Chain a panic hook that restores the terminal so the panic message is readable.
+/// Exits afterwards: a panic in a spawned task would otherwise leave the app
+/// drawing on a terminal that is no longer in raw mode.
+fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        restore_terminal();
+        default_hook(info);
+        std::process::exit(101);
+    }));
+}
+
+/// Read a token without echoing it and save it in the system keyring
+async fn login() -> Result<()> {
+    use crossterm::event::{read, DisableBracketedPaste, EnableBracketedPaste, Event, KeyCode, KeyEventKind, KeyModifiers};
+    use std::io::Write;
 
-    // Try parsing as a number first
-    if let Ok(num) = pr_str.parse::<u64>() {
-        return (None, Some(num));
-    }
-
-    // Try parsing as a GitHub PR URL
-    // Format: https://github.com/owner/repo/pull/123
-    if pr_str.contains("github.com") && pr_str.contains("/pull/") {
-        let parts: Vec<&str> = pr_str.split('/').collect();
-        // Find the index of "pull" and get the number after it
-        if let Some(pull_idx) = parts.iter().position(|&p| p == "pull") {
-            if let Some(num_str) = parts.get(pull_idx + 1) {
-                if let Ok(num) = num_str.parse::<u64>() {
-                    // Extract owner/repo
-                    if let (Some(owner_idx), Some(repo_idx)) = (
-                        parts.iter().position(|&p| p == "github.com").map(|i| i + 1),
-                        parts.iter().position(|&p| p == "github.com").map(|i| i + 2),
-                    ) {
-                        if let (Some(owner), Some(repo)) = (parts.get(owner_idx), parts.get(repo_idx)) {
-                            return (Some(format!("{}/{}", owner, repo)), Some(num));
-                        }
-                    }
-                    return (None, Some(num));
+    print!("GitHub personal access token: ");
+    stdout().flush()?;
+
+    // Raw mode keeps the token off the screen; restore it before reporting anything.
+    // Bracketed paste delivers a pasted token as one event, trailing newline and all.
+    enable_raw_mode()?;
+    let _ = execute!(stdout(), EnableBracketedPaste);
+    let mut token = String::new();
+    let result = loop {
+        match read() {
+            Ok(Event::Key(key)) if key.kind != KeyEventKind::Release => match key.code {
+                KeyCode::Enter => break Ok(()),
+                KeyCode::Esc => break Err(anyhow::anyhow!("Login cancelled")),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    break Err(anyhow::anyhow!("Login cancelled"))
+                }
+                KeyCode::Backspace => {
+                    token.pop();
                 }
-            }
+                KeyCode::Char(c) => token.push(c),
+                _ => {}
+            },
+            Ok(Event::Paste(text)) => token.push_str(&text),
+            Ok(_) => {}
+            Err(e) => break Err(e.into()),
         }
+    };
+    let _ = execute!(stdout(), DisableBracketedPaste);
+    let _ = disable_raw_mode();
+    println!();
+    result?;
+
+    let token = token.trim().to_string();
+    if token.is_empty() {
+        anyhow::bail!("No token entered");
     }
+    tokio::task::spawn_blocking(move || github::Client::store_keyring_token(&token)).await??;
+    println!("Token saved to the system keyring (service \"github-tui\")");
+    Ok(())
+}
 
-    (None, None)
+/// Parse a PR argument: a number (`123`, `#123`) or a PR URL on github.com or an
+/// Enterprise host (`https://host/owner/repo/pull/123`, optionally followed by
+/// `/files`, a query or a fragment). Returns the URL's `owner/repo` and the number.
+fn parse_pr_arg(arg: &str) -> Option<(Option<String>, u64)> {
+    let arg = arg.trim();
+    if let Ok(number) = arg.trim_start_matches('#').parse::<u64>() {
+        return Some((None, number));
+    }
+
+    let url = arg.split_once("://").map_or(arg, |(_, rest)| rest);
+    let url = url.split(['?', '#']).next().unwrap_or(url);
+    // host / owner / repo / pull / number [/ files ...]
+    let segments: Vec<&str> = url.split('/').filter(|s| !s.is_empty()).collect();
+    match segments.as_slice() {
+        [_host, owner, repo, "pull" | "pulls", number, ..] => {
+            let number = number.parse::<u64>().ok()?;
+            Some((Some(format!("{}/{}", owner, repo)), number))
+        }
+        _ => None,
+    }
 }
 
-fn detect_repo() -> Option<String> {
-    let output = std::process::Command::new("git")
-        .args(["remote", "get-url", "origin"])
-        .output()
-        .ok()?;
-
-    if !output.status.success() {
-        return None;
-    }
-
-    let url = String::from_utf8(output.stdout).ok()?;
-    let url = url.trim();
-
-    // Parse GitHub URL formats:
-    // git@github.com:owner/repo.git
-    // https://github.com/owner/repo.git
-    if url.contains("github.com") {
-        let repo = url
-            .trim_start_matches("git@github.com:")
-            .trim_start_matches("https://github.com/")
-            .trim_end_matches(".git");
-        Some(repo.to_string())
-    } else {
-        None
+#[cfg(test)]
+mod tests {
+    use super::parse_pr_arg;
+
+    fn repo(s: &str) -> Option<String> {
+        Some(s.to_string())
+    }
+
+    #[test]
+    fn bare_number() {
+        assert_eq!(parse_pr_arg("123"), Some((None, 123)));
+        assert_eq!(parse_pr_arg("#123"), Some((None, 123)));
+        assert_eq!(parse_pr_arg(" 42 "), Some((None, 42)));
+    }
+
+    #[test]
+    fn full_pr_url() {
+        assert_eq!(
+            parse_pr_arg("https://github.com/owner/repo/pull/123"),
+            Some((repo("owner/repo"), 123))
+        );
+        assert_eq!(parse_pr_arg("github.com/owner/repo/pull/7/"), Some((repo("owner/repo"), 7)));
+    }
+
+    #[test]
+    fn url_with_trailing_path_or_query() {
+        assert_eq!(
+            parse_pr_arg("https://github.com/owner/repo/pull/123/files"),
+            Some((repo("owner/repo"), 123))
+        );
+        assert_eq!(
+            parse_pr_arg("https://github.com/owner/repo/pull/123/commits/abc123"),
+            Some((repo("owner/repo"), 123))
+        );
+        assert_eq!(
+            parse_pr_arg("https://github.com/owner/repo/pull/123?diff=split"),
+            Some((repo("owner/repo"), 123))
+        );
+        assert_eq!(
+            parse_pr_arg("https://github.com/owner/repo/pull/123#issuecomment-1"),
+            Some((repo("owner/repo"), 123))
+        );
+    }
+
+    #[test]
+    fn enterprise_host() {
+        assert_eq!(
+            parse_pr_arg("https://ghe.example.com/team/service/pull/9"),
+            Some((repo("team/service"), 9))
+        );
+    }
+
+    #[test]
+    fn repo_named_pull() {
+        assert_eq!(
+            parse_pr_arg("https://github.com/owner/pull/pull/5"),
+            Some((repo("owner/pull"), 5))
+        );
+    }
+
+    #[test]
+    fn malformed_input() {
+        assert_eq!(parse_pr_arg(""), None);
+        assert_eq!(parse_pr_arg("abc"), None);
+        assert_eq!(parse_pr_arg("-1"), None);
+        assert_eq!(parse_pr_arg("https://github.com/owner/repo"), None);
+        assert_eq!(parse_pr_arg("https://github.com/owner/repo/pull/"), None);
+        assert_eq!(parse_pr_arg("https://github.com/owner/repo/pull/abc"), None);
+        assert_eq!(parse_pr_arg("https://github.com/owner/repo/issues/123"), None);
     }
 }
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::app::{PrFilter, Tab};
+
+/// Where the last session left off, kept in `<cache dir>/github-tui/state.toml`:
+///
+/// ```toml
+/// repo = "owner/repo"
+/// tab = "actions"
+/// pr_filter = "review_requested"
+/// ```
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct State {
+    pub repo: Option<String>,
+    pub tab: Option<Tab>,
+    pub pr_filter: Option<PrFilter>,
+}
+
+impl State {
+    pub fn path() -> Option<PathBuf> {
+        dirs::cache_dir().map(|dir| dir.join("github-tui/state.toml"))
+    }
+
+    /// The saved state. A missing or unreadable file is ignored, as the state
+    /// only saves a few keystrokes.
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write the state, best effort
+    pub fn save(&self) {
+        let (Some(path), Ok(content)) = (Self::path(), toml::to_string(self)) else {
+            return;
+        };
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        let _ = std::fs::write(path, content);
+    }
+}
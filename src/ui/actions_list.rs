@@ -6,15 +6,19 @@ use ratatui::{
 };
 
 use crate::app::App;
+use crate::keymap::Action;
+use crate::util::relative_time;
 
-use super::styles;
+use super::text::truncate;
 
-pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
+    app.mouse_areas.runs = area;
+    let theme = &app.theme;
     let items: Vec<ListItem> = app
         .runs
         .iter()
         .map(|run| {
-            let status_style = styles::status_style(&run.status, run.conclusion.as_deref());
+            let status_style = theme.status_style(&run.status, run.conclusion.as_deref());
 
             let conclusion_text = run.conclusion.as_deref().unwrap_or(&run.status);
 
@@ -23,38 +27,44 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
                 Span::raw(" "),
                 Span::styled(
                     truncate(&run.name, 30),
-                    styles::TEXT_NORMAL,
+                    theme.text_normal,
                 ),
                 Span::raw(" "),
-                Span::styled(format!("#{}", run.run_number), styles::TEXT_DIM),
+                Span::styled(format!("#{}", run.run_number), theme.text_dim),
                 Span::raw(" "),
-                Span::styled(&run.head_branch, styles::TEXT_DIM),
+                Span::styled(&run.head_branch, theme.text_dim),
                 Span::raw(" "),
                 Span::styled(conclusion_text, status_style),
+                Span::raw(" "),
+                Span::styled(run.duration(), theme.text_dim),
+                Span::raw(" "),
+                Span::styled(relative_time(&run.created_at), theme.text_dim),
             ]);
 
             ListItem::new(line)
         })
         .collect();
 
+    let filter = if app.run_filter.is_active() {
+        format!(" ({})", app.run_filter.label())
+    } else {
+        String::new()
+    };
+    let rerun = app.keymap.hint(&[Action::Rerun], "rerun");
+    let title = if app.runs_loading_more {
+        format!(" Workflow Runs{} [{}] (loading more...) ", filter, rerun)
+    } else {
+        format!(" Workflow Runs{} [{}] ", filter, rerun)
+    };
+
     let list = List::new(items)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(styles::BORDER_ACTIVE)
-                .title(" Workflow Runs [R:rerun] "),
+                .border_style(theme.border_active)
+                .title(title),
         )
-        .highlight_style(styles::SELECTED);
-
-    frame.render_stateful_widget(list, area, &mut app.run_list_state.clone());
-}
+        .highlight_style(theme.selected);
 
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
-    } else if max_len > 3 {
-        format!("{}...", &s[..max_len - 3])
-    } else {
-        s[..max_len].to_string()
-    }
+    frame.render_stateful_widget(list, area, &mut app.run_list_state);
 }
@@ -6,176 +6,128 @@ use ratatui::{
 };
 
 use crate::app::App;
+use crate::keymap::Action;
 
-use super::styles;
+use super::styles::Theme;
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let theme = &app.theme;
+    // Key labels come from the active keymap so rebound keys show up here
+    let k = |actions: &[Action]| app.keymap.label(actions);
 
-pub fn render(frame: &mut Frame, _app: &App) {
     let help_text = vec![
-        Line::from(Span::styled("Global Keys", styles::TEXT_BOLD)),
+        Line::from(Span::styled("Global Keys", theme.text_bold)),
+        Line::from(""),
+        entry(theme, k(&[Action::NextTab]), format!("Next tab / {}: Previous tab", k(&[Action::PrevTab]))),
+        entry(
+            theme,
+            k(&[Action::TabPrs, Action::TabActions, Action::TabLogs, Action::TabNotifications]),
+            "Jump to tab (PRs/Actions/Logs/Inbox)",
+        ),
+        entry(theme, k(&[Action::Refresh]), "Refresh current view"),
+        entry(theme, format!("50{}", k(&[Action::NextItem])), "Count prefix: repeat a motion 50 times"),
+        entry(theme, k(&[Action::Command]), "Command: 1234 / pr 1234 / repo owner/name"),
+        entry(theme, k(&[Action::ToggleAutoRefresh]), "Toggle auto-refresh of running workflows"),
+        entry(theme, k(&[Action::Help]), "Toggle help"),
+        entry(theme, k(&[Action::Back]), "Dismiss an error, or cancel loading"),
+        entry(theme, k(&[Action::Quit]), "Quit"),
+        Line::from(""),
+        Line::from(Span::styled("PRs Tab", theme.text_bold)),
         Line::from(""),
-        Line::from(vec![
-            Span::styled("  Tab      ", styles::TAB_ACTIVE),
-            Span::styled("Next tab / Shift+Tab: Previous tab", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  1/2/3    ", styles::TAB_ACTIVE),
-            Span::styled("Jump to tab (PRs/Actions/Logs)", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  r        ", styles::TAB_ACTIVE),
-            Span::styled("Refresh current view", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  ?        ", styles::TAB_ACTIVE),
-            Span::styled("Toggle help", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  q        ", styles::TAB_ACTIVE),
-            Span::styled("Quit", styles::TEXT_NORMAL),
-        ]),
+        entry(theme, k(&[Action::NextItem, Action::PrevItem]), "Navigate list / scroll diff"),
+        entry(theme, k(&[Action::FocusLeft, Action::FocusRight]), "Switch between list and detail panels"),
+        entry(theme, k(&[Action::Select]), "View PR details / fold a workflow in CI checks"),
+        entry(theme, k(&[Action::ViewDiff]), "View full diff"),
+        entry(theme, k(&[Action::PrevFile, Action::NextFile]), "Previous/next file (in full diff)"),
+        entry(theme, k(&[Action::HalfPageDown, Action::HalfPageUp]), "Half page down/up (in full diff)"),
+        entry(theme, k(&[Action::ToggleDiffLayout]), "Toggle side-by-side diff (in full diff)"),
+        entry(theme, k(&[Action::ToggleWhitespace]), "Hide/show whitespace-only changes in diffs"),
+        entry(theme, k(&[Action::ToggleViewed]), "Mark file as viewed and go to next (in full diff)"),
+        entry(theme, k(&[Action::LoadFullDiff]), "Load a large diff that was held back"),
+        entry(theme, k(&[Action::NextMatch, Action::PrevMatch]), "Next/previous diff match (in full diff)"),
+        entry(theme, k(&[Action::ToggleSelect]), "Select PR for batch approve/merge"),
+        entry(theme, k(&[Action::Approve]), "Approve PR (or selected PRs)"),
+        entry(theme, k(&[Action::ApproveWithComment]), "Approve PR with a comment"),
+        entry(theme, k(&[Action::Reviews]), "Reviews: d dismiss, r re-request"),
+        entry(theme, k(&[Action::RequestChanges]), "Request changes"),
+        entry(theme, k(&[Action::Comment]), "Add comment"),
+        entry(theme, k(&[Action::Merge]), "Merge PR or selected PRs (confirm with y)"),
+        entry(theme, k(&[Action::ToggleAutoMerge]), "Enable (confirm with y) / disable auto-merge"),
+        entry(theme, k(&[Action::Checkout]), "Checkout PR branch"),
+        entry(theme, k(&[Action::CloseReopen]), "Close PR (confirm with y) / reopen"),
+        entry(theme, k(&[Action::UpdateBranch]), "Update branch: merge base into PR (in detail)"),
+        entry(theme, k(&[Action::CopyBranch]), "Copy branch name to clipboard"),
+        entry(theme, k(&[Action::CopyCheckout]), "Copy checkout command to clipboard"),
+        entry(theme, k(&[Action::CopyUrl]), "Copy PR URL to clipboard"),
+        entry(theme, k(&[Action::CycleFilter]), "Cycle filter (All/Mine/Review/Closed)"),
+        entry(theme, k(&[Action::CyclePrSort]), "Cycle sort (created/updated/number/title)"),
+        entry(theme, k(&[Action::Search]), "Filter list as you type / search diff (in detail)"),
+        entry(theme, k(&[Action::NewPr]), "Create new PR (opens browser)"),
+        entry(theme, k(&[Action::CycleFocus]), "Cycle focus: List/Description/Diff/CI Checks"),
+        entry(theme, k(&[Action::Rerun]), "Rerun selected CI check (in CI panel)"),
+        entry(theme, k(&[Action::ViewLogs]), "View jobs for CI check (in CI panel)"),
+        entry(theme, k(&[Action::FailureLogs]), "Logs of the failed job, at the first error"),
+        entry(theme, k(&[Action::EditTitle]), "Edit PR title"),
+        entry(theme, k(&[Action::EditDescription]), "Edit PR description (Ctrl+S: save)"),
+        entry(theme, k(&[Action::AddReviewer]), "Add reviewer"),
+        entry(theme, k(&[Action::Assign]), "Assign user (-user to unassign)"),
+        entry(theme, k(&[Action::AddLabel]), "Add label"),
+        entry(theme, k(&[Action::RemoveLabel]), "Remove label"),
+        entry(theme, k(&[Action::OpenInBrowser]), "Open PR in browser (full edit)"),
+        entry(theme, k(&[Action::ToggleCommits]), "Toggle commit view (full diff / per-commit)"),
+        entry(theme, k(&[Action::PrevCommit, Action::NextCommit]), "Previous/next commit (in commit view)"),
+        entry(theme, k(&[Action::CopyCommitSha]), "Copy commit SHA (in commit view)"),
+        entry(theme, k(&[Action::CopyCommitMessage]), "Copy commit summary (in commit view)"),
+        entry(theme, k(&[Action::NextUrl]), "Cycle URLs in the description"),
+        entry(theme, k(&[Action::OpenUrl]), "Open selected URL"),
         Line::from(""),
-        Line::from(Span::styled("PRs Tab", styles::TEXT_BOLD)),
+        Line::from(Span::styled("Actions Tab", theme.text_bold)),
         Line::from(""),
-        Line::from(vec![
-            Span::styled("  j/k      ", styles::TAB_ACTIVE),
-            Span::styled("Navigate list / scroll diff", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  h/l      ", styles::TAB_ACTIVE),
-            Span::styled("Switch between list and detail panels", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  Enter    ", styles::TAB_ACTIVE),
-            Span::styled("View PR details", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  d        ", styles::TAB_ACTIVE),
-            Span::styled("View full diff", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  v        ", styles::TAB_ACTIVE),
-            Span::styled("Approve PR", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  x        ", styles::TAB_ACTIVE),
-            Span::styled("Request changes", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  c        ", styles::TAB_ACTIVE),
-            Span::styled("Add comment", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  m        ", styles::TAB_ACTIVE),
-            Span::styled("Merge PR (squash)", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  C        ", styles::TAB_ACTIVE),
-            Span::styled("Checkout PR branch", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  y        ", styles::TAB_ACTIVE),
-            Span::styled("Copy branch name to clipboard", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  Y        ", styles::TAB_ACTIVE),
-            Span::styled("Copy checkout command to clipboard", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  u        ", styles::TAB_ACTIVE),
-            Span::styled("Copy PR URL to clipboard", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  f        ", styles::TAB_ACTIVE),
-            Span::styled("Cycle filter (All/Mine/Review)", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  n        ", styles::TAB_ACTIVE),
-            Span::styled("Create new PR (opens browser)", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  o        ", styles::TAB_ACTIVE),
-            Span::styled("Cycle focus: List -> Detail -> CI Checks", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  R        ", styles::TAB_ACTIVE),
-            Span::styled("Rerun selected CI check (in CI panel)", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  L        ", styles::TAB_ACTIVE),
-            Span::styled("View jobs for CI check (in CI panel)", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  e        ", styles::TAB_ACTIVE),
-            Span::styled("Edit PR title", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  a        ", styles::TAB_ACTIVE),
-            Span::styled("Add reviewer", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  b        ", styles::TAB_ACTIVE),
-            Span::styled("Add label", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  w        ", styles::TAB_ACTIVE),
-            Span::styled("Open PR in browser (full edit)", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  p        ", styles::TAB_ACTIVE),
-            Span::styled("Toggle commit view (full diff / per-commit)", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  [/]      ", styles::TAB_ACTIVE),
-            Span::styled("Previous/next commit (in commit view)", styles::TEXT_NORMAL),
-        ]),
+        entry(theme, k(&[Action::NextItem, Action::PrevItem]), "Navigate runs/jobs"),
+        entry(theme, k(&[Action::Select]), "View jobs for run"),
+        entry(theme, k(&[Action::ViewLogs]), "View logs"),
+        entry(theme, k(&[Action::ToggleSteps]), "Show/hide the selected job's steps"),
+        entry(theme, k(&[Action::ApproveDeployment, Action::RejectDeployment]), "Approve/reject a waiting deployment (in jobs)"),
+        entry(theme, k(&[Action::Rerun]), "Rerun workflow"),
+        entry(theme, k(&[Action::Dispatch]), "Run a workflow (workflow_dispatch)"),
+        entry(theme, k(&[Action::CycleFilter]), "Cycle run status (All/In progress/Failure/Success)"),
+        entry(theme, k(&[Action::FilterRunBranch]), "Only runs of the checked-out branch / all branches"),
+        entry(theme, k(&[Action::CycleRunEvent]), "Cycle run event (push/pull_request/schedule/dispatch)"),
+        entry(theme, k(&[Action::FocusLeft, Action::FocusRight]), "Switch between jobs and artifacts"),
+        entry(theme, k(&[Action::DownloadArtifact]), "Download and extract the selected artifact"),
+        entry(theme, k(&[Action::OpenInBrowser]), "Open run/job in browser"),
+        entry(theme, k(&[Action::CopyUrl]), "Copy run/job URL to clipboard"),
         Line::from(""),
-        Line::from(Span::styled("Actions Tab", styles::TEXT_BOLD)),
+        Line::from(Span::styled("Logs Tab", theme.text_bold)),
         Line::from(""),
-        Line::from(vec![
-            Span::styled("  j/k      ", styles::TAB_ACTIVE),
-            Span::styled("Navigate runs/jobs", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  Enter    ", styles::TAB_ACTIVE),
-            Span::styled("View jobs for run", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  L        ", styles::TAB_ACTIVE),
-            Span::styled("View logs", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  R        ", styles::TAB_ACTIVE),
-            Span::styled("Rerun workflow", styles::TEXT_NORMAL),
-        ]),
+        entry(theme, k(&[Action::NextItem, Action::PrevItem]), "Scroll up/down"),
+        entry(theme, k(&[Action::ScrollLeft, Action::ScrollRight]), "Scroll left/right"),
+        entry(theme, k(&[Action::Top, Action::Bottom]), "Go to top/bottom"),
+        entry(theme, k(&[Action::HalfPageDown, Action::HalfPageUp]), "Half page down/up"),
+        entry(theme, k(&[Action::Search]), "Search"),
+        entry(theme, k(&[Action::GotoLine]), "Go to line number"),
+        entry(theme, k(&[Action::NextMatch, Action::PrevMatch]), "Next/previous match"),
+        entry(theme, k(&[Action::ToggleColors]), "Toggle ANSI colors / plain text"),
+        entry(theme, k(&[Action::ToggleProblems]), "Show only errors/warnings / full log"),
+        entry(theme, k(&[Action::ToggleLineNumbers]), "Toggle line numbers"),
+        entry(theme, k(&[Action::ToggleWrap]), "Wrap / truncate long lines"),
+        entry(theme, k(&[Action::Follow]), "Follow logs of a running job"),
+        entry(theme, k(&[Action::SaveLogs]), "Save logs to ./<run>-<job>.log"),
+        entry(theme, k(&[Action::SaveArchive]), "Save raw log archive (.zip)"),
+        entry(theme, k(&[Action::ToggleFold]) + "/za", "Fold/unfold log group at top"),
+        entry(theme, k(&[Action::NextUrl]), "Cycle URLs on screen"),
+        entry(theme, k(&[Action::OpenUrl]), "Open selected URL"),
+        entry(theme, k(&[Action::Back]), "Return to Actions"),
         Line::from(""),
-        Line::from(Span::styled("Logs Tab", styles::TEXT_BOLD)),
+        Line::from(Span::styled("Notifications Tab", theme.text_bold)),
         Line::from(""),
-        Line::from(vec![
-            Span::styled("  j/k      ", styles::TAB_ACTIVE),
-            Span::styled("Scroll up/down", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  h/l      ", styles::TAB_ACTIVE),
-            Span::styled("Scroll left/right", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  g/G      ", styles::TAB_ACTIVE),
-            Span::styled("Go to top/bottom", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  /        ", styles::TAB_ACTIVE),
-            Span::styled("Search", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  n/N      ", styles::TAB_ACTIVE),
-            Span::styled("Next/previous match", styles::TEXT_NORMAL),
-        ]),
-        Line::from(vec![
-            Span::styled("  Esc      ", styles::TAB_ACTIVE),
-            Span::styled("Return to Actions", styles::TEXT_NORMAL),
-        ]),
+        entry(theme, k(&[Action::NextItem, Action::PrevItem]), "Navigate notifications"),
+        entry(theme, k(&[Action::Select]), "Open PR (this repo) or in browser"),
+        entry(theme, k(&[Action::OpenInBrowser]), "Open in browser"),
+        entry(theme, k(&[Action::MarkRead]), "Mark as read"),
+        entry(theme, k(&[Action::ToggleAllRepos]), "Toggle this repo / all repos"),
         Line::from(""),
-        Line::from(Span::styled("Press ? or Esc to close", styles::TEXT_DIM)),
+        Line::from(Span::styled(format!("Press {} or Esc to close", k(&[Action::Help])), theme.text_dim)),
     ];
 
     // Calculate height based on content: lines + 2 for border
@@ -190,7 +142,7 @@ pub fn render(frame: &mut Frame, _app: &App) {
     let help = Paragraph::new(help_text).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(styles::BORDER_ACTIVE)
+            .border_style(theme.border_active)
             .title(" Help "),
     );
 
@@ -217,3 +169,11 @@ fn centered_rect_fixed(width: u16, height: u16, area: Rect) -> Rect {
         ])
         .split(popup_layout[1])[1]
 }
+
+/// One help line: the key column padded to a fixed width, then the description
+fn entry(theme: &Theme, keys: String, description: impl Into<String>) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(format!("  {:<8} ", keys), theme.tab_active),
+        Span::styled(description.into(), theme.text_normal),
+    ])
+}
@@ -1,15 +1,48 @@
 use ratatui::{
-    layout::Rect,
+    layout::{Constraint, Direction, Layout, Rect},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem},
     Frame,
 };
 
 use crate::app::App;
+use crate::keymap::Action;
+use crate::github::types::parse_timestamp;
 
-use super::styles;
 
-pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
+    // Environments waiting for approval above the jobs
+    let area = if app.pending_deployments.is_empty() {
+        area
+    } else {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(app.pending_deployments.len() as u16 + 2), Constraint::Min(0)])
+            .split(area);
+        render_pending_deployments(frame, app, chunks[0]);
+        chunks[1]
+    };
+
+    if app.artifacts.is_empty() {
+        render_jobs(frame, app, area);
+        return;
+    }
+
+    // Artifacts below the jobs, at most 40% of the height
+    let max_height = area.height * 2 / 5;
+    let height = (app.artifacts.len() as u16 + 2).min(max_height);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(height)])
+        .split(area);
+
+    render_jobs(frame, app, chunks[0]);
+    render_artifacts(frame, app, chunks[1]);
+}
+
+fn render_jobs(frame: &mut Frame, app: &mut App, area: Rect) {
+    app.mouse_areas.jobs = area;
+    let theme = &app.theme;
     let run_title = app
         .selected_run
         .as_ref()
@@ -18,11 +51,11 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
 
     if app.jobs.is_empty() {
         let placeholder = ratatui::widgets::Paragraph::new("Select a run to view jobs")
-            .style(styles::TEXT_DIM)
+            .style(theme.text_dim)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .border_style(styles::BORDER_ACTIVE)
+                    .border_style(theme.border_active)
                     .title(run_title),
             );
 
@@ -34,21 +67,113 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
         .jobs
         .iter()
         .map(|job| {
-            let status_style = styles::status_style(&job.status, job.conclusion.as_deref());
+            let status_style = theme.status_style(&job.status, job.conclusion.as_deref());
 
             let conclusion_text = job.conclusion.as_deref().unwrap_or(&job.status);
 
-            let line = Line::from(vec![
+            let mut lines = vec![Line::from(vec![
+                Span::styled(if app.expanded_jobs.contains(&job.id) { "▾ " } else { "▸ " }, theme.text_dim),
                 Span::styled(job.status_icon(), status_style),
                 Span::raw(" "),
-                Span::styled(&job.name, styles::TEXT_NORMAL),
+                Span::styled(&job.name, theme.text_normal),
                 Span::raw(" "),
                 Span::styled(format!("[{}]", conclusion_text), status_style),
                 Span::raw(" "),
-                Span::styled(job.duration(), styles::TEXT_DIM),
-            ]);
+                Span::styled(job.duration(), theme.text_dim),
+            ])];
+
+            // Steps of an expanded job, indented under it
+            if app.expanded_jobs.contains(&job.id) {
+                lines.extend(job.steps.iter().map(|step| {
+                    let step_style = theme.status_style(&step.status, step.conclusion.as_deref());
+                    Line::from(vec![
+                        Span::raw("    "),
+                        Span::styled(step.status_icon(), step_style),
+                        Span::raw(" "),
+                        Span::styled(format!("{}. {}", step.number, step.name), theme.text_normal),
+                    ])
+                }));
+            }
+
+            ListItem::new(lines)
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(if app.artifacts_focused { theme.border_inactive } else { theme.border_active })
+                .title(format!(
+                    "{} [{}, {}] ",
+                    run_title,
+                    app.keymap.hint(&[Action::Select, Action::ViewLogs], "logs"),
+                    app.keymap.hint(&[Action::ToggleSteps], "steps"),
+                )),
+        )
+        .highlight_style(theme.selected);
 
-            ListItem::new(line)
+    frame.render_stateful_widget(list, area, &mut app.job_list_state);
+}
+
+fn render_pending_deployments(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+
+    let items: Vec<ListItem> = app
+        .pending_deployments
+        .iter()
+        .map(|deployment| {
+            let mut spans = vec![
+                Span::styled("⏸ ", theme.pending),
+                Span::styled(&deployment.environment, theme.text_normal),
+            ];
+            if !deployment.can_approve {
+                spans.push(Span::styled(" (another reviewer must approve)", theme.text_dim));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(theme.pending)
+            .title(format!(
+                " Waiting for approval [{}, {}] ",
+                app.keymap.hint(&[Action::ApproveDeployment], "approve"),
+                app.keymap.hint(&[Action::RejectDeployment], "reject"),
+            )),
+    );
+
+    frame.render_widget(list, area);
+}
+
+fn render_artifacts(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+
+    let items: Vec<ListItem> = app
+        .artifacts
+        .iter()
+        .map(|artifact| {
+            let expiry = if artifact.expired {
+                Span::styled("expired", theme.failure)
+            } else {
+                let date = artifact
+                    .expires_at
+                    .as_deref()
+                    .and_then(parse_timestamp)
+                    .map(|t| format!("expires {}", t.format("%Y-%m-%d")))
+                    .unwrap_or_default();
+                Span::styled(date, theme.text_dim)
+            };
+
+            ListItem::new(Line::from(vec![
+                Span::styled(&artifact.name, theme.text_normal),
+                Span::raw(" "),
+                Span::styled(artifact.size(), theme.text_dim),
+                Span::raw(" "),
+                expiry,
+            ]))
         })
         .collect();
 
@@ -56,10 +181,14 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(styles::BORDER_ACTIVE)
-                .title(format!("{} [Enter/L:logs] ", run_title)),
+                .border_style(if app.artifacts_focused { theme.border_active } else { theme.border_inactive })
+                .title(format!(
+                    " Artifacts ({}) [{}] ",
+                    app.artifacts.len(),
+                    app.keymap.hint(&[Action::DownloadArtifact], "download"),
+                )),
         )
-        .highlight_style(styles::SELECTED);
+        .highlight_style(if app.artifacts_focused { theme.selected } else { theme.text_normal });
 
-    frame.render_stateful_widget(list, area, &mut app.job_list_state.clone());
+    frame.render_stateful_widget(list, area, &mut app.artifact_list_state.clone());
 }
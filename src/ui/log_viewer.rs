@@ -1,46 +1,187 @@
 use ratatui::{
     layout::Rect,
+    style::{Color, Modifier, Style},
     text::{Line, Span, Text},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Paragraph, Wrap},
     Frame,
 };
+use unicode_width::UnicodeWidthChar;
 
 use crate::app::App;
+use crate::keymap::Action;
 
-use super::styles;
+use super::scrollbar;
 
 /// Strip ANSI escape codes from a string
-fn strip_ansi(s: &str) -> String {
-    let mut result = String::with_capacity(s.len());
+pub fn strip_ansi(s: &str) -> String {
+    ansi_segments(s).into_iter().map(|(text, _)| text).collect()
+}
+
+/// Severity a log line is highlighted with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineLevel {
+    Error,
+    Warning,
+}
+
+/// Classify a line without ANSI codes: `##[error]`/`##[warning]` annotations, or
+/// any mention of "error"/"warning". Group markers are never classified.
+pub fn line_level(clean_line: &str) -> Option<LineLevel> {
+    if clean_line.contains("##[group]") || clean_line.contains("##[endgroup]") {
+        return None;
+    }
+    let lower = clean_line.to_lowercase();
+    if clean_line.contains("##[error]") || lower.contains("error") {
+        Some(LineLevel::Error)
+    } else if clean_line.contains("##[warning]") || lower.contains("warning") {
+        Some(LineLevel::Warning)
+    } else {
+        None
+    }
+}
+
+/// Split a line into text segments with the style set by ANSI SGR escape codes.
+/// Tabs are expanded and other control characters dropped, as in plain mode.
+fn ansi_segments(s: &str) -> Vec<(String, Style)> {
+    let mut segments: Vec<(String, Style)> = Vec::new();
+    let mut current = String::new();
+    let mut style = Style::default();
     let mut chars = s.chars().peekable();
 
     while let Some(c) = chars.next() {
         if c == '\x1b' {
-            // Skip escape sequence
-            if chars.peek() == Some(&'[') {
-                chars.next(); // consume '['
-                // Skip until we hit a letter (end of escape sequence)
-                while let Some(&next) = chars.peek() {
-                    chars.next();
-                    if next.is_ascii_alphabetic() {
-                        break;
-                    }
+            if chars.peek() != Some(&'[') {
+                continue;
+            }
+            chars.next(); // consume '['
+
+            // Collect parameters until the final letter of the sequence
+            let mut params = String::new();
+            let mut terminator = None;
+            for next in chars.by_ref() {
+                if next.is_ascii_alphabetic() {
+                    terminator = Some(next);
+                    break;
                 }
+                params.push(next);
+            }
+
+            // Only SGR ("m") sequences affect styling; others are skipped
+            if terminator == Some('m') {
+                if !current.is_empty() {
+                    segments.push((std::mem::take(&mut current), style));
+                }
+                style = apply_sgr(style, &params);
             }
         } else if c == '\t' {
             // Replace tabs with spaces
-            result.push_str("    ");
+            current.push_str("    ");
         } else if c.is_ascii_control() && c != '\n' {
             // Skip other control characters
         } else {
-            result.push(c);
+            current.push(c);
+        }
+    }
+
+    if !current.is_empty() {
+        segments.push((current, style));
+    }
+
+    segments
+}
+
+/// Apply a `;`-separated list of SGR parameters to a style
+fn apply_sgr(mut style: Style, params: &str) -> Style {
+    let codes: Vec<u16> = if params.is_empty() {
+        vec![0]
+    } else {
+        params.split(';').map(|p| p.parse().unwrap_or(0)).collect()
+    };
+
+    let mut i = 0;
+    while i < codes.len() {
+        match codes[i] {
+            0 => style = Style::default(),
+            1 => style = style.add_modifier(Modifier::BOLD),
+            2 => style = style.add_modifier(Modifier::DIM),
+            3 => style = style.add_modifier(Modifier::ITALIC),
+            4 => style = style.add_modifier(Modifier::UNDERLINED),
+            22 => style = style.remove_modifier(Modifier::BOLD | Modifier::DIM),
+            23 => style = style.remove_modifier(Modifier::ITALIC),
+            24 => style = style.remove_modifier(Modifier::UNDERLINED),
+            code @ 30..=37 => style = style.fg(Color::Indexed((code - 30) as u8)),
+            code @ 90..=97 => style = style.fg(Color::Indexed((code - 90 + 8) as u8)),
+            code @ 40..=47 => style = style.bg(Color::Indexed((code - 40) as u8)),
+            code @ 100..=107 => style = style.bg(Color::Indexed((code - 100 + 8) as u8)),
+            39 => style.fg = None,
+            49 => style.bg = None,
+            code @ (38 | 48) => {
+                // Extended colors: 38;5;n (256-color) or 38;2;r;g;b (truecolor)
+                let color = match codes.get(i + 1) {
+                    Some(5) => {
+                        let color = codes.get(i + 2).map(|&n| Color::Indexed(n as u8));
+                        i += 2;
+                        color
+                    }
+                    Some(2) => {
+                        let color = match (codes.get(i + 2), codes.get(i + 3), codes.get(i + 4)) {
+                            (Some(&r), Some(&g), Some(&b)) => Some(Color::Rgb(r as u8, g as u8, b as u8)),
+                            _ => None,
+                        };
+                        i += 4;
+                        color
+                    }
+                    _ => None,
+                };
+                if let Some(color) = color {
+                    style = if code == 38 { style.fg(color) } else { style.bg(color) };
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    style
+}
+
+/// Cut `width` display columns starting at column `start` out of styled segments.
+/// A wide char split by either edge becomes spaces so the columns stay aligned.
+fn slice_segments(segments: Vec<(String, Style)>, start: usize, width: usize) -> Vec<(String, Style)> {
+    let end = start + width;
+    let mut result = Vec::new();
+    let mut col = 0;
+
+    for (text, style) in segments {
+        if col >= end {
+            break;
+        }
+        let mut piece = String::new();
+        for c in text.chars() {
+            let next = col + c.width().unwrap_or(0);
+            if col >= start && next <= end {
+                piece.push(c);
+            } else if next > start && col < end {
+                piece.extend(std::iter::repeat_n(' ', next.min(end) - col.max(start)));
+            }
+            col = next;
+            if col >= end {
+                break;
+            }
+        }
+        if !piece.is_empty() {
+            result.push((piece, style));
         }
     }
 
     result
 }
 
-pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
+    app.log_height = area.height.saturating_sub(2);
+    app.log_width = area.width.saturating_sub(2);
+    app.mouse_areas.logs = area;
+    let theme = &app.theme;
     let title = if let Some(ref run) = app.selected_run {
         let job_name = app
             .job_list_state
@@ -49,18 +190,25 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
             .map(|j| format!(" - {}", j.name))
             .unwrap_or_default();
 
-        format!(" Logs: {} #{}{} ", run.name, run.run_number, job_name)
+        let follow = if app.log_follow { " [following]" } else { "" };
+        let problems = if app.log_problems_only {
+            let (errors, warnings) = app.log_problem_counts();
+            format!(" [errors: {}, warnings: {}]", errors, warnings)
+        } else {
+            String::new()
+        };
+        format!(" Logs: {} #{}{}{}{} ", run.name, run.run_number, job_name, follow, problems)
     } else {
         " Logs ".to_string()
     };
 
     if app.logs.is_empty() {
         let placeholder = Paragraph::new("No logs available. Select a job and press Enter or L to view logs.")
-            .style(styles::TEXT_DIM)
+            .style(theme.text_dim)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .border_style(styles::BORDER_ACTIVE)
+                    .border_style(theme.border_active)
                     .title(title),
             );
 
@@ -68,78 +216,107 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
         return;
     }
 
-    let height = area.height as usize - 2;
-    let width = area.width as usize - 2;
+    let height = (area.height as usize).saturating_sub(2);
     let search_term = app.log_search.as_deref();
 
-    let lines: Vec<Line> = app
-        .logs
-        .lines()
-        .enumerate()
+    let visible_lines = app.visible_log_lines();
+
+    // Line numbers are 1-based, right-aligned to the widest one, and don't scroll sideways
+    let number_width = if app.log_line_numbers { app.log_line_count().to_string().len() } else { 0 };
+    let gutter = |line_num: usize| {
+        app.log_line_numbers
+            .then(|| Span::styled(format!("{:>w$} ", line_num + 1, w = number_width), theme.text_dim))
+    };
+    let width = (area.width as usize).saturating_sub(2).saturating_sub(if app.log_line_numbers { number_width + 1 } else { 0 });
+    // Wrapped lines are kept whole; otherwise they're cut to the pane, scrolled sideways
+    let fit = |segments| if app.log_wrap { segments } else { slice_segments(segments, app.log_h_scroll as usize, width) };
+
+    let lines: Vec<Line> = visible_lines
+        .iter()
         .skip(app.log_scroll as usize)
         .take(height)
-        .map(|(line_num, line)| {
-            // Strip ANSI codes and clean the line
-            let clean_line = strip_ansi(line);
-
-            // Truncate to terminal width (with horizontal scroll offset)
-            let display_line: String = if clean_line.len() > width {
-                let start = app.log_h_scroll as usize;
-                if start < clean_line.len() {
-                    clean_line.chars().skip(start).take(width).collect()
+        .map(|&line_num| {
+            let line = app.log_line(line_num);
+
+            // Group headers get a fold marker; collapsed ones also show their size
+            if let Some(group) = app.log_group_at(line_num) {
+                let header = if app.collapsed_groups.contains(&group.start) {
+                    format!("▶ {} ({} lines)", group.name, group.end - group.start + 1)
                 } else {
-                    String::new()
-                }
-            } else {
-                let start = app.log_h_scroll as usize;
-                if start < clean_line.len() {
-                    clean_line.chars().skip(start).collect()
+                    format!("▼ {}", group.name)
+                };
+                let style = if app.log_matches.contains(&line_num) {
+                    theme.highlight
                 } else {
-                    String::new()
-                }
+                    theme.diff_header
+                };
+                let spans: Vec<Span> = gutter(line_num)
+                    .into_iter()
+                    .chain(
+                        fit(vec![(header, style)])
+                            .into_iter()
+                            .map(|(text, style)| Span::styled(text, style)),
+                    )
+                    .collect();
+                return Line::from(spans);
+            }
+
+            // Parse ANSI colors (or drop them in plain mode) and clean the line
+            let segments = if app.log_plain {
+                vec![(strip_ansi(line), Style::default())]
+            } else {
+                ansi_segments(line)
             };
+            let clean_line: String = segments.iter().map(|(text, _)| text.as_str()).collect();
 
             // Check if this line is a match
             let is_match = app.log_matches.contains(&line_num);
 
             // Determine style based on content
+            let level = line_level(&clean_line);
             let style = if clean_line.contains("##[group]") || clean_line.contains("##[endgroup]") {
-                styles::DIFF_HEADER
-            } else if clean_line.contains("##[error]") || clean_line.to_lowercase().contains("error") {
-                styles::FAILURE
-            } else if clean_line.contains("##[warning]") || clean_line.to_lowercase().contains("warning") {
-                styles::PENDING
+                theme.diff_header
+            } else if level == Some(LineLevel::Error) {
+                theme.failure
+            } else if level == Some(LineLevel::Warning) {
+                theme.pending
             } else if is_match {
-                styles::HIGHLIGHT
+                theme.highlight
             } else if clean_line.starts_with("Run ") || clean_line.contains("\t") {
-                styles::TEXT_DIM
+                theme.text_dim
             } else {
-                styles::TEXT_NORMAL
+                theme.text_normal
             };
 
-            // Highlight search matches
-            if let Some(term) = search_term {
-                if !term.is_empty() && clean_line.to_lowercase().contains(&term.to_lowercase()) {
-                    Line::from(Span::styled(display_line, styles::HIGHLIGHT))
-                } else {
-                    Line::from(Span::styled(display_line, style))
-                }
-            } else {
-                Line::from(Span::styled(display_line, style))
-            }
+            // Highlight search matches over everything else
+            let is_search_hit = search_term.is_some_and(|term| {
+                !term.is_empty() && clean_line.to_lowercase().contains(&term.to_lowercase())
+            });
+
+            let visible = fit(segments);
+
+            let spans: Vec<Span> = gutter(line_num)
+                .into_iter()
+                .chain(visible.into_iter().map(|(text, ansi_style)| {
+                    if is_search_hit {
+                        Span::styled(text, theme.highlight)
+                    } else {
+                        // Colors from the log itself win over our content-based guess
+                        Span::styled(text, style.patch(ansi_style))
+                    }
+                }))
+                .collect();
+
+            Line::from(spans)
         })
         .collect();
 
     let text = Text::from(lines);
 
     // Build status line
-    let total_lines = app.logs.lines().count();
+    let total_lines = visible_lines.len();
     let current_line = app.log_scroll as usize + 1;
-    let percentage = if total_lines > 0 {
-        (current_line * 100) / total_lines
-    } else {
-        0
-    };
+    let percentage = (current_line * 100).checked_div(total_lines).unwrap_or(0);
 
     let status = if let Some(ref search) = app.log_search {
         format!(
@@ -156,17 +333,99 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
             app.log_matches.len()
         )
     } else {
-        format!(" Line {}/{} ({}%) | h/l:horizontal scroll ", current_line, total_lines, percentage)
+        let keys = &app.keymap;
+        let wrap = if app.log_wrap {
+            keys.hint(&[Action::ToggleWrap], "truncate")
+        } else {
+            format!(
+                "{} | {}",
+                keys.hint(&[Action::ScrollLeft, Action::ScrollRight], "horizontal scroll"),
+                keys.hint(&[Action::ToggleWrap], "wrap"),
+            )
+        };
+        format!(
+            " Line {}/{} ({}%) | {} | {} | {} ",
+            current_line,
+            total_lines,
+            percentage,
+            wrap,
+            keys.hint(&[Action::ToggleColors], if app.log_plain { "colors" } else { "plain" }),
+            keys.hint(&[Action::ToggleProblems], if app.log_problems_only { "full log" } else { "errors only" }),
+        )
     };
 
-    let log_widget = Paragraph::new(text)
+    let mut log_widget = Paragraph::new(text)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(styles::BORDER_ACTIVE)
+                .border_style(theme.border_active)
                 .title(title)
                 .title_bottom(status),
         );
+    if app.log_wrap {
+        log_widget = log_widget.wrap(Wrap { trim: false });
+    }
 
     frame.render_widget(log_widget, area);
+    scrollbar::render(frame, area, app.log_scroll as usize, total_lines, theme);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slice(line: &str, start: usize, width: usize) -> String {
+        slice_segments(ansi_segments(line), start, width)
+            .into_iter()
+            .map(|(text, _)| text)
+            .collect()
+    }
+
+    #[test]
+    fn slices_ascii_by_columns() {
+        assert_eq!(slice("cargo build --release", 0, 5), "cargo");
+        assert_eq!(slice("cargo build --release", 6, 5), "build");
+        assert_eq!(slice("short", 10, 5), "");
+    }
+
+    #[test]
+    fn slices_mixed_ascii_and_cjk_by_display_width() {
+        // Columns: a=0 b=1 中=2-3 文=4-5 c=6 d=7
+        let line = "ab中文cd";
+        assert_eq!(slice(line, 0, 4), "ab中");
+        assert_eq!(slice(line, 2, 4), "中文");
+        assert_eq!(slice(line, 4, 10), "文cd");
+        // Wide chars cut by the pane edges are padded, keeping widths exact
+        assert_eq!(slice(line, 0, 3), "ab ");
+        assert_eq!(slice(line, 3, 4), " 文c");
+        for start in 0..8 {
+            for width in 0..8 {
+                let cols = slice(line, start, width).chars().map(|c| c.width().unwrap_or(0)).sum::<usize>();
+                assert_eq!(cols, width.min(8usize.saturating_sub(start)), "start {start} width {width}");
+            }
+        }
+    }
+
+    #[test]
+    fn slicing_keeps_ansi_styles() {
+        let segments = slice_segments(ansi_segments("\x1b[31m错误\x1b[0m: failed"), 2, 6);
+        assert_eq!(segments[0].0, "误");
+        assert_eq!(segments[0].1.fg, Some(Color::Indexed(1)));
+        assert_eq!(segments[1].0, ": fa");
+        assert_eq!(segments[1].1.fg, None);
+    }
+
+    #[test]
+    fn classifies_errors_before_warnings() {
+        assert_eq!(line_level("##[error]Process completed with exit code 1."), Some(LineLevel::Error));
+        assert_eq!(line_level("warning: unused variable, error: aborting"), Some(LineLevel::Error));
+        assert_eq!(line_level("##[warning]Node.js 16 actions are deprecated"), Some(LineLevel::Warning));
+        assert_eq!(line_level("##[group]Run cargo test -- error_handling"), None);
+        assert_eq!(line_level("test result: ok"), None);
+    }
+
+    #[test]
+    fn expands_tabs_before_slicing() {
+        assert_eq!(slice("\tindented", 4, 8), "indented");
+    }
 }
@@ -0,0 +1,220 @@
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span, Text};
+
+use crate::util::url_len;
+
+use super::styles::Theme;
+
+/// Render the markdown commonly found in PR descriptions. A line-based subset,
+/// not CommonMark: headers, fenced code, lists, task lists, quotes, inline code,
+/// bold, links and bare URLs. HTML comments (PR template hints) are hidden.
+pub fn render(markdown: &str, theme: &Theme) -> Text<'static> {
+    let mut lines = Vec::new();
+    let mut fence: Option<&str> = None;
+    let mut in_comment = false;
+
+    for raw in markdown.lines() {
+        let trimmed = raw.trim_start();
+
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            } else {
+                lines.push(Line::from(Span::styled(format!("  {}", raw), theme.diff_header)));
+            }
+            continue;
+        }
+        if in_comment {
+            in_comment = !raw.contains("-->");
+            continue;
+        }
+
+        if let Some(marker) = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m)) {
+            fence = Some(marker);
+            continue;
+        }
+        if trimmed.starts_with("<!--") {
+            in_comment = !trimmed.contains("-->");
+            // A comment on its own line leaves no blank line behind
+            if trimmed.ends_with("-->") || in_comment {
+                continue;
+            }
+        }
+
+        lines.push(render_line(raw, theme));
+    }
+
+    Text::from(lines)
+}
+
+fn render_line(raw: &str, theme: &Theme) -> Line<'static> {
+    let trimmed = raw.trim_start();
+    let indent = &raw[..raw.len() - trimmed.len()];
+
+    // Headers: `#` to `######` followed by a space
+    let level = trimmed.chars().take_while(|&c| c == '#').count();
+    if (1..=6).contains(&level) && trimmed[level..].starts_with(' ') {
+        return Line::from(inline(trimmed[level..].trim(), theme.text_bold, theme));
+    }
+
+    if let Some(quote) = trimmed.strip_prefix('>') {
+        let mut spans = vec![Span::styled(format!("{}│ ", indent), theme.text_dim)];
+        spans.extend(inline(quote.trim_start(), theme.text_dim, theme));
+        return Line::from(spans);
+    }
+
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(item) = trimmed.strip_prefix(bullet) {
+            let (marker, item) = if let Some(rest) = item.strip_prefix("[ ] ") {
+                ("☐ ", rest)
+            } else if let Some(rest) = item.strip_prefix("[x] ").or_else(|| item.strip_prefix("[X] ")) {
+                ("☑ ", rest)
+            } else {
+                ("• ", item)
+            };
+            let mut spans = vec![Span::raw(indent.to_string()), Span::styled(marker, theme.text_dim)];
+            spans.extend(inline(item, theme.text_normal, theme));
+            return Line::from(spans);
+        }
+    }
+
+    Line::from(inline(raw, theme.text_normal, theme))
+}
+
+/// Split a line into styled spans for inline code, bold, links and bare URLs
+fn inline(text: &str, base: Style, theme: &Theme) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        let styled = code_span(rest, theme)
+            .or_else(|| bold_span(rest, base))
+            .or_else(|| link_spans(rest, theme))
+            .or_else(|| url_span(rest, &plain, theme));
+        match styled {
+            Some((styled, after)) => {
+                if !plain.is_empty() {
+                    spans.push(Span::styled(std::mem::take(&mut plain), base));
+                }
+                spans.extend(styled);
+                rest = after;
+            }
+            None => {
+                plain.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+
+    if !plain.is_empty() {
+        spans.push(Span::styled(plain, base));
+    }
+    spans
+}
+
+/// `` `code` ``
+fn code_span<'a>(text: &'a str, theme: &Theme) -> Option<(Vec<Span<'static>>, &'a str)> {
+    let inner = text.strip_prefix('`')?;
+    let end = inner.find('`')?;
+    Some((vec![Span::styled(inner[..end].to_string(), theme.diff_header)], &inner[end + 1..]))
+}
+
+/// `**bold**` or `__bold__`
+fn bold_span(text: &str, base: Style) -> Option<(Vec<Span<'static>>, &str)> {
+    let marker = ["**", "__"].into_iter().find(|m| text.starts_with(m))?;
+    let inner = &text[2..];
+    let end = inner.find(marker).filter(|&end| end > 0)?;
+    let style = base.add_modifier(Modifier::BOLD);
+    Some((vec![Span::styled(inner[..end].to_string(), style)], &inner[end + 2..]))
+}
+
+/// `[text](url)` and `![alt](url)`: the text in link style, followed by the URL
+/// so it can still be read (and opened)
+fn link_spans<'a>(text: &'a str, theme: &Theme) -> Option<(Vec<Span<'static>>, &'a str)> {
+    let (image, text) = match text.strip_prefix('!') {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+    let inner = text.strip_prefix('[')?;
+    let label_end = inner.find("](")?;
+    let target = &inner[label_end + 2..];
+    let url_end = target.find(')')?;
+    let (label, url) = (&inner[..label_end], &target[..url_end]);
+
+    let label = match (image, label.is_empty()) {
+        (true, true) => "image".to_string(),
+        (true, false) => format!("image: {}", label),
+        _ => label.to_string(),
+    };
+    let mut spans = vec![Span::styled(label.clone(), theme.link)];
+    if label != url {
+        spans.push(Span::styled(format!(" ({})", url), theme.text_dim));
+    }
+    Some((spans, &target[url_end + 1..]))
+}
+
+/// A bare `http(s)://` URL at the start of a word
+fn url_span<'a>(text: &'a str, before: &str, theme: &Theme) -> Option<(Vec<Span<'static>>, &'a str)> {
+    if !(text.starts_with("https://") || text.starts_with("http://")) {
+        return None;
+    }
+    if before.chars().last().is_some_and(|c| c.is_alphanumeric()) {
+        return None;
+    }
+    let end = url_len(text);
+    Some((vec![Span::styled(text[..end].to_string(), theme.link)], &text[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(text: &Text) -> Vec<String> {
+        text.lines
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn headers_and_lists() {
+        let text = render("## Summary\n- one\n  * two\n- [ ] todo\n- [x] done\n1. first", &Theme::default());
+        assert_eq!(plain(&text), ["Summary", "• one", "  • two", "☐ todo", "☑ done", "1. first"]);
+        assert!(text.lines[0].spans[0].style.add_modifier.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn code_blocks_and_comments_are_not_parsed() {
+        let text = render(
+            "<!-- Describe your change -->\nBefore\n```rust\n# not a header\n```\n<!--\nhidden\n-->\nAfter",
+            &Theme::default(),
+        );
+        assert_eq!(plain(&text), ["Before", "  # not a header", "After"]);
+    }
+
+    #[test]
+    fn inline_styles() {
+        let theme = Theme::default();
+        let text = render("Run `cargo test` **now** or never", &theme);
+        let spans = &text.lines[0].spans;
+        assert_eq!(plain(&text), ["Run cargo test now or never"]);
+        assert_eq!(spans[1].style, theme.diff_header);
+        assert!(spans[3].style.add_modifier.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn links_keep_their_url() {
+        let theme = Theme::default();
+        let text = render("See [the docs](https://example.com/docs), or https://example.com/x.", &theme);
+        assert_eq!(plain(&text), ["See the docs (https://example.com/docs), or https://example.com/x."]);
+        assert_eq!(text.lines[0].spans[1].style, theme.link);
+        assert_eq!(text.lines[0].spans[4].content, "https://example.com/x");
+        assert_eq!(text.lines[0].spans[4].style, theme.link);
+    }
+
+    #[test]
+    fn unmatched_markers_stay_literal() {
+        assert_eq!(plain(&render("a ` b ** c [d]", &Theme::default())), ["a ` b ** c [d]"]);
+    }
+}
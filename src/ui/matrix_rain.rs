@@ -7,7 +7,7 @@ use ratatui::{
     Frame,
 };
 
-use super::styles;
+use super::styles::Theme;
 
 const MATRIX_CHARS: &[char] = &[
     // Numbers
@@ -131,13 +131,13 @@ impl MatrixRain {
         }
     }
 
-    pub fn render(&self, frame: &mut Frame, area: Rect, loading_text: Option<&str>) {
+    pub fn render(&self, frame: &mut Frame, area: Rect, loading_text: Option<&str>, theme: &Theme) {
         // Clear and draw border around the matrix area
         frame.render_widget(Clear, area);
 
         let block = Block::default()
             .borders(Borders::ALL)
-            .border_style(styles::BORDER_ACTIVE)
+            .border_style(theme.border_active)
             .style(Style::default().bg(Color::Black));
 
         let inner_area = block.inner(area);
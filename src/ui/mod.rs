@@ -2,11 +2,19 @@ mod actions_list;
 mod help;
 mod jobs_view;
 mod log_viewer;
+mod markdown;
+mod notifications;
 pub mod matrix_rain;
 mod pr_detail;
 mod pr_list;
 mod render;
+mod scrollbar;
 mod styles;
+mod text;
 
+pub use log_viewer::{line_level, strip_ansi, LineLevel};
+pub use markdown::render as render_markdown;
+pub use pr_detail::{RenderedDiff, SPLIT_DIFF_MIN_WIDTH};
 pub use matrix_rain::MatrixRain;
 pub use render::render;
+pub use styles::Theme;
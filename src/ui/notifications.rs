@@ -0,0 +1,69 @@
+use ratatui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+    Frame,
+};
+
+use crate::app::App;
+use crate::keymap::Action;
+use crate::util::relative_time;
+
+use super::text::truncate;
+
+pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+    let scope = if app.notifications_all_repos { "all repos" } else { app.repo.as_str() };
+    let title = format!(
+        " Notifications ({}) [{}, {}] ",
+        scope,
+        app.keymap.hint(&[Action::MarkRead], "mark read"),
+        app.keymap.hint(&[Action::ToggleAllRepos], "all repos"),
+    );
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border_active)
+        .title(title);
+
+    if app.notifications.is_empty() {
+        let placeholder = Paragraph::new("No unread notifications")
+            .style(theme.text_dim)
+            .block(block);
+        frame.render_widget(placeholder, area);
+        return;
+    }
+
+    let items: Vec<ListItem> = app
+        .notifications
+        .iter()
+        .map(|notification| {
+            let mut spans = vec![
+                Span::styled(notification.kind_icon(), theme.text_dim),
+                Span::raw(" "),
+            ];
+            // The repo is implied when showing a single repo's inbox
+            if app.notifications_all_repos {
+                spans.push(Span::styled(truncate(&notification.repo, 30), theme.text_dim));
+                spans.push(Span::raw(" "));
+            }
+            spans.extend([
+                Span::styled(
+                    truncate(&notification.title, (area.width as usize).saturating_sub(50)),
+                    theme.text_normal,
+                ),
+                Span::raw(" "),
+                Span::styled(notification.reason.replace('_', " "), theme.pending),
+                Span::raw(" "),
+                Span::styled(relative_time(&notification.updated_at), theme.text_dim),
+            ]);
+
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(theme.selected);
+
+    frame.render_stateful_widget(list, area, &mut app.notification_list_state.clone());
+}
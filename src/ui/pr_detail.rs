@@ -1,3 +1,6 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
+
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     text::{Line, Span, Text},
@@ -5,159 +8,194 @@ use ratatui::{
     Frame,
 };
 
-use crate::app::{App, DiffMode, Focus};
+use crate::app::{App, CheckRow, DiffLayout, DiffMode, Focus};
+use crate::keymap::Action;
+use crate::github::types::{aggregate_ci_status, ci_icon, latest_reviews, Commit, PullRequest};
+use crate::util::relative_time;
 
-use super::styles;
+use super::markdown;
+use super::scrollbar;
+use super::styles::Theme;
 
 pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
+    let theme = app.theme;
     let detail_focused = app.focus == Focus::Detail;
     let checks_focused = app.focus == Focus::PrChecks;
+    let description_focused = app.focus == Focus::Description;
 
     let detail_border = if detail_focused {
-        styles::BORDER_ACTIVE
+        theme.border_active
     } else {
-        styles::BORDER_INACTIVE
+        theme.border_inactive
     };
 
     let checks_border = if checks_focused {
-        styles::BORDER_ACTIVE
+        theme.border_active
     } else {
-        styles::BORDER_INACTIVE
+        theme.border_inactive
     };
 
-    if let Some(ref pr) = app.selected_pr {
-        // Calculate description height (3-6 lines depending on content)
-        let desc_height = if pr.body.is_some() { 5 } else { 0 };
+    let description_border = if description_focused {
+        theme.border_active
+    } else {
+        theme.border_inactive
+    };
 
+    if let Some(ref pr) = app.selected_pr {
         // Split into metadata, description, diff preview, and checks panel
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(8),              // Metadata
-                Constraint::Length(desc_height),    // Description (0 if empty)
+                Constraint::Length(9),              // Metadata
+                Constraint::Length(7),              // Description
                 Constraint::Min(10),                // Diff preview
                 Constraint::Length(10),             // PR Checks
             ])
             .split(area);
 
         // Metadata section
-        let status_style = styles::pr_style(&pr.state, pr.merged, pr.draft);
-        let ci_style = match pr.ci_status.as_deref() {
-            Some("success") => styles::SUCCESS,
-            Some("failure") => styles::FAILURE,
-            Some("pending") => styles::PENDING,
-            _ => styles::NEUTRAL,
-        };
-
-        let labels_text = if pr.labels.is_empty() {
-            String::new()
-        } else {
-            pr.labels.iter().map(|l| l.name.as_str()).collect::<Vec<_>>().join(", ")
-        };
+        let status_style = theme.pr_style(&pr.state, pr.merged, pr.draft);
+        let ci_style = theme.ci_style(pr.ci_status.as_deref());
 
         // Build reviewers line with actual review status
         let reviewers_spans: Vec<Span> = build_reviewers_spans(app, pr);
 
         let meta_lines = vec![
             Line::from(vec![
-                Span::styled(format!("#{} ", pr.number), styles::TEXT_BOLD),
-                Span::styled(&pr.title, styles::TEXT_NORMAL),
-            ]),
-            Line::from(vec![
-                Span::styled("Author: ", styles::TEXT_DIM),
-                Span::styled(&pr.user.login, styles::TEXT_NORMAL),
-            ]),
-            Line::from(vec![
-                Span::styled("Branch: ", styles::TEXT_DIM),
-                Span::styled(&pr.head.ref_name, styles::TEXT_NORMAL),
-                Span::styled(" -> ", styles::TEXT_DIM),
-                Span::styled(&pr.base.ref_name, styles::TEXT_NORMAL),
+                Span::styled(format!("#{} ", pr.number), theme.text_bold),
+                Span::styled(&pr.title, theme.text_normal),
             ]),
             Line::from(vec![
-                Span::styled("Status: ", styles::TEXT_DIM),
-                Span::styled(pr.status_icon(), status_style),
+                Span::styled("Author: ", theme.text_dim),
+                Span::styled(&pr.user.login, theme.text_normal),
                 Span::styled(
-                    if pr.merged {
-                        " Merged"
-                    } else if pr.state == "closed" {
-                        " Closed"
-                    } else if pr.draft {
-                        " Draft"
-                    } else {
-                        " Open"
-                    },
-                    status_style,
+                    format!(" · opened {} · updated {}", relative_time(&pr.created_at), relative_time(&pr.updated_at)),
+                    theme.text_dim,
                 ),
-                Span::styled(" | CI: ", styles::TEXT_DIM),
-                Span::styled(pr.ci_icon(), ci_style),
             ]),
             Line::from({
-                let mut spans = vec![Span::styled("Reviews: ", styles::TEXT_DIM)];
+                let mut spans = vec![
+                    Span::styled("Branch: ", theme.text_dim),
+                    Span::styled(&pr.head.ref_name, theme.text_normal),
+                    Span::styled(" -> ", theme.text_dim),
+                    Span::styled(&pr.base.ref_name, theme.text_normal),
+                ];
+                spans.extend(checkout_spans(app, pr, &theme));
+                spans
+            }),
+            Line::from({
+                let mut spans = vec![
+                    Span::styled("Status: ", theme.text_dim),
+                    Span::styled(pr.status_icon(), status_style),
+                    Span::styled(
+                        if pr.merged {
+                            " Merged"
+                        } else if pr.state == "closed" {
+                            " Closed"
+                        } else if pr.draft {
+                            " Draft"
+                        } else {
+                            " Open"
+                        },
+                        status_style,
+                    ),
+                    Span::styled(" | CI: ", theme.text_dim),
+                    Span::styled(pr.ci_icon(), ci_style),
+                ];
+                spans.extend(mergeable_spans(pr, &theme));
+                if let Some(method) = &app.pr_auto_merge {
+                    spans.push(Span::styled(" | Auto-merge: ", theme.text_dim));
+                    spans.push(Span::styled(method.clone(), theme.success));
+                }
+                spans
+            }),
+            Line::from({
+                let mut spans = vec![Span::styled("Reviews: ", theme.text_dim)];
                 spans.extend(reviewers_spans.clone());
                 spans
             }),
             Line::from(vec![
-                Span::styled("Labels: ", styles::TEXT_DIM),
-                Span::styled(labels_text, styles::TEXT_NORMAL),
+                Span::styled("Assignees: ", theme.text_dim),
+                if pr.assignees.is_empty() {
+                    Span::styled("None", theme.text_dim)
+                } else {
+                    Span::styled(
+                        pr.assignees.iter().map(|u| u.login.as_str()).collect::<Vec<_>>().join(", "),
+                        theme.text_normal,
+                    )
+                },
             ]),
+            Line::from({
+                let mut spans = vec![Span::styled("Labels: ", theme.text_dim)];
+                spans.extend(theme.label_spans(&pr.labels));
+                spans
+            }),
         ];
 
         let meta = Paragraph::new(meta_lines).block(
             Block::default()
                 .borders(Borders::ALL)
                 .border_style(detail_border)
-                .title(" PR Details [d:full diff] "),
+                .title(format!(" PR Details [{}] ", app.keymap.hint(&[Action::ViewDiff], "full diff"))),
         );
 
         frame.render_widget(meta, chunks[0]);
 
-        // Description section (only if body exists)
-        if let Some(ref body) = pr.body {
-            let desc_text = if body.len() > 200 {
-                format!("{}...", &body[..200])
-            } else {
-                body.clone()
-            };
+        // Description section (scrollable, with placeholder when empty)
+        let desc_block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(description_border)
+            .title(format!(
+                " Description [{}, {}] ",
+                app.keymap.hint(&[Action::EditDescription], "edit"),
+                app.keymap.hint(&[Action::NextItem, Action::PrevItem], "scroll"),
+            ));
+
+        let desc_widget = match pr.body {
+            Some(ref body) => Paragraph::new(markdown::render(body, &theme))
+                .block(desc_block)
+                .wrap(Wrap { trim: false })
+                .scroll((app.body_scroll, 0)),
+            None => Paragraph::new("No description")
+                .style(theme.text_dim)
+                .block(desc_block),
+        };
 
-            let desc_widget = Paragraph::new(desc_text)
-                .style(styles::TEXT_NORMAL)
-                .block(
-                    Block::default()
-                        .borders(Borders::ALL)
-                        .border_style(detail_border)
-                        .title(" Description [E:edit] "),
-                )
-                .wrap(Wrap { trim: true });
-
-            frame.render_widget(desc_widget, chunks[1]);
-        }
+        frame.render_widget(desc_widget, chunks[1]);
+        app.body_area = (chunks[1].width.saturating_sub(2), chunks[1].height.saturating_sub(2));
 
         // Diff area - changes based on mode
         let diff_chunk = chunks[2];
+        app.mouse_areas.diff = diff_chunk;
         match app.diff_mode {
             DiffMode::Full => {
                 // Full diff preview
-                if let Some(ref diff) = app.pr_diff {
-                    let diff_lines = render_diff_lines(diff, app.diff_scroll as usize, diff_chunk.height as usize - 2);
+                if app.pr_diff.is_some() {
+                    let (diff_lines, total) = render_diff_lines(app, &app.pr_diff_view, (diff_chunk.height as usize).saturating_sub(2));
 
                     let diff_widget = Paragraph::new(diff_lines)
                         .block(
                             Block::default()
                                 .borders(Borders::ALL)
                                 .border_style(detail_border)
-                                .title(" Diff Preview [p:commits, j/k:scroll] "),
+                                .title(diff_title(app, &format!(
+                                    "Diff Preview [{}, {}]",
+                                    app.keymap.hint(&[Action::ToggleCommits], "commits"),
+                                    app.keymap.hint(&[Action::NextItem, Action::PrevItem], "scroll"),
+                                ))),
                         )
                         .wrap(Wrap { trim: false });
 
                     frame.render_widget(diff_widget, diff_chunk);
+                    scrollbar::render(frame, diff_chunk, app.diff_scroll as usize, total, &theme);
                 } else {
                     let placeholder = Paragraph::new("Loading diff...")
-                        .style(styles::TEXT_DIM)
+                        .style(theme.text_dim)
                         .block(
                             Block::default()
                                 .borders(Borders::ALL)
                                 .border_style(detail_border)
-                                .title(" Diff Preview [p:commits] "),
+                                .title(format!(" Diff Preview [{}] ", app.keymap.hint(&[Action::ToggleCommits], "commits"))),
                         );
 
                     frame.render_widget(placeholder, diff_chunk);
@@ -173,12 +211,18 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
                 // Commit list
                 render_commit_list(frame, app, commit_chunks[0], detail_border);
 
+                // Commit message above its diff
+                let commit = app.pr_commits_state.selected().and_then(|i| app.pr_commits.get(i));
+                let diff_area = match commit {
+                    Some(commit) => render_commit_message(frame, commit, commit_chunks[1], detail_border, &theme),
+                    None => commit_chunks[1],
+                };
+
                 // Commit diff
-                if let Some(ref diff) = app.commit_diff {
-                    let diff_lines = render_diff_lines(diff, app.diff_scroll as usize, commit_chunks[1].height as usize - 2);
+                if app.commit_diff.is_some() {
+                    let (diff_lines, total) = render_diff_lines(app, &app.commit_diff_view, (diff_area.height as usize).saturating_sub(2));
 
-                    let commit_info = app.pr_commits_state.selected()
-                        .and_then(|i| app.pr_commits.get(i))
+                    let commit_info = commit
                         .map(|c| format!(" {} ", c.short_sha()))
                         .unwrap_or_else(|| " Commit ".to_string());
 
@@ -187,19 +231,26 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
                             Block::default()
                                 .borders(Borders::ALL)
                                 .border_style(detail_border)
-                                .title(format!(" Commit {} [j/k:scroll, p:full diff] ", commit_info)),
+                                .title(format!(
+                                    " Commit {} [{}, {}]{} ",
+                                    commit_info,
+                                    app.keymap.hint(&[Action::NextItem, Action::PrevItem], "scroll"),
+                                    app.keymap.hint(&[Action::ToggleCommits], "full diff"),
+                                    ws_hint(app),
+                                )),
                         )
                         .wrap(Wrap { trim: false });
 
-                    frame.render_widget(diff_widget, commit_chunks[1]);
+                    frame.render_widget(diff_widget, diff_area);
+                    scrollbar::render(frame, diff_area, app.diff_scroll as usize, total, &theme);
                 } else {
                     let placeholder = Paragraph::new("Select a commit to view diff...")
-                        .style(styles::TEXT_DIM)
+                        .style(theme.text_dim)
                         .block(
                             Block::default()
                                 .borders(Borders::ALL)
                                 .border_style(detail_border)
-                                .title(" Commit Diff [p:full diff] "),
+                                .title(format!(" Commit Diff [{}] ", app.keymap.hint(&[Action::ToggleCommits], "full diff"))),
                         );
 
                     frame.render_widget(placeholder, commit_chunks[1]);
@@ -211,7 +262,7 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
         render_pr_checks(frame, app, chunks[3], checks_border);
     } else {
         let placeholder = Paragraph::new("Select a PR to view details")
-            .style(styles::TEXT_DIM)
+            .style(theme.text_dim)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
@@ -224,6 +275,7 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
 }
 
 fn render_commit_list(frame: &mut Frame, app: &mut App, area: Rect, border_style: ratatui::style::Style) {
+    let theme = app.theme;
     let commit_count = app.pr_commits.len();
     let selected_idx = app.pr_commits_state.selected().unwrap_or(0);
 
@@ -233,10 +285,14 @@ fn render_commit_list(frame: &mut Frame, app: &mut App, area: Rect, border_style
         .enumerate()
         .map(|(i, commit)| {
             let marker = if i == selected_idx { ">" } else { " " };
+            let ci = app.commit_ci.get(&commit.sha).and_then(|s| s.as_deref());
             let line = Line::from(vec![
-                Span::styled(marker, styles::TEXT_BOLD),
-                Span::styled(format!(" {} ", commit.short_sha()), styles::DIFF_HEADER),
-                Span::styled(commit.first_line(), styles::TEXT_NORMAL),
+                Span::styled(marker, theme.text_bold),
+                Span::raw(" "),
+                Span::styled(ci_icon(ci), theme.ci_style(ci)),
+                Span::styled(format!(" {} ", commit.short_sha()), theme.diff_header),
+                Span::styled(commit.first_line(), theme.text_normal),
+                Span::styled(format!(" {}", relative_time(&commit.date)), theme.text_dim),
             ]);
             ListItem::new(line)
         })
@@ -251,37 +307,97 @@ fn render_commit_list(frame: &mut Frame, app: &mut App, area: Rect, border_style
                 .border_style(border_style)
                 .title(title),
         )
-        .highlight_style(styles::HIGHLIGHT)
+        .highlight_style(theme.highlight)
         .highlight_symbol("> ");
 
     frame.render_stateful_widget(list, area, &mut app.pr_commits_state);
 }
 
+/// The commit's summary and wrapped body at the top of `area`, taking at
+/// most a third of it. Returns the space left below for the diff.
+fn render_commit_message(
+    frame: &mut Frame,
+    commit: &Commit,
+    area: Rect,
+    border_style: ratatui::style::Style,
+    theme: &Theme,
+) -> Rect {
+    let mut lines = vec![Line::from(Span::styled(commit.first_line().to_string(), theme.text_bold))];
+    let body = commit.body();
+    if !body.is_empty() {
+        lines.push(Line::from(""));
+        lines.extend(body.lines().map(|line| Line::from(Span::styled(line.to_string(), theme.text_normal))));
+    }
+
+    let height = (lines.len() as u16 + 2).min((area.height / 3).max(3));
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(height), Constraint::Min(3)])
+        .split(area);
+
+    let message = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(border_style)
+                .title(format!(" {} · {} ", commit.author, relative_time(&commit.date))),
+        )
+        .wrap(Wrap { trim: false });
+    frame.render_widget(message, chunks[0]);
+
+    chunks[1]
+}
+
 fn render_pr_checks(frame: &mut Frame, app: &mut App, area: Rect, border_style: ratatui::style::Style) {
+    let theme = app.theme;
     if app.pr_checks.is_empty() {
         let placeholder = Paragraph::new("No workflow runs found for this PR")
-            .style(styles::TEXT_DIM)
+            .style(theme.text_dim)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
                     .border_style(border_style)
-                    .title(" CI Checks [Tab:focus, R:rerun, L:logs] "),
+                    .title(format!(
+                        " CI Checks [{}, {}, {}] ",
+                        app.keymap.hint(&[Action::CycleFocus], "focus"),
+                        app.keymap.hint(&[Action::Rerun], "rerun"),
+                        app.keymap.hint(&[Action::ViewLogs], "logs"),
+                    )),
             );
         frame.render_widget(placeholder, area);
         return;
     }
 
     let items: Vec<ListItem> = app
-        .pr_checks
-        .iter()
-        .map(|run| {
+        .pr_check_rows()
+        .into_iter()
+        .map(|row| {
+            let (run, indent) = match row {
+                CheckRow::Group(range) => {
+                    let runs = &app.pr_checks[range];
+                    let name = &runs[0].name;
+                    let status = aggregate_ci_status(runs);
+                    let status_style = theme.ci_style(status.as_deref());
+                    let arrow = if app.collapsed_workflows.contains(name) { "▸" } else { "▾" };
+                    return ListItem::new(Line::from(vec![
+                        Span::styled(format!("{} ", arrow), theme.text_dim),
+                        Span::styled(ci_icon(status.as_deref()), status_style),
+                        Span::raw(" "),
+                        Span::styled(name.clone(), theme.text_bold),
+                        Span::styled(format!(" ({} runs, ", runs.len()), theme.text_dim),
+                        Span::styled(status.unwrap_or_default(), status_style),
+                        Span::styled(")", theme.text_dim),
+                    ]));
+                }
+                CheckRow::Check { index, grouped } => (&app.pr_checks[index], if grouped { "    " } else { "" }),
+            };
             let status_style = match run.conclusion.as_deref() {
-                Some("success") => styles::SUCCESS,
-                Some("failure") => styles::FAILURE,
-                Some("cancelled") | Some("skipped") => styles::NEUTRAL,
+                Some("success") => theme.success,
+                Some("failure") => theme.failure,
+                Some("cancelled") | Some("skipped") => theme.neutral,
                 _ => match run.status.as_str() {
-                    "in_progress" | "queued" => styles::PENDING,
-                    _ => styles::TEXT_NORMAL,
+                    "in_progress" | "queued" => theme.pending,
+                    _ => theme.text_normal,
                 },
             };
 
@@ -289,12 +405,15 @@ fn render_pr_checks(frame: &mut Frame, app: &mut App, area: Rect, border_style:
                 .unwrap_or(&run.status);
 
             let line = Line::from(vec![
+                Span::raw(indent),
                 Span::styled(run.status_icon(), status_style),
                 Span::raw(" "),
-                Span::styled(&run.name, styles::TEXT_NORMAL),
-                Span::styled(" (", styles::TEXT_DIM),
+                Span::styled(run.name.as_str(), theme.text_normal),
+                Span::styled(" (", theme.text_dim),
                 Span::styled(status_text, status_style),
-                Span::styled(")", styles::TEXT_DIM),
+                Span::styled(")", theme.text_dim),
+                Span::raw(" "),
+                Span::styled(run.source.as_str(), theme.text_dim),
             ]);
 
             ListItem::new(line)
@@ -306,40 +425,405 @@ fn render_pr_checks(frame: &mut Frame, app: &mut App, area: Rect, border_style:
             Block::default()
                 .borders(Borders::ALL)
                 .border_style(border_style)
-                .title(" CI Checks [Tab:focus, R:rerun, L:logs] "),
+                .title(format!(
+                    " CI Checks [{}, {}, {}, {}] ",
+                    app.keymap.hint(&[Action::CycleFocus], "focus"),
+                    app.keymap.hint(&[Action::Select], "fold"),
+                    app.keymap.hint(&[Action::Rerun], "rerun"),
+                    app.keymap.hint(&[Action::ViewLogs], "logs"),
+                )),
         )
-        .highlight_style(styles::HIGHLIGHT)
+        .highlight_style(theme.highlight)
         .highlight_symbol("> ");
 
     frame.render_stateful_widget(list, area, &mut app.pr_checks_state);
 }
 
-pub fn render_full_diff(frame: &mut Frame, app: &App, area: Rect) {
-    if let Some(ref diff) = app.pr_diff {
-        let diff_lines = render_diff_lines(diff, app.diff_scroll as usize, area.height as usize - 2);
-
+/// Narrower panes show the unified diff even when the split layout is chosen
+pub const SPLIT_DIFF_MIN_WIDTH: u16 = 100;
+
+pub fn render_full_diff(frame: &mut Frame, app: &mut App, area: Rect) {
+    app.diff_height = area.height.saturating_sub(2);
+    app.diff_width = area.width;
+    let theme = &app.theme;
+    if app.pr_diff.is_some() {
+        let split = app.diff_split_shown() && app.pr_diff_view.held_back.is_none();
+        let toggle = |what| app.keymap.hint(&[Action::ToggleDiffLayout], what);
+        let layout_hint = match (app.diff_layout, split) {
+            (DiffLayout::Unified, _) => format!("[{}]", toggle("split")),
+            (DiffLayout::Split, true) => format!("[{}]", toggle("unified")),
+            (DiffLayout::Split, false) => format!("[{}, too narrow to split]", toggle("unified")),
+        };
         let pr_title = app
             .selected_pr
             .as_ref()
-            .map(|pr| format!(" #{} - {} ", pr.number, pr.title))
+            .map(|pr| diff_title(app, &format!("#{} - {} {}", pr.number, pr.title, layout_hint)))
             .unwrap_or_else(|| " Diff ".to_string());
 
+        if split {
+            render_split_diff(frame, app, &app.pr_diff_view, area, pr_title);
+            return;
+        }
+
+        let (diff_lines, total) = render_diff_lines(app, &app.pr_diff_view, (area.height as usize).saturating_sub(2));
+
         let diff_widget = Paragraph::new(diff_lines)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .border_style(styles::BORDER_ACTIVE)
+                    .border_style(theme.border_active)
                     .title(pr_title),
             )
             .wrap(Wrap { trim: false });
 
         frame.render_widget(diff_widget, area);
+        scrollbar::render(frame, area, app.diff_scroll as usize, total, theme);
+    }
+}
+
+/// Block title with the diff size and review progress appended once they're known
+fn diff_title(app: &App, title: &str) -> String {
+    let viewed = match app.viewed_count() {
+        (_, 0) => String::new(),
+        (viewed, files) => format!(" · viewed {}/{} files", viewed, files),
+    };
+    match app.pr_diff_stat {
+        Some(stat) => format!(" {} ({}{}){} ", title, stat.summary(), viewed, ws_hint(app)),
+        None => format!(" {}{} ", title, ws_hint(app)),
+    }
+}
+
+fn ws_hint(app: &App) -> &'static str {
+    if app.diff_ignore_ws { " [whitespace ignored]" } else { "" }
+}
+
+/// The diff as displayed: with whitespace-only hunks dropped when asked to
+fn shown_diff(diff: &str, ignore_ws: bool) -> Cow<'_, str> {
+    if ignore_ws {
+        Cow::Owned(strip_whitespace_changes(diff))
+    } else {
+        Cow::Borrowed(diff)
+    }
+}
+
+/// Drop hunks whose removed and added lines differ only in whitespace, e.g.
+/// re-indentation. GitHub's diff endpoint has no `-w`, so this stands in for
+/// it. Files left with no hunks are dropped entirely.
+fn strip_whitespace_changes(diff: &str) -> String {
+    let lines: Vec<&str> = diff.lines().collect();
+    let mut kept: Vec<&str> = Vec::new();
+
+    for file in chunks(&lines, |line| line.starts_with("diff --git ")) {
+        let mut parts = chunks(file, |line| line.starts_with("@@")).into_iter();
+        let header = parts.next().unwrap_or_default();
+        let hunks: Vec<&[&str]> = parts.collect();
+        let changed: Vec<&[&str]> = hunks.iter().copied().filter(|hunk| !whitespace_only(hunk)).collect();
+        if !hunks.is_empty() && changed.is_empty() {
+            continue;
+        }
+        kept.extend(header);
+        kept.extend(changed.into_iter().flatten());
+    }
+
+    kept.join("\n")
+}
+
+/// Split `lines` before each line matching `starts`; the first chunk holds
+/// whatever comes before the first match
+fn chunks<'a, 'b>(lines: &'b [&'a str], starts: impl Fn(&str) -> bool) -> Vec<&'b [&'a str]> {
+    let mut bounds: Vec<usize> = std::iter::once(0)
+        .chain((1..lines.len()).filter(|&i| starts(lines[i])))
+        .collect();
+    bounds.push(lines.len());
+    bounds.windows(2).map(|w| &lines[w[0]..w[1]]).collect()
+}
+
+/// A hunk with changes whose removed and added text match once whitespace is ignored
+fn whitespace_only(hunk: &[&str]) -> bool {
+    let squeezed = |sign: char| -> String {
+        hunk.iter()
+            .skip(1)
+            .filter_map(|line| line.strip_prefix(sign))
+            .flat_map(|text| text.chars().filter(|c| !c.is_whitespace()))
+            .collect()
+    };
+    let changes = hunk.iter().skip(1).any(|line| line.starts_with(['+', '-']));
+    changes && squeezed('-') == squeezed('+')
+}
+
+/// Raw diffs longer than this are only styled on request
+const LARGE_DIFF_LINES: usize = 10_000;
+
+/// A diff styled for display. Built when the diff, the whitespace setting or the
+/// viewed files change, so drawing a frame only slices it.
+#[derive(Default)]
+pub struct RenderedDiff {
+    lines: Vec<Line<'static>>,
+    rows: Vec<SplitRow>,
+    /// Line index of each file header, for jumping between files
+    pub file_offsets: Vec<usize>,
+    /// The same among the side-by-side rows
+    pub split_file_offsets: Vec<usize>,
+    /// Names of the files, in the order of the offsets
+    pub files: Vec<String>,
+    /// Line count of a large diff that wasn't styled; everything else is empty then
+    pub held_back: Option<usize>,
+}
+
+impl RenderedDiff {
+    /// Style `diff` as displayed. Past LARGE_DIFF_LINES that only happens with `full`.
+    pub fn new(diff: &str, ignore_ws: bool, viewed: Option<&HashSet<String>>, full: bool, theme: &Theme) -> Self {
+        let line_count = diff.lines().count();
+        if line_count > LARGE_DIFF_LINES && !full {
+            return Self { held_back: Some(line_count), ..Self::default() };
+        }
+
+        let diff = shown_diff(diff, ignore_ws);
+        let (lines, file_offsets) = process_diff(&diff, viewed, theme);
+        let (rows, split_file_offsets) = process_split_diff(&diff, viewed, theme);
+        Self {
+            lines,
+            rows,
+            file_offsets,
+            split_file_offsets,
+            files: diff_file_names(&diff),
+            held_back: None,
+        }
+    }
+
+    /// Indices of the lines, or with `split` the side-by-side rows, containing
+    /// `query`, ignoring case
+    pub fn find(&self, query: &str, split: bool) -> Vec<usize> {
+        let query = query.to_lowercase();
+        if split {
+            self.rows.iter()
+                .enumerate()
+                .filter(|(_, row)| match row {
+                    SplitRow::Full(line) => line_matches(line, &query),
+                    SplitRow::Pair(old, new) => [old, new].into_iter().flatten().any(|line| line_matches(line, &query)),
+                })
+                .map(|(i, _)| i)
+                .collect()
+        } else {
+            self.lines.iter()
+                .enumerate()
+                .filter(|(_, line)| line_matches(line, &query))
+                .map(|(i, _)| i)
+                .collect()
+        }
+    }
+}
+
+/// Whether the line's text contains `query`, which is already lowercase
+fn line_matches(line: &Line, query: &str) -> bool {
+    let text: String = line.spans.iter().map(|span| span.content.as_ref()).collect();
+    text.to_lowercase().contains(query)
+}
+
+/// The line, highlighted if it matches the diff search (lowercase)
+fn highlight_match(line: &Line<'static>, search: Option<&str>, theme: &Theme) -> Line<'static> {
+    match search {
+        Some(query) if line_matches(line, query) => Line::from(
+            line.spans.iter().map(|span| span.clone().patch_style(theme.highlight)).collect::<Vec<_>>(),
+        ),
+        _ => line.clone(),
+    }
+}
+
+/// The visible slice of a diff, plus the total line count for the scrollbar
+fn render_diff_lines(app: &App, diff: &RenderedDiff, height: usize) -> (Text<'static>, usize) {
+    if let Some(line_count) = diff.held_back {
+        let notice = Line::from(Span::styled(
+            format!("Large diff ({} lines), press {} to load it fully", line_count, app.keymap.label(&[Action::LoadFullDiff])),
+            app.theme.pending,
+        ));
+        return (Text::from(notice), 1);
+    }
+
+    let search = app.diff_search.as_ref().map(|s| s.to_lowercase());
+    let visible_lines: Vec<Line> = diff.lines
+        .iter()
+        .skip(app.diff_scroll as usize)
+        .take(height)
+        .map(|line| highlight_match(line, search.as_deref(), &app.theme))
+        .collect();
+
+    (Text::from(visible_lines), diff.lines.len())
+}
+
+/// Names of the diff's files, in the order of its file headers
+fn diff_file_names(diff: &str) -> Vec<String> {
+    diff.lines()
+        .filter(|line| line.starts_with("diff --git "))
+        .filter_map(extract_filename_from_diff_line)
+        .collect()
+}
+
+/// A row of the side-by-side diff
+enum SplitRow {
+    /// Spans both sides: file headers and hunk headers
+    Full(Line<'static>),
+    /// Old and new side of a line; a side is empty where a line was only added or removed
+    Pair(Option<Line<'static>>, Option<Line<'static>>),
+}
+
+/// Old lines on the left, new lines on the right, the visible rows only
+fn render_split_diff(frame: &mut Frame, app: &App, diff: &RenderedDiff, area: Rect, title: String) {
+    let theme = &app.theme;
+    let scroll = app.diff_scroll as usize;
+    let search = app.diff_search.as_ref().map(|s| s.to_lowercase());
+    let highlight = |line: &Line<'static>| highlight_match(line, search.as_deref(), theme);
+    let total = diff.rows.len();
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border_active)
+        .title(title);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Fill(1), Constraint::Length(1), Constraint::Fill(1)])
+        .split(inner);
+
+    let (mut old, mut new) = (Vec::new(), Vec::new());
+    for row in diff.rows.iter().skip(scroll).take(inner.height as usize) {
+        match row {
+            SplitRow::Full(line) => {
+                old.push(highlight(line));
+                new.push(highlight(line));
+            }
+            SplitRow::Pair(left, right) => {
+                old.push(left.as_ref().map(highlight).unwrap_or_default());
+                new.push(right.as_ref().map(highlight).unwrap_or_default());
+            }
+        }
     }
+    let separator: Vec<Line> = (0..old.len()).map(|_| Line::from(Span::styled("│", theme.border_inactive))).collect();
+
+    frame.render_widget(Paragraph::new(old), columns[0]);
+    frame.render_widget(Paragraph::new(separator), columns[1]);
+    frame.render_widget(Paragraph::new(new), columns[2]);
+    scrollbar::render(frame, area, scroll, total, theme);
 }
 
-fn render_diff_lines(diff: &str, scroll: usize, height: usize) -> Text<'static> {
-    // First, process the diff to add file separators
+/// Pair up a diff's lines for the side-by-side view: context on both sides,
+/// a run of removals next to the additions that follow it. Also returns where
+/// each file's header starts among the rows.
+fn process_split_diff(diff: &str, viewed: Option<&HashSet<String>>, theme: &Theme) -> (Vec<SplitRow>, Vec<usize>) {
+    let mut rows: Vec<SplitRow> = Vec::new();
+    let mut file_offsets: Vec<usize> = Vec::new();
+    let (mut removed, mut added): (Vec<Line<'static>>, Vec<Line<'static>>) = (Vec::new(), Vec::new());
+    let (mut old_no, mut new_no) = (0usize, 0usize);
+
+    let numbered = |number: usize, text: &str, style| {
+        Line::from(vec![
+            Span::styled(format!("{:>5} ", number), theme.text_dim),
+            Span::styled(text.to_string(), style),
+        ])
+    };
+    let flush = |rows: &mut Vec<SplitRow>, removed: &mut Vec<Line<'static>>, added: &mut Vec<Line<'static>>| {
+        let len = removed.len().max(added.len());
+        let mut removed = removed.drain(..);
+        let mut added = added.drain(..);
+        rows.extend((0..len).map(|_| SplitRow::Pair(removed.next(), added.next())));
+    };
+
+    for line in diff.lines() {
+        if line.starts_with("diff --git ") {
+            flush(&mut rows, &mut removed, &mut added);
+            if let Some(filename) = extract_filename_from_diff_line(line) {
+                if !file_offsets.is_empty() {
+                    rows.push(SplitRow::Full(Line::from("")));
+                }
+                file_offsets.push(rows.len());
+                rows.extend(file_header(filename, viewed, theme).into_iter().map(SplitRow::Full));
+            }
+            continue;
+        }
+        if line.starts_with("index ") || line.starts_with("+++") || line.starts_with("---") {
+            continue;
+        }
+        if is_binary_notice(line) {
+            flush(&mut rows, &mut removed, &mut added);
+            rows.push(SplitRow::Full(binary_marker(theme)));
+            continue;
+        }
+
+        if let Some(text) = line.strip_prefix('-') {
+            removed.push(numbered(old_no, text, theme.diff_remove));
+            old_no += 1;
+            continue;
+        }
+        if let Some(text) = line.strip_prefix('+') {
+            added.push(numbered(new_no, text, theme.diff_add));
+            new_no += 1;
+            continue;
+        }
+
+        flush(&mut rows, &mut removed, &mut added);
+        if line.starts_with("@@") {
+            (old_no, new_no) = hunk_starts(line).unwrap_or((old_no, new_no));
+            rows.push(SplitRow::Full(Line::from(Span::styled(line.to_string(), theme.diff_hunk))));
+        } else if line.starts_with('\\') {
+            // "\ No newline at end of file"
+            rows.push(SplitRow::Full(Line::from(Span::styled(line.to_string(), theme.text_dim))));
+        } else {
+            let text = line.strip_prefix(' ').unwrap_or(line);
+            rows.push(SplitRow::Pair(
+                Some(numbered(old_no, text, theme.text_normal)),
+                Some(numbered(new_no, text, theme.text_normal)),
+            ));
+            old_no += 1;
+            new_no += 1;
+        }
+    }
+    flush(&mut rows, &mut removed, &mut added);
+    if rows.is_empty() {
+        rows.push(SplitRow::Full(no_changes_marker(theme)));
+    }
+
+    (rows, file_offsets)
+}
+
+/// First old and new line numbers of a hunk: `@@ -12,7 +12,9 @@` gives (12, 12)
+fn hunk_starts(header: &str) -> Option<(usize, usize)> {
+    let mut ranges = header.split_whitespace().skip(1);
+    let start = |range: Option<&str>, sign: char| -> Option<usize> {
+        range?.strip_prefix(sign)?.split(',').next()?.parse().ok()
+    };
+    Some((start(ranges.next(), '-')?, start(ranges.next(), '+')?))
+}
+
+/// The separator-framed file name that replaces git's `diff --git` header,
+/// dimmed and ticked once the file is marked as viewed
+fn file_header(filename: String, viewed: Option<&HashSet<String>>, theme: &Theme) -> [Line<'static>; 3] {
+    let separator = "─".repeat(60);
+    let name = if viewed.is_some_and(|v| v.contains(&filename)) {
+        vec![
+            Span::styled("✓  ", theme.success),
+            Span::styled(filename, theme.text_dim),
+            Span::styled("  (viewed)", theme.text_dim),
+        ]
+    } else {
+        vec![
+            Span::styled(">> ", theme.diff_header),
+            Span::styled(filename, theme.text_bold),
+        ]
+    };
+    [
+        Line::from(Span::styled(separator.clone(), theme.diff_header)),
+        Line::from(name),
+        Line::from(Span::styled(separator, theme.diff_header)),
+    ]
+}
+
+/// Style a raw diff for display, with file separators in place of the git headers.
+/// Also returns where each file's header starts among the processed lines.
+fn process_diff(diff: &str, viewed: Option<&HashSet<String>>, theme: &Theme) -> (Vec<Line<'static>>, Vec<usize>) {
     let mut processed_lines: Vec<Line> = Vec::new();
+    let mut file_offsets: Vec<usize> = Vec::new();
     let mut current_file: Option<String> = None;
 
     for line in diff.lines() {
@@ -353,19 +837,8 @@ fn render_diff_lines(diff: &str, scroll: usize, height: usize) -> Text<'static>
                 }
 
                 // Create a prominent file header
-                let separator = "─".repeat(60);
-                processed_lines.push(Line::from(Span::styled(
-                    separator.clone(),
-                    styles::DIFF_HEADER,
-                )));
-                processed_lines.push(Line::from(vec![
-                    Span::styled(">> ", styles::DIFF_HEADER),
-                    Span::styled(filename.clone(), styles::TEXT_BOLD),
-                ]));
-                processed_lines.push(Line::from(Span::styled(
-                    separator,
-                    styles::DIFF_HEADER,
-                )));
+                file_offsets.push(processed_lines.len());
+                processed_lines.extend(file_header(filename.clone(), viewed, theme));
 
                 current_file = Some(filename);
             }
@@ -382,28 +855,45 @@ fn render_diff_lines(diff: &str, scroll: usize, height: usize) -> Text<'static>
             continue;
         }
 
+        if is_binary_notice(line) {
+            processed_lines.push(binary_marker(theme));
+            continue;
+        }
+
         // Style the remaining lines
         let style = if line.starts_with('+') {
-            styles::DIFF_ADD
+            theme.diff_add
         } else if line.starts_with('-') {
-            styles::DIFF_REMOVE
+            theme.diff_remove
         } else if line.starts_with("@@") {
-            styles::DIFF_HUNK
+            theme.diff_hunk
         } else {
-            styles::TEXT_NORMAL
+            theme.text_normal
         };
 
         processed_lines.push(Line::from(Span::styled(line.to_string(), style)));
     }
 
-    // Apply scroll and height limits
-    let visible_lines: Vec<Line> = processed_lines
-        .into_iter()
-        .skip(scroll)
-        .take(height)
-        .collect();
+    // A blank pane would look like the diff is still loading
+    if processed_lines.is_empty() {
+        processed_lines.push(no_changes_marker(theme));
+    }
+
+    (processed_lines, file_offsets)
+}
+
+/// Git's "Binary files a/x and b/x differ" in place of a binary file's hunks
+fn is_binary_notice(line: &str) -> bool {
+    line.starts_with("Binary files ") && line.ends_with(" differ")
+}
 
-    Text::from(visible_lines)
+fn binary_marker(theme: &Theme) -> Line<'static> {
+    Line::from(Span::styled("📦 binary file (not shown)", theme.text_dim))
+}
+
+/// Shown for a diff without files, e.g. of a merge commit
+fn no_changes_marker(theme: &Theme) -> Line<'static> {
+    Line::from(Span::styled("No textual changes", theme.text_dim))
 }
 
 fn extract_filename_from_diff_line(line: &str) -> Option<String> {
@@ -420,50 +910,71 @@ fn extract_filename_from_diff_line(line: &str) -> Option<String> {
     None
 }
 
-use crate::github::types::PullRequest;
+/// "● checked out" when the PR's branch is the local one, with a warning when
+/// the local branch is at another commit than the PR's head
+fn checkout_spans(app: &App, pr: &PullRequest, theme: &Theme) -> Vec<Span<'static>> {
+    if !app.is_checked_out(pr) {
+        return Vec::new();
+    }
+    let local_sha = app.local_head.as_ref().and_then(|head| head.sha.as_deref());
+    let mut spans = vec![Span::styled("  ● checked out", theme.success)];
+    if let Some(sha) = local_sha.filter(|sha| *sha != pr.head.sha) {
+        spans.push(Span::styled(
+            format!(" (local is at {}, not the PR head)", &sha[..sha.len().min(7)]),
+            theme.pending,
+        ));
+    }
+    spans
+}
+
+/// "Mergeable: ✓ / ✗ conflicts / computing..." for open PRs. GitHub leaves
+/// `mergeable` null until it has test-merged the branch.
+fn mergeable_spans(pr: &PullRequest, theme: &Theme) -> Vec<Span<'static>> {
+    if pr.state != "open" {
+        return Vec::new();
+    }
+    let (text, style) = match pr.mergeable {
+        Some(true) => ("✓", theme.success),
+        Some(false) => ("✗ conflicts", theme.failure),
+        None => ("computing...", theme.text_dim),
+    };
+    vec![
+        Span::styled(" | Mergeable: ", theme.text_dim),
+        Span::styled(text, style),
+    ]
+}
 
 fn build_reviewers_spans(app: &App, pr: &PullRequest) -> Vec<Span<'static>> {
+    let theme = &app.theme;
     let mut spans: Vec<Span<'static>> = Vec::new();
 
-    // Get the latest review per user (GitHub allows multiple reviews)
-    // Use BTreeMap for stable iteration order
-    use std::collections::BTreeMap;
-    let mut latest_reviews: BTreeMap<String, (String, &'static str)> = BTreeMap::new();
-
-    for review in &app.pr_reviews {
-        // PENDING reviews should be ignored unless they're the only review
-        if review.state == "PENDING" {
-            continue;
-        }
-        // Keep the latest non-pending review for each user
-        latest_reviews.insert(
-            review.user.login.clone(),
-            (review.state.clone(), review.status_icon()),
-        );
-    }
+    // The standing review per user (GitHub allows multiple reviews), sorted by username
+    let latest_reviews = latest_reviews(&app.pr_reviews);
 
-    // Show submitted reviews with status (sorted alphabetically by username)
     let mut first = true;
-    for (login, (state, icon)) in &latest_reviews {
+    for (login, review) in &latest_reviews {
         if !first {
-            spans.push(Span::styled(", ", styles::TEXT_DIM));
+            spans.push(Span::styled(", ", theme.text_dim));
         }
         first = false;
 
-        let style = match state.as_str() {
-            "APPROVED" => styles::SUCCESS,
-            "CHANGES_REQUESTED" => styles::FAILURE,
-            "COMMENTED" => styles::NEUTRAL,
-            "DISMISSED" => styles::TEXT_DIM,
-            _ => styles::TEXT_NORMAL,
+        let style = match review.state.as_str() {
+            "APPROVED" => theme.success,
+            "CHANGES_REQUESTED" => theme.failure,
+            "COMMENTED" => theme.neutral,
+            "DISMISSED" => theme.text_dim,
+            _ => theme.text_normal,
         };
 
-        spans.push(Span::styled(format!("{} ", icon), style));
-        spans.push(Span::styled(login.clone(), styles::TEXT_NORMAL));
+        spans.push(Span::styled(format!("{} ", review.status_icon()), style));
+        spans.push(Span::styled(login.to_string(), theme.text_normal));
+        if let Some(submitted_at) = &review.submitted_at {
+            spans.push(Span::styled(format!(" {}", relative_time(submitted_at)), theme.text_dim));
+        }
     }
 
     // Show pending reviewers (requested but haven't reviewed yet)
-    let reviewed_users: std::collections::HashSet<&str> = latest_reviews.keys().map(|s| s.as_str()).collect();
+    let reviewed_users: std::collections::HashSet<&str> = latest_reviews.keys().copied().collect();
     let mut pending_reviewers: Vec<&str> = pr
         .requested_reviewers
         .iter()
@@ -474,16 +985,98 @@ fn build_reviewers_spans(app: &App, pr: &PullRequest) -> Vec<Span<'static>> {
 
     for reviewer in pending_reviewers {
         if !first {
-            spans.push(Span::styled(", ", styles::TEXT_DIM));
+            spans.push(Span::styled(", ", theme.text_dim));
         }
         first = false;
-        spans.push(Span::styled("◯ ", styles::PENDING));
-        spans.push(Span::styled(reviewer.to_string(), styles::TEXT_NORMAL));
+        spans.push(Span::styled("◯ ", theme.pending));
+        spans.push(Span::styled(reviewer.to_string(), theme.text_normal));
+    }
+
+    // Team requests, shown as @org/team; GitHub drops one once a member reviews
+    for team in &pr.requested_teams {
+        if !first {
+            spans.push(Span::styled(", ", theme.text_dim));
+        }
+        first = false;
+        spans.push(Span::styled("◯ ", theme.pending));
+        spans.push(Span::styled(format!("@{}/{}", app.owner, team), theme.text_normal));
     }
 
     if spans.is_empty() {
-        spans.push(Span::styled("None", styles::TEXT_DIM));
+        spans.push(Span::styled("None", theme.text_dim));
+    }
+
+    // Tally of the latest reviews, against what branch protection requires
+    let (approvals, changes) = app.review_tally();
+    let required = app.required_approvals.get(&pr.base.ref_name).copied().flatten();
+    let mut tally = Vec::new();
+    if approvals > 0 || required.is_some() {
+        tally.push(format!("{} approval{}", approvals, if approvals == 1 { "" } else { "s" }));
+    }
+    if changes > 0 {
+        tally.push(format!("{} change{} requested", changes, if changes == 1 { "" } else { "s" }));
+    }
+    if let Some(required) = required {
+        tally.push(format!("needs {}", required));
+    }
+    if !tally.is_empty() {
+        let style = match required {
+            _ if changes > 0 => theme.failure,
+            Some(required) if approvals >= required as usize => theme.success,
+            Some(_) => theme.pending,
+            None => theme.text_dim,
+        };
+        spans.push(Span::styled(format!(" ({})", tally.join(", ")), style));
     }
 
     spans
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REINDENT: &str = "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -1,2 +1,2 @@\n-fn a() {\n-  1\n+fn a() {\n+    1\n }";
+    const REAL: &str = "diff --git a/b.rs b/b.rs\n--- a/b.rs\n+++ b/b.rs\n@@ -1 +1 @@\n-let x = 1;\n+let x = 2;";
+
+    #[test]
+    fn drops_files_with_only_whitespace_changes() {
+        let diff = format!("{}\n{}", REINDENT, REAL);
+        assert_eq!(strip_whitespace_changes(&diff), REAL);
+    }
+
+    #[test]
+    fn keeps_real_hunks_and_files_without_hunks() {
+        let rename = "diff --git a/c.rs b/d.rs\nsimilarity index 100%\nrename from c.rs\nrename to d.rs";
+        assert_eq!(strip_whitespace_changes(REAL), REAL);
+        assert_eq!(strip_whitespace_changes(rename), rename);
+    }
+
+    fn rendered(diff: &str) -> Vec<String> {
+        process_diff(diff, None, &Theme::default()).0.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn marks_binary_files_and_empty_diffs() {
+        let binary = "diff --git a/logo.png b/logo.png\nindex 1..2 100644\nBinary files a/logo.png and b/logo.png differ";
+        assert_eq!(rendered(binary).last().map(String::as_str), Some("📦 binary file (not shown)"));
+        assert_eq!(rendered(""), ["No textual changes"]);
+    }
+
+    #[test]
+    fn finds_lines_in_both_layouts() {
+        let diff = RenderedDiff::new(REAL, false, None, false, &Theme::default());
+        let unified = diff.find("LET X", false);
+        assert_eq!(unified.iter().map(|&i| rendered(REAL)[i].clone()).collect::<Vec<_>>(), ["-let x = 1;", "+let x = 2;"]);
+        // The removed and added line share a side-by-side row
+        assert_eq!(diff.find("let x", true).len(), 1);
+        assert!(diff.find("nothing", false).is_empty());
+    }
+
+    #[test]
+    fn drops_only_the_whitespace_hunk() {
+        let diff = format!("{}\n@@ -9 +9 @@\n-old\n+new", REINDENT);
+        let expected = "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -9 +9 @@\n-old\n+new";
+        assert_eq!(strip_whitespace_changes(&diff), expected);
+    }
+}
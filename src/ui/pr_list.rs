@@ -5,48 +5,102 @@ use ratatui::{
     Frame,
 };
 
-use crate::app::{App, Focus, PrFilter};
+use crate::app::{App, Focus, PrFilter, PrSort};
+use crate::keymap::Action;
+use crate::util::relative_time;
 
-use super::styles;
+use super::text::truncate;
 
-pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
+    app.mouse_areas.pr_list = area;
+    let theme = &app.theme;
     let is_focused = app.focus == Focus::List;
 
     let filter_text = match app.pr_filter {
         PrFilter::All => "All",
         PrFilter::Mine => "Mine",
         PrFilter::ReviewRequested => "Review Requested",
+        PrFilter::Closed => "Closed",
     };
 
-    let title = format!(" PRs ({}) [f:filter] ", filter_text);
+    let sort_text = match app.pr_sort {
+        PrSort::Created => "",
+        PrSort::Updated => ", by updated",
+        PrSort::Number => ", by number",
+        PrSort::Title => ", by title",
+    };
+
+    let query_text = if app.pr_query.is_empty() { String::new() } else { format!(" \"{}\"", app.pr_query) };
+
+    let title = if app.selected_prs.is_empty() {
+        format!(
+            " PRs ({}{}){} [{} {} {}] ",
+            filter_text,
+            sort_text,
+            query_text,
+            app.keymap.hint(&[Action::CycleFilter], "filter"),
+            app.keymap.hint(&[Action::CyclePrSort], "sort"),
+            app.keymap.hint(&[Action::Search], "search"),
+        )
+    } else {
+        format!(
+            " PRs ({}{}){} [{} selected, {}] ",
+            filter_text,
+            sort_text,
+            query_text,
+            app.selected_prs.len(),
+            app.keymap.hint(&[Action::Back], "clear"),
+        )
+    };
 
     let items: Vec<ListItem> = app
         .prs
         .iter()
         .map(|pr| {
-            let style = styles::pr_style(&pr.state, pr.merged, pr.draft);
+            let style = theme.pr_style(&pr.state, pr.merged, pr.draft);
 
-            let line = Line::from(vec![
+            let mut spans = Vec::new();
+            // Checkbox column only while a batch is being picked
+            if !app.selected_prs.is_empty() {
+                let checked = app.selected_prs.contains(&pr.number);
+                spans.push(Span::styled(if checked { "✓ " } else { "  " }, theme.success));
+            }
+            spans.extend([
                 Span::styled(pr.status_icon(), style),
                 Span::raw(" "),
-                Span::styled(format!("#{}", pr.number), styles::TEXT_BOLD),
+                // Checks roll-up; a neutral ○ until it has loaded
+                Span::styled(pr.ci_icon(), theme.ci_style(pr.ci_status.as_deref())),
                 Span::raw(" "),
-                Span::styled(
-                    truncate(&pr.title, (area.width as usize).saturating_sub(20)),
-                    styles::TEXT_NORMAL,
-                ),
+                Span::styled(format!("#{}", pr.number), theme.text_bold),
+                Span::styled(if app.is_checked_out(pr) { " ●" } else { "" }, theme.success),
+                Span::styled(if app.updated_prs.contains_key(&pr.number) { " •" } else { "" }, theme.pending),
                 Span::raw(" "),
-                Span::styled(format!("@{}", pr.user.login), styles::TEXT_DIM),
             ]);
+            let mut tail = vec![
+                Span::raw(" "),
+                Span::styled(format!("@{}", pr.user.login), theme.text_dim),
+                Span::raw(" "),
+                Span::styled(relative_time(&pr.updated_at), theme.text_dim),
+            ];
+            if !pr.labels.is_empty() {
+                tail.push(Span::raw(" "));
+                tail.extend(theme.label_spans(&pr.labels));
+            }
+
+            // The title gets what the other columns leave, so label chips aren't clipped
+            let used: usize = spans.iter().chain(&tail).map(|span| span.width()).sum();
+            let room = (area.width as usize).saturating_sub(used + 2);
+            spans.push(Span::styled(truncate(&pr.title, room), theme.text_normal));
+            spans.extend(tail);
 
-            ListItem::new(line)
+            ListItem::new(Line::from(spans))
         })
         .collect();
 
     let border_style = if is_focused {
-        styles::BORDER_ACTIVE
+        theme.border_active
     } else {
-        styles::BORDER_INACTIVE
+        theme.border_inactive
     };
 
     let list = List::new(items)
@@ -56,17 +110,7 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
                 .border_style(border_style)
                 .title(title),
         )
-        .highlight_style(styles::SELECTED);
+        .highlight_style(theme.selected);
 
-    frame.render_stateful_widget(list, area, &mut app.pr_list_state.clone());
-}
-
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
-    } else if max_len > 3 {
-        format!("{}...", &s[..max_len - 3])
-    } else {
-        s[..max_len].to_string()
-    }
+    frame.render_stateful_widget(list, area, &mut app.pr_list_state);
 }
@@ -1,23 +1,51 @@
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph, Tabs},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Tabs, Wrap},
     Frame,
 };
+use unicode_width::UnicodeWidthStr;
 
 use crate::app::{App, InputMode, Tab, View};
+use crate::config::LoadingStyle;
+use crate::keymap::Action;
 
-use super::{actions_list, help, jobs_view, log_viewer, pr_detail, pr_list, styles};
+use super::styles::Theme;
+use super::{actions_list, help, jobs_view, log_viewer, notifications, pr_detail, pr_list, scrollbar};
+
+/// Smallest terminal the layout fits in; anything smaller only shows a notice
+const MIN_WIDTH: u16 = 40;
+const MIN_HEIGHT: u16 = 10;
 
 pub fn render(frame: &mut Frame, app: &mut App) {
-    // Main layout: header, content, footer
+    // Panes record where they are drawn for mouse handling; hidden ones stay empty
+    app.mouse_areas = Default::default();
+
+    let area = frame.area();
+    if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+        let notice = format!("Terminal too small ({}x{}), needs {}x{}", area.width, area.height, MIN_WIDTH, MIN_HEIGHT);
+        let top = area.height.saturating_sub(1) / 2;
+        let notice_area = Rect { y: area.y + top, height: area.height - top, ..area };
+        frame.render_widget(
+            Paragraph::new(notice)
+                .style(app.theme.error)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            notice_area,
+        );
+        return;
+    }
+
+    // Main layout: header, content, footer. Stacked status messages take a
+    // line each; an error replaces them.
+    let footer_lines = if app.error.is_some() { 1 } else { app.status_messages.len().max(1) as u16 };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3), // Header with tabs
             Constraint::Min(0),    // Content
-            Constraint::Length(2), // Footer with help
+            Constraint::Length(1 + footer_lines), // Footer with help
         ])
         .split(frame.area());
 
@@ -34,51 +62,83 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         render_input(frame, app);
     }
 
+    if app.label_picker.is_some() {
+        render_label_picker(frame, app);
+    }
+
+    if app.workflow_picker.is_some() {
+        render_workflow_picker(frame, app);
+    }
+
+    if app.review_picker.is_some() {
+        render_review_picker(frame, app);
+    }
+
+    if let Some(action) = &app.pending_action {
+        render_confirm(frame, &action.prompt(), &app.theme);
+    }
+
+    // Errors too long for the footer line open in a popup
+    let footer_room = (frame.area().width as usize).saturating_sub(30);
+    app.error_popup = app.error.as_deref().is_some_and(|err| err.contains('\n') || err.width() > footer_room);
+    if app.error_popup {
+        render_error_popup(frame, app);
+    }
+
     // Render description editor overlay
     if app.editing_description {
         render_description_editor(frame, app);
     }
 
-    // Render loading overlay with matrix rain effect
-    if app.loading {
+    // Render loading overlay; the spinner lives in the footer
+    if app.loading && app.config.loading_style.is_overlay() {
         render_loading(frame, app);
     }
 }
 
 fn render_header(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
     let header_chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Min(30), Constraint::Length(30)])
         .split(area);
 
     // Tabs
-    let tab_titles = vec!["[1] PRs", "[2] Actions", "[3] Logs"];
+    let tab_titles = vec!["[1] PRs", "[2] Actions", "[3] Logs", "[4] Notifications"];
     let selected = match app.tab {
         Tab::PRs => 0,
         Tab::Actions => 1,
         Tab::Logs => 2,
+        Tab::Notifications => 3,
     };
 
     let tabs = Tabs::new(tab_titles)
         .select(selected)
-        .style(styles::TAB_INACTIVE)
-        .highlight_style(styles::TAB_ACTIVE)
+        .style(theme.tab_inactive)
+        .highlight_style(theme.tab_active)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(styles::BORDER_ACTIVE)
+                .border_style(theme.border_active)
                 .title(" GitHub TUI "),
         );
 
     frame.render_widget(tabs, header_chunks[0]);
 
-    // Repo info
-    let repo_info = Paragraph::new(app.repo.clone())
-        .style(styles::TEXT_DIM)
+    // Repo info, with a live indicator while auto-refresh is polling
+    let mut repo_spans = vec![Span::styled(app.repo.clone(), theme.text_dim)];
+    if app.auto_refresh {
+        if app.has_active_runs() {
+            repo_spans.push(Span::styled(" ● live", theme.success));
+        } else {
+            repo_spans.push(Span::styled(" ○ watch", theme.text_dim));
+        }
+    }
+    let repo_info = Paragraph::new(Line::from(repo_spans))
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(styles::BORDER_INACTIVE),
+                .border_style(theme.border_inactive),
         );
 
     frame.render_widget(repo_info, header_chunks[1]);
@@ -89,12 +149,14 @@ fn render_content(frame: &mut Frame, app: &mut App, area: Rect) {
         Tab::PRs => render_pr_content(frame, app, area),
         Tab::Actions => render_actions_content(frame, app, area),
         Tab::Logs => log_viewer::render(frame, app, area),
+        Tab::Notifications => notifications::render(frame, app, area),
     }
 }
 
 fn render_pr_content(frame: &mut Frame, app: &mut App, area: Rect) {
     match app.view {
         View::Diff => {
+            app.mouse_areas.diff = area;
             pr_detail::render_full_diff(frame, app, area);
         }
         _ => {
@@ -111,7 +173,7 @@ fn render_pr_content(frame: &mut Frame, app: &mut App, area: Rect) {
 
             // Render the banner if we have a recent branch
             if let (Some(banner_area), Some(branch)) = (banner_area, &app.recent_branch) {
-                render_recent_branch_banner(frame, branch, banner_area);
+                render_recent_branch_banner(frame, branch, banner_area, &app.theme);
             }
 
             // Split into list and detail
@@ -126,23 +188,17 @@ fn render_pr_content(frame: &mut Frame, app: &mut App, area: Rect) {
     }
 }
 
-fn render_recent_branch_banner(frame: &mut Frame, branch: &crate::github::types::RecentBranch, area: Rect) {
-    let time_text = if branch.minutes_ago == 0 {
-        "just now".to_string()
-    } else if branch.minutes_ago == 1 {
-        "1 minute ago".to_string()
-    } else {
-        format!("{} minutes ago", branch.minutes_ago)
-    };
+fn render_recent_branch_banner(frame: &mut Frame, branch: &crate::github::types::RecentBranch, area: Rect, theme: &Theme) {
+    let time_text = crate::util::relative_time(&branch.pushed_at);
 
     let content = Line::from(vec![
         Span::styled("⌥ ", Style::default().fg(Color::Yellow)),
         Span::styled(&branch.name, Style::default().fg(Color::Cyan).add_modifier(ratatui::style::Modifier::BOLD)),
-        Span::styled(" had recent pushes ", styles::TEXT_DIM),
-        Span::styled(&time_text, styles::TEXT_DIM),
-        Span::styled(" │ Press ", styles::TEXT_DIM),
+        Span::styled(" had recent pushes ", theme.text_dim),
+        Span::styled(&time_text, theme.text_dim),
+        Span::styled(" │ Press ", theme.text_dim),
         Span::styled("P", Style::default().fg(Color::Green).add_modifier(ratatui::style::Modifier::BOLD)),
-        Span::styled(" to create PR", styles::TEXT_DIM),
+        Span::styled(" to create PR", theme.text_dim),
     ]);
 
     let banner = Paragraph::new(content)
@@ -156,7 +212,7 @@ fn render_recent_branch_banner(frame: &mut Frame, branch: &crate::github::types:
     frame.render_widget(banner, area);
 }
 
-fn render_actions_content(frame: &mut Frame, app: &App, area: Rect) {
+fn render_actions_content(frame: &mut Frame, app: &mut App, area: Rect) {
     match app.view {
         View::Jobs => {
             let chunks = Layout::default()
@@ -175,79 +231,170 @@ fn render_actions_content(frame: &mut Frame, app: &App, area: Rect) {
 
 fn render_footer(frame: &mut Frame, app: &App, area: Rect) {
     use crate::app::{DiffMode, Focus};
+    let theme = &app.theme;
 
     // Error or message display
-    let status_line = if let Some(ref err) = app.error {
-        Line::from(vec![
-            Span::styled("Error: ", styles::ERROR),
-            Span::styled(err.as_str(), styles::ERROR),
-        ])
-    } else if let Some(ref msg) = app.status_message {
-        Line::from(Span::styled(msg.text(), styles::MESSAGE))
+    let status_lines = if let Some(ref err) = app.error {
+        vec![Line::from(vec![
+            Span::styled("Error: ", theme.error),
+            Span::styled(err.as_str(), theme.error),
+            Span::styled(format!("  {}", app.keymap.hint(&[Action::Back], "dismiss")), theme.text_dim),
+        ])]
+    } else if !app.status_messages.is_empty() {
+        app.status_messages
+            .iter()
+            .map(|msg| Line::from(Span::styled(msg.text(), theme.message)))
+            .collect()
+    } else if app.loading && app.config.loading_style == LoadingStyle::Spinner {
+        const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+        vec![Line::from(vec![
+            Span::styled(SPINNER[app.spinner_frame % SPINNER.len()], theme.pending),
+            Span::raw(" "),
+            Span::styled(app.loading_what.as_deref().unwrap_or("Loading..."), theme.text_dim),
+        ])]
     } else {
-        // Context-sensitive help based on tab, view, and focus
-        let help_text = match app.tab {
+        // Context-sensitive help based on tab, view, and focus, with the keys
+        // from the active keymap
+        use Action::*;
+        let h = |actions: &[Action], what: &str| app.keymap.hint(actions, what);
+        let nav = h(&[NextItem, PrevItem], "nav");
+        let scroll = h(&[NextItem, PrevItem], "scroll");
+        let (help, quit) = (h(&[Help], "help"), h(&[Quit], "quit"));
+        let hints: Vec<String> = match app.tab {
             Tab::PRs => match app.view {
-                View::Diff => "j/k:scroll  PgUp/PgDn:fast  Esc:back  ?:help  q:quit",
+                View::Diff => vec![
+                    scroll, h(&[HalfPageDown, HalfPageUp], "half page"), h(&[PrevFile, NextFile], "prev/next file"),
+                    h(&[ToggleDiffLayout], "split"), h(&[ToggleWhitespace], "whitespace"), h(&[ToggleViewed], "viewed"),
+                    h(&[Back], "back"), help, quit,
+                ],
                 _ => match app.focus {
                     Focus::List => {
-                        if app.selected_pr.is_some() {
-                            "j/k:nav  Enter:detail  o:focus  f:filter  n:new PR  r:refresh  ?:help  q:quit"
+                        if !app.selected_prs.is_empty() {
+                            vec![
+                                h(&[ToggleSelect], "select"), h(&[Approve], "approve selected"),
+                                h(&[Merge], "merge selected"), h(&[Back], "clear"), help, quit,
+                            ]
+                        } else if app.selected_pr.is_some() {
+                            vec![
+                                nav, h(&[Select], "detail"), h(&[CycleFocus], "focus"), h(&[CycleFilter], "filter"),
+                                h(&[NewPr], "new PR"), h(&[Refresh], "refresh"), help, quit,
+                            ]
                         } else {
-                            "j/k:nav  f:filter  n:new PR  r:refresh  ?:help  q:quit"
+                            vec![nav, h(&[CycleFilter], "filter"), h(&[NewPr], "new PR"), h(&[Refresh], "refresh"), help, quit]
                         }
                     }
-                    Focus::Detail => {
-                        match app.diff_mode {
-                            DiffMode::Full => {
-                                "j/k:scroll  p:commits  v:approve  m:merge  e:title  a:reviewer  b:label  d:diff  ?:help"
-                            }
-                            DiffMode::ByCommit => {
-                                "j/k:scroll  [/]:prev/next commit  p:full diff  v:approve  m:merge  ?:help"
-                            }
-                        }
+                    Focus::Description => {
+                        vec![h(&[NextItem, PrevItem], "scroll description"), h(&[EditDescription], "edit"), h(&[CycleFocus], "focus"), help, quit]
                     }
+                    Focus::Detail => match app.diff_mode {
+                        DiffMode::Full => vec![
+                            scroll, h(&[ToggleCommits], "commits"), h(&[Approve], "approve"), h(&[Merge], "merge"),
+                            h(&[EditTitle], "title"), h(&[AddReviewer], "reviewer"), h(&[AddLabel], "label"),
+                            h(&[ViewDiff], "diff"), help,
+                        ],
+                        DiffMode::ByCommit => vec![
+                            scroll, h(&[PrevCommit, NextCommit], "prev/next commit"), h(&[ToggleCommits], "full diff"),
+                            h(&[Approve], "approve"), h(&[Merge], "merge"), help,
+                        ],
+                    },
                     Focus::PrChecks => {
-                        "j/k:nav  Enter/L:jobs  R:rerun  o:focus  ?:help  q:quit"
+                        vec![nav, h(&[Select, ViewLogs], "jobs"), h(&[Rerun], "rerun"), h(&[CycleFocus], "focus"), help, quit]
                     }
                 },
             },
             Tab::Actions => match app.view {
-                View::Jobs => "j/k:nav  Enter/L:logs  R:rerun  Esc:back  ?:help  q:quit",
-                _ => "j/k:nav  Enter:jobs  R:rerun  r:refresh  ?:help  q:quit",
+                View::Jobs => vec![
+                    nav, h(&[Select, ViewLogs], "logs"), h(&[FocusLeft, FocusRight], "jobs/artifacts"),
+                    h(&[DownloadArtifact], "download"), h(&[Rerun], "rerun"), h(&[OpenInBrowser], "browser"),
+                    h(&[Back], "back"), help, quit,
+                ],
+                _ => vec![
+                    nav, h(&[Select], "jobs"), h(&[Rerun], "rerun"), h(&[Dispatch], "dispatch"), h(&[OpenInBrowser], "browser"),
+                    h(&[CycleFilter, FilterRunBranch, CycleRunEvent], "filter"), h(&[Refresh], "refresh"), help, quit,
+                ],
             },
-            Tab::Logs => "j/k:scroll  h/l:pan  g/G:top/bottom  /:search  n/N:match  Esc:back  ?:help  q:quit",
+            Tab::Logs => vec![
+                scroll, h(&[HalfPageDown, HalfPageUp], "half page"), h(&[ScrollLeft, ScrollRight], "pan"),
+                h(&[Top, Bottom], "top/bottom"), h(&[Search], "search"), h(&[NextMatch, PrevMatch], "match"),
+                h(&[GotoLine], "line"), h(&[ToggleLineNumbers], "numbers"), h(&[ToggleWrap], "wrap"),
+                h(&[ToggleFold], "fold"), h(&[ToggleColors], "colors"), h(&[ToggleProblems], "errors"),
+                h(&[Follow], "follow"), h(&[SaveLogs], "save"), h(&[Back], "back"), help, quit,
+            ],
+            Tab::Notifications => vec![
+                nav, h(&[Select], "open"), h(&[OpenInBrowser], "browser"), h(&[MarkRead], "mark read"),
+                h(&[ToggleAllRepos], "all repos"), h(&[Refresh], "refresh"), help, quit,
+            ],
         };
-        Line::from(Span::styled(help_text, styles::TEXT_DIM))
+        vec![Line::from(Span::styled(hints.join("  "), theme.text_dim))]
     };
 
-    let footer = Paragraph::new(status_line).block(
-        Block::default()
-            .borders(Borders::TOP)
-            .border_style(styles::BORDER_INACTIVE),
-    );
+    let block = Block::default()
+        .borders(Borders::TOP)
+        .border_style(if app.error_flash_until.is_some() { theme.error } else { theme.border_inactive });
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    // Remaining API quota on the right, once GitHub has reported it
+    let quota = app.client.as_ref().and_then(|c| c.rate_limit()).map(|limit| {
+        let style = if limit.remaining == 0 {
+            theme.error
+        } else if limit.remaining * 10 < limit.limit {
+            theme.pending
+        } else {
+            theme.text_dim
+        };
+        Span::styled(format!(" API {}/{}", limit.remaining, limit.limit), style)
+    });
+    let quota_width = quota.as_ref().map_or(0, |span| span.width() as u16);
+
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(0), Constraint::Length(quota_width)])
+        .split(inner);
 
-    frame.render_widget(footer, area);
+    frame.render_widget(Paragraph::new(status_lines), chunks[0]);
+    if let Some(quota) = quota {
+        frame.render_widget(Paragraph::new(Line::from(quota)), chunks[1]);
+    }
 }
 
 fn render_input(frame: &mut Frame, app: &App) {
+    let theme = &app.theme;
     let area = centered_rect(60, 3, frame.area());
 
     let title = match app.input_mode {
         Some(InputMode::Search) => " Search ",
+        Some(InputMode::GotoLine) => " Go to Line ",
         Some(InputMode::Comment) => " Comment ",
+        Some(InputMode::ApproveComment) => " Approve ",
+        Some(InputMode::DismissReview) => " Dismiss Review ",
         Some(InputMode::EditTitle) => " Edit PR Title ",
         Some(InputMode::AddLabel) => " Add Label ",
         Some(InputMode::AddReviewer) => " Add Reviewer ",
+        Some(InputMode::AddAssignee) => " Assignee ",
+        Some(InputMode::Command) => " Command ",
+        Some(InputMode::DispatchRef) => " Workflow Ref ",
+        Some(InputMode::DispatchInput) => " Workflow Input ",
+        Some(InputMode::FilterPrs) => " Filter PRs ",
+        Some(InputMode::SearchDiff) => " Search Diff ",
+        Some(InputMode::ApproveDeployment) => " Approve Deployment ",
+        Some(InputMode::RejectDeployment) => " Reject Deployment ",
         None => "",
     };
 
+    // Cursor column in display width - multibyte and wide chars take 1-2 cells,
+    // not their byte length. Long input scrolls so the cursor stays in the box.
+    let inner_width = area.width.saturating_sub(2);
+    let text_width = app.input_buffer.width() as u16;
+    let h_scroll = (text_width + 1).saturating_sub(inner_width);
+
     let input = Paragraph::new(app.input_buffer.as_str())
         .style(Style::default().fg(Color::White))
+        .scroll((0, h_scroll))
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(styles::BORDER_ACTIVE)
+                .border_style(theme.border_active)
                 .title(title),
         );
 
@@ -255,14 +402,140 @@ fn render_input(frame: &mut Frame, app: &App) {
     frame.render_widget(input, area);
 
     // Show cursor
-    frame.set_cursor_position((area.x + app.input_buffer.len() as u16 + 1, area.y + 1));
+    frame.set_cursor_position((area.x + text_width.saturating_sub(h_scroll) + 1, area.y + 1));
+}
+
+fn render_review_picker(frame: &mut Frame, app: &mut App) {
+    let theme = app.theme;
+    let reviews: Vec<ListItem> = app
+        .pr_reviews
+        .iter()
+        .map(|review| {
+            let style = match review.state.as_str() {
+                "APPROVED" => theme.success,
+                "CHANGES_REQUESTED" => theme.failure,
+                _ => theme.text_dim,
+            };
+            let when = review.submitted_at.as_deref().map(crate::util::relative_time).unwrap_or_default();
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{} ", review.status_icon()), style),
+                Span::styled(review.user.login.clone(), theme.text_normal),
+                Span::styled(format!(" {} ", review.state.to_lowercase().replace('_', " ")), style),
+                Span::styled(when, theme.text_dim),
+            ]))
+        })
+        .collect();
+
+    let height = (reviews.len() as u16 + 2).min(frame.area().height.saturating_sub(4));
+    let area = centered_rect(60, height, frame.area());
+
+    let list = List::new(reviews)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(theme.border_active)
+                .title(format!(" Reviews [d:dismiss, r:re-request, {}] ", app.keymap.hint(&[Action::Back], "close"))),
+        )
+        .highlight_style(theme.highlight)
+        .highlight_symbol("> ");
+
+    frame.render_widget(Clear, area);
+    if let Some(ref mut state) = app.review_picker {
+        frame.render_stateful_widget(list, area, state);
+    }
+}
+
+fn render_label_picker(frame: &mut Frame, app: &mut App) {
+    let theme = app.theme;
+    let labels: Vec<ListItem> = app
+        .selected_pr
+        .as_ref()
+        .map(|pr| pr.labels.iter().map(|l| ListItem::new(l.name.clone())).collect())
+        .unwrap_or_default();
+
+    let height = (labels.len() as u16 + 2).min(frame.area().height.saturating_sub(4));
+    let area = centered_rect(40, height, frame.area());
+
+    let list = List::new(labels)
+        .style(theme.text_normal)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(theme.border_active)
+                .title(format!(
+                    " Remove Label [{}, {}] ",
+                    app.keymap.hint(&[Action::Select], "remove"),
+                    app.keymap.hint(&[Action::Back], "cancel"),
+                )),
+        )
+        .highlight_style(theme.highlight)
+        .highlight_symbol("> ");
+
+    frame.render_widget(Clear, area);
+    if let Some(ref mut state) = app.label_picker {
+        frame.render_stateful_widget(list, area, state);
+    }
+}
+
+fn render_workflow_picker(frame: &mut Frame, app: &mut App) {
+    let theme = app.theme;
+    let workflows: Vec<ListItem> = app
+        .workflows
+        .iter()
+        .map(|w| {
+            ListItem::new(Line::from(vec![
+                Span::styled(w.name.clone(), theme.text_normal),
+                Span::raw(" "),
+                Span::styled(w.path.rsplit('/').next().unwrap_or(&w.path).to_string(), theme.text_dim),
+            ]))
+        })
+        .collect();
+
+    let height = (workflows.len() as u16 + 2).min(frame.area().height.saturating_sub(4));
+    let area = centered_rect(60, height, frame.area());
+
+    let list = List::new(workflows)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(theme.border_active)
+                .title(format!(
+                    " Run Workflow [{}, {}] ",
+                    app.keymap.hint(&[Action::Select], "select"),
+                    app.keymap.hint(&[Action::Back], "cancel"),
+                )),
+        )
+        .highlight_style(theme.highlight)
+        .highlight_symbol("> ");
+
+    frame.render_widget(Clear, area);
+    if let Some(ref mut state) = app.workflow_picker {
+        frame.render_stateful_widget(list, area, state);
+    }
+}
+
+fn render_confirm(frame: &mut Frame, prompt: &str, theme: &Theme) {
+    let width = (prompt.chars().count() as u16 + 4).min(frame.area().width);
+    let area = centered_rect(width, 3, frame.area());
+
+    let confirm = Paragraph::new(prompt)
+        .style(theme.text_bold)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(theme.pending)
+                .title(" Confirm "),
+        );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(confirm, area);
 }
 
 fn render_description_editor(frame: &mut Frame, app: &mut App) {
     // Large centered popup for description editing
     let area = frame.area();
-    let popup_width = (area.width * 80 / 100).max(60).min(area.width - 4);
-    let popup_height = (area.height * 70 / 100).max(15).min(area.height - 4);
+    let popup_width = (area.width * 80 / 100).max(60).min(area.width.saturating_sub(4));
+    let popup_height = (area.height * 70 / 100).max(15).min(area.height.saturating_sub(4));
     let popup_area = centered_rect(popup_width, popup_height, area);
 
     frame.render_widget(Clear, popup_area);
@@ -273,6 +546,7 @@ fn render_description_editor(frame: &mut Frame, app: &mut App) {
 }
 
 fn render_loading(frame: &mut Frame, app: &App) {
+    let theme = &app.theme;
     let loading_text = app.loading_what.as_deref().unwrap_or("Loading...");
 
     // Create a centered popup area for the matrix rain effect
@@ -280,7 +554,63 @@ fn render_loading(frame: &mut Frame, app: &App) {
     let popup_height = 15.min(frame.area().height.saturating_sub(4));
     let popup_area = centered_rect(popup_width, popup_height, frame.area());
 
-    app.matrix_rain.render(frame, popup_area, Some(loading_text));
+    if app.config.loading_style == LoadingStyle::Popup {
+        let area = centered_rect(popup_width, 3, frame.area());
+        let popup = Paragraph::new(loading_text)
+            .style(theme.text_normal)
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(theme.border_active),
+            );
+        frame.render_widget(Clear, area);
+        frame.render_widget(popup, area);
+        return;
+    }
+
+    app.matrix_rain.render(frame, popup_area, Some(loading_text), theme);
+}
+
+/// The whole error, wrapped, scrolled by `error_scroll`
+fn render_error_popup(frame: &mut Frame, app: &mut App) {
+    let Some(error) = app.error.as_deref() else {
+        return;
+    };
+    let theme = &app.theme;
+    let area = frame.area();
+    let width = 80u16.min(area.width.saturating_sub(4));
+    let text_width = width.saturating_sub(2).max(1) as usize;
+
+    // Rows once wrapped; close enough for word wrapping to size the popup
+    let total: usize = error.lines().map(|line| line.width().div_ceil(text_width).max(1)).sum();
+    let height = (total as u16 + 2).min(area.height.saturating_sub(4));
+    let viewport = height.saturating_sub(2) as usize;
+    app.error_scroll = app.error_scroll.min(total.saturating_sub(viewport) as u16);
+
+    let popup = centered_rect(width, height, area);
+    let paragraph = Paragraph::new(error.to_string())
+        .style(theme.error)
+        .wrap(Wrap { trim: false })
+        .scroll((app.error_scroll, 0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(theme.error)
+                .title(" Error ")
+                .title_bottom(
+                    Line::from(format!(
+                        " {}  {} ",
+                        app.keymap.hint(&[Action::NextItem, Action::PrevItem], "scroll"),
+                        app.keymap.hint(&[Action::Back], "dismiss"),
+                    ))
+                    .right_aligned(),
+                ),
+        );
+
+    frame.render_widget(Clear, popup);
+    frame.render_widget(paragraph, popup);
+    scrollbar::render(frame, popup, app.error_scroll as usize, total, theme);
 }
 
 fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
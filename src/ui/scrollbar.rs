@@ -0,0 +1,29 @@
+use ratatui::{
+    layout::{Margin, Rect},
+    symbols,
+    widgets::{Scrollbar, ScrollbarOrientation, ScrollbarState},
+    Frame,
+};
+
+use super::styles::Theme;
+
+/// Draw a scrollbar over the right border of a bordered pane showing `position`
+/// within `total` lines. Nothing is drawn when everything fits.
+pub fn render(frame: &mut Frame, area: Rect, position: usize, total: usize, theme: &Theme) {
+    let viewport = area.height.saturating_sub(2) as usize;
+    if total <= viewport {
+        return;
+    }
+
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .symbols(symbols::scrollbar::VERTICAL)
+        .begin_symbol(None)
+        .end_symbol(None)
+        .thumb_style(theme.border_active)
+        .track_style(theme.text_dim);
+    let mut state = ScrollbarState::new(total.saturating_sub(viewport))
+        .position(position)
+        .viewport_content_length(viewport);
+
+    frame.render_stateful_widget(scrollbar, area.inner(Margin { vertical: 1, horizontal: 0 }), &mut state);
+}
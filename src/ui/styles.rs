@@ -1,72 +1,239 @@
+use anyhow::Result;
 use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Span;
+use std::collections::HashMap;
+use std::str::FromStr;
 
-// Tab colors
-pub const TAB_ACTIVE: Style = Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD);
-pub const TAB_INACTIVE: Style = Style::new().fg(Color::DarkGray);
-
-// Status colors
-pub const SUCCESS: Style = Style::new().fg(Color::Green);
-pub const FAILURE: Style = Style::new().fg(Color::Red);
-pub const PENDING: Style = Style::new().fg(Color::Yellow);
-pub const NEUTRAL: Style = Style::new().fg(Color::DarkGray);
-
-// PR state colors
-pub const PR_OPEN: Style = Style::new().fg(Color::Green);
-pub const PR_CLOSED: Style = Style::new().fg(Color::Red);
-pub const PR_MERGED: Style = Style::new().fg(Color::Magenta);
-pub const PR_DRAFT: Style = Style::new().fg(Color::DarkGray);
-
-// Diff colors
-pub const DIFF_ADD: Style = Style::new().fg(Color::Green);
-pub const DIFF_REMOVE: Style = Style::new().fg(Color::Red);
-pub const DIFF_HEADER: Style = Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD);
-pub const DIFF_HUNK: Style = Style::new().fg(Color::Blue);
-
-// Selection
-pub const SELECTED: Style = Style::new()
-    .bg(Color::DarkGray)
-    .add_modifier(Modifier::BOLD);
-pub const HIGHLIGHT: Style = Style::new().bg(Color::Yellow).fg(Color::Black);
-
-// Borders
-pub const BORDER_ACTIVE: Style = Style::new()
-    .fg(Color::LightCyan)
-    .add_modifier(Modifier::BOLD);
-pub const BORDER_INACTIVE: Style = Style::new().fg(Color::Rgb(60, 60, 60));
-
-// Text
-pub const TEXT_NORMAL: Style = Style::new().fg(Color::White);
-pub const TEXT_DIM: Style = Style::new().fg(Color::DarkGray);
-pub const TEXT_BOLD: Style = Style::new()
-    .fg(Color::White)
-    .add_modifier(Modifier::BOLD);
-
-// Error/Message
-pub const ERROR: Style = Style::new().fg(Color::Red).add_modifier(Modifier::BOLD);
-pub const MESSAGE: Style = Style::new().fg(Color::Green);
-
-// Helper to get status style
-pub fn status_style(status: &str, conclusion: Option<&str>) -> Style {
-    match conclusion {
-        Some("success") => SUCCESS,
-        Some("failure") => FAILURE,
-        Some("cancelled") | Some("skipped") => NEUTRAL,
-        _ => match status {
-            "in_progress" | "queued" => PENDING,
-            _ => NEUTRAL,
-        },
+use crate::github::types::Label;
+
+/// All UI colors. Defaults suit a dark terminal; `[theme]` in the config file
+/// overrides individual colors (see `Theme::from_config`).
+#[derive(Clone, Copy)]
+pub struct Theme {
+    // Tab colors
+    pub tab_active: Style,
+    pub tab_inactive: Style,
+
+    // Status colors
+    pub success: Style,
+    pub failure: Style,
+    pub pending: Style,
+    pub neutral: Style,
+
+    // PR state colors
+    pub pr_open: Style,
+    pub pr_closed: Style,
+    pub pr_merged: Style,
+    pub pr_draft: Style,
+
+    // Diff colors
+    pub diff_add: Style,
+    pub diff_remove: Style,
+    pub diff_header: Style,
+    pub diff_hunk: Style,
+
+    // Selection
+    pub selected: Style,
+    pub highlight: Style,
+
+    // Borders
+    pub border_active: Style,
+    pub border_inactive: Style,
+
+    // Text
+    pub text_normal: Style,
+    pub text_dim: Style,
+    pub text_bold: Style,
+    pub link: Style,
+
+    // Error/Message
+    pub error: Style,
+    pub message: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            tab_active: Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            tab_inactive: Style::new().fg(Color::DarkGray),
+
+            success: Style::new().fg(Color::Green),
+            failure: Style::new().fg(Color::Red),
+            pending: Style::new().fg(Color::Yellow),
+            neutral: Style::new().fg(Color::DarkGray),
+
+            pr_open: Style::new().fg(Color::Green),
+            pr_closed: Style::new().fg(Color::Red),
+            pr_merged: Style::new().fg(Color::Magenta),
+            pr_draft: Style::new().fg(Color::DarkGray),
+
+            diff_add: Style::new().fg(Color::Green),
+            diff_remove: Style::new().fg(Color::Red),
+            diff_header: Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            diff_hunk: Style::new().fg(Color::Blue),
+
+            selected: Style::new().bg(Color::DarkGray).add_modifier(Modifier::BOLD),
+            highlight: Style::new().bg(Color::Yellow).fg(Color::Black),
+
+            border_active: Style::new().fg(Color::LightCyan).add_modifier(Modifier::BOLD),
+            border_inactive: Style::new().fg(Color::Rgb(60, 60, 60)),
+
+            text_normal: Style::new().fg(Color::White),
+            text_dim: Style::new().fg(Color::DarkGray),
+            text_bold: Style::new().fg(Color::White).add_modifier(Modifier::BOLD),
+            link: Style::new().fg(Color::Blue).add_modifier(Modifier::UNDERLINED),
+
+            error: Style::new().fg(Color::Red).add_modifier(Modifier::BOLD),
+            message: Style::new().fg(Color::Green),
+        }
+    }
+}
+
+impl Theme {
+    /// Apply `[theme]` overrides: `name = "color"`, where the color is a name
+    /// ("red", "lightcyan", "darkgray"), "#rrggbb" or a 256-color index. Each
+    /// sets the foreground, except `selected` and `highlight` which set the background.
+    pub fn from_config(colors: &HashMap<String, String>) -> Result<Self> {
+        let mut theme = Self::default();
+
+        for (name, value) in colors {
+            let color = Color::from_str(value)
+                .map_err(|_| anyhow::anyhow!("Invalid color '{}' for '{}' in [theme]", value, name))?;
+            let style = match name.as_str() {
+                "tab_active" => &mut theme.tab_active,
+                "tab_inactive" => &mut theme.tab_inactive,
+                "success" => &mut theme.success,
+                "failure" => &mut theme.failure,
+                "pending" => &mut theme.pending,
+                "neutral" => &mut theme.neutral,
+                "pr_open" => &mut theme.pr_open,
+                "pr_closed" => &mut theme.pr_closed,
+                "pr_merged" => &mut theme.pr_merged,
+                "pr_draft" => &mut theme.pr_draft,
+                "diff_add" => &mut theme.diff_add,
+                "diff_remove" => &mut theme.diff_remove,
+                "diff_header" => &mut theme.diff_header,
+                "diff_hunk" => &mut theme.diff_hunk,
+                "selected" => {
+                    theme.selected = theme.selected.bg(color);
+                    continue;
+                }
+                "highlight" => {
+                    theme.highlight = theme.highlight.bg(color);
+                    continue;
+                }
+                "border_active" => &mut theme.border_active,
+                "border_inactive" => &mut theme.border_inactive,
+                "text" => {
+                    theme.text_normal = theme.text_normal.fg(color);
+                    theme.text_bold = theme.text_bold.fg(color);
+                    continue;
+                }
+                "text_dim" => &mut theme.text_dim,
+                "link" => &mut theme.link,
+                "error" => &mut theme.error,
+                "message" => &mut theme.message,
+                _ => anyhow::bail!("Unknown color '{}' in [theme]", name),
+            };
+            *style = style.fg(color);
+        }
+
+        Ok(theme)
+    }
+
+    // Helper to get status style
+    pub fn status_style(&self, status: &str, conclusion: Option<&str>) -> Style {
+        match conclusion {
+            Some("success") => self.success,
+            Some("failure") => self.failure,
+            Some("cancelled") | Some("skipped") => self.neutral,
+            _ => match status {
+                "in_progress" | "queued" => self.pending,
+                _ => self.neutral,
+            },
+        }
+    }
+
+    // Helper to get PR style
+    pub fn pr_style(&self, state: &str, merged: bool, draft: bool) -> Style {
+        if merged {
+            self.pr_merged
+        } else if state == "closed" {
+            self.pr_closed
+        } else if draft {
+            self.pr_draft
+        } else {
+            self.pr_open
+        }
+    }
+
+    // Helper to get aggregate CI status style
+    pub fn ci_style(&self, ci_status: Option<&str>) -> Style {
+        match ci_status {
+            Some("success") => self.success,
+            Some("failure") => self.failure,
+            Some("pending") => self.pending,
+            // ⚠ for cancelled or errored runs: neither passed nor failed
+            Some("error") => self.pending,
+            _ => self.neutral,
+        }
+    }
+
+    // Helper to get a label chip style: label color as background, black or white text for contrast
+    pub fn label_style(&self, hex: &str) -> Style {
+        match parse_hex_color(hex) {
+            Some(Color::Rgb(r, g, b)) => {
+                // Perceived luminance (ITU-R BT.601 weights)
+                let luminance = 0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32;
+                let fg = if luminance > 150.0 { Color::Black } else { Color::White };
+                Style::new().bg(Color::Rgb(r, g, b)).fg(fg)
+            }
+            _ => self.text_normal,
+        }
+    }
+
+    // Render labels as colored chips separated by spaces
+    pub fn label_spans(&self, labels: &[Label]) -> Vec<Span<'static>> {
+        let mut spans = Vec::new();
+        for (i, label) in labels.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::raw(" "));
+            }
+            spans.push(Span::styled(format!(" {} ", label.name), self.label_style(&label.color)));
+        }
+        spans
     }
 }
 
-// Helper to get PR style
-pub fn pr_style(state: &str, merged: bool, draft: bool) -> Style {
-    if merged {
-        PR_MERGED
-    } else if state == "closed" {
-        PR_CLOSED
-    } else if draft {
-        PR_DRAFT
-    } else {
-        PR_OPEN
+// Parse a GitHub hex color like "f29513" (leading '#' optional)
+pub fn parse_hex_color(hex: &str) -> Option<Color> {
+    let hex = hex.trim_start_matches('#');
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
+    let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
+    let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
+    Some(Color::Rgb(r, g, b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_hex_color;
+    use ratatui::style::Color;
+
+    #[test]
+    fn parses_github_label_colors() {
+        assert_eq!(parse_hex_color("f29513"), Some(Color::Rgb(0xf2, 0x95, 0x13)));
+        assert_eq!(parse_hex_color("#0E8A16"), Some(Color::Rgb(0x0e, 0x8a, 0x16)));
+    }
+
+    #[test]
+    fn rejects_malformed_colors() {
+        assert_eq!(parse_hex_color(""), None);
+        assert_eq!(parse_hex_color("fff"), None);
+        assert_eq!(parse_hex_color("gg0000"), None);
+        assert_eq!(parse_hex_color("f2951"), None);
+        assert_eq!(parse_hex_color("ää00"), None);
     }
 }
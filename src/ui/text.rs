@@ -0,0 +1,69 @@
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Shorten `s` to at most `max_width` terminal columns, ending in "..." when cut.
+/// Works on display width, so emoji and CJK (two columns each) never get split.
+pub fn truncate(s: &str, max_width: usize) -> String {
+    if s.width() <= max_width {
+        return s.to_string();
+    }
+
+    let (budget, ellipsis) = if max_width > 3 { (max_width - 3, "...") } else { (max_width, "") };
+    let mut out = String::new();
+    let mut width = 0;
+    for c in s.chars() {
+        let w = c.width().unwrap_or(0);
+        if width + w > budget {
+            break;
+        }
+        width += w;
+        out.push(c);
+    }
+    out.push_str(ellipsis);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_short_strings() {
+        assert_eq!(truncate("Fix login", 20), "Fix login");
+        assert_eq!(truncate("", 5), "");
+    }
+
+    #[test]
+    fn truncates_ascii() {
+        assert_eq!(truncate("Refactor the parser", 10), "Refacto...");
+        assert_eq!(truncate("abcdef", 3), "abc");
+    }
+
+    #[test]
+    fn truncates_emoji_titles() {
+        // Each emoji is 4 bytes and 2 columns wide
+        assert_eq!(truncate("🚀 Release v2.0 with new features", 12), "🚀 Releas...");
+        assert_eq!(truncate("🐛🐛🐛🐛🐛", 8), "🐛🐛...");
+        assert_eq!(truncate("🐛🐛🐛", 3), "🐛");
+    }
+
+    #[test]
+    fn truncates_cjk_titles() {
+        assert_eq!(truncate("修复登录页面的错误", 10), "修复登...");
+        assert_eq!(truncate("修复登录页面的错误", 9), "修复登...");
+        assert_eq!(truncate("日本語のタイトル", 16), "日本語のタイトル");
+    }
+
+    #[test]
+    fn truncates_accented_chars() {
+        assert_eq!(truncate("Ünïcödé çhåråctérs", 8), "Ünïcö...");
+    }
+
+    #[test]
+    fn never_exceeds_width() {
+        for title in ["✨ Add 日本語 support", "é".repeat(50).as_str(), "👍🏽 thumbs"] {
+            for max in 0..30 {
+                assert!(truncate(title, max).width() <= max, "{title:?} at {max}");
+            }
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use chrono::{DateTime, Utc};
+
+use crate::github::types::parse_timestamp;
+
+/// Render a GitHub timestamp relative to now: "just now", "5m ago", "3h ago",
+/// "yesterday", "5d ago", or the date once it's over a month old.
+/// Falls back to the raw string if it can't be parsed.
+pub fn relative_time(timestamp: &str) -> String {
+    match parse_timestamp(timestamp) {
+        Some(time) => format_relative(time, Utc::now()),
+        None => timestamp.to_string(),
+    }
+}
+
+/// Whether `repo` is in `owner/name` form
+pub fn is_valid_repo(repo: &str) -> bool {
+    matches!(repo.split_once('/'), Some((owner, name)) if !owner.is_empty() && !name.is_empty() && !name.contains('/'))
+}
+
+/// `http(s)://` URLs in a line of text, without trailing punctuation
+pub fn find_urls(line: &str) -> Vec<&str> {
+    let mut urls = Vec::new();
+    let mut rest = line;
+
+    while let Some(start) = rest.find("http") {
+        let candidate = &rest[start..];
+        let word_start = !rest[..start].chars().last().is_some_and(|c| c.is_alphanumeric());
+        let scheme = ["https://", "http://"].into_iter().find(|s| candidate.starts_with(s));
+        match scheme {
+            Some(scheme) if word_start && url_len(candidate) > scheme.len() => {
+                let len = url_len(candidate);
+                urls.push(&candidate[..len]);
+                rest = &candidate[len..];
+            }
+            _ => rest = &candidate[4..],
+        }
+    }
+
+    urls
+}
+
+/// Length of the URL at the start of `text`, without trailing punctuation
+pub fn url_len(text: &str) -> usize {
+    let end = text
+        .find(|c: char| c.is_whitespace() || matches!(c, '<' | '>' | '"' | '`'))
+        .unwrap_or(text.len());
+    text[..end].trim_end_matches(['.', ',', ';', ':', '!', '?', ')', ']', '\'']).len()
+}
+
+fn format_relative(time: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let elapsed = now.signed_duration_since(time);
+    let minutes = elapsed.num_minutes();
+    let hours = elapsed.num_hours();
+    let days = elapsed.num_days();
+
+    if minutes < 1 {
+        // Also covers slight clock skew putting the time in the future
+        "just now".to_string()
+    } else if hours < 1 {
+        format!("{}m ago", minutes)
+    } else if days < 1 {
+        format!("{}h ago", hours)
+    } else if days == 1 {
+        "yesterday".to_string()
+    } else if days < 30 {
+        format!("{}d ago", days)
+    } else {
+        time.format("%Y-%m-%d").to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::find_urls;
+
+    #[test]
+    fn finds_urls_in_text() {
+        assert_eq!(
+            find_urls("Report: https://ci.example.com/run/1, artifacts at http://x.io/a?b=c."),
+            ["https://ci.example.com/run/1", "http://x.io/a?b=c"]
+        );
+        assert_eq!(find_urls("see the docs (https://example.com/docs)"), ["https://example.com/docs"]);
+    }
+
+    #[test]
+    fn ignores_non_urls() {
+        assert!(find_urls("no links here, just http and https").is_empty());
+        assert!(find_urls("https:// alone").is_empty());
+        assert!(find_urls("xhttps://example.com").is_empty());
+    }
+}